@@ -19,4 +19,21 @@ pub struct BlockchainConfig {
     pub chain_start_timestamp: u32,
     pub check_validator: bool,
     pub max_validator_commision: u8,
+    /// Maximum change of a validator's commision per epoch, n parts out of 255
+    /// parts
+    pub max_commision_change: u8,
+    /// Number of epochs a commision change is announced before it is charged,
+    /// at least 1
+    pub commision_change_delay: u32,
+    /// Number of epochs undelegated funds stay locked before they can be
+    /// reclaimed, so that they can still be slashed
+    pub unbonding_epochs: u32,
+    /// Share of the stake burnt when a validator double-signs, n parts out of
+    /// 255 parts
+    pub slashing_ratio: u8,
+    pub tx_history: bool,
+    pub max_reorg_depth: u64,
+    /// When set, block bodies, rollback diffs and compressed-state history
+    /// older than this many blocks are deleted. Headers are always kept.
+    pub pruning: Option<u64>,
 }
@@ -12,6 +12,8 @@ pub enum BlockchainError {
     SignatureError,
     #[error("balance insufficient")]
     BalanceInsufficient,
+    #[error("allowance insufficient")]
+    AllowanceInsufficient,
     #[error("contract balance insufficient")]
     ContractBalanceInsufficient,
     #[error("inconsistency error")]
@@ -22,10 +24,16 @@ pub enum BlockchainError {
     ExtendFromGenesis,
     #[error("cannot extend from very future blocks")]
     ExtendFromFuture,
+    #[error("cannot rollback deeper than the maximum reorg depth")]
+    ReorgTooDeep,
+    #[error("fork is not heavier than the current chain")]
+    WeakerFork,
     #[error("block number invalid")]
     InvalidBlockNumber,
     #[error("parent hash invalid")]
     InvalidParentHash,
+    #[error("header is not signed by its validator")]
+    InvalidHeaderSignature,
     #[error("merkle root invalid")]
     InvalidMerkleRoot,
     #[error("transaction nonce invalid")]
@@ -62,6 +70,12 @@ pub enum BlockchainError {
     CompressedStateNotFound,
     #[error("no blocks to roll back")]
     NoBlocksToRollback,
+    #[error("cannot roll back past the pruning window")]
+    RollbackPruned,
+    #[error("block has been pruned")]
+    BlockPruned,
+    #[error("compressed-state at specified height has been pruned")]
+    CompressedStatePruned,
     #[error("zk error happened: {0}")]
     ZkError(#[from] ZkError),
     #[error("state-manager error happened: {0}")]
@@ -98,8 +112,10 @@ pub enum BlockchainError {
     TokenSupplyOverflow,
     #[error("token has an invalid name/symbol")]
     TokenBadNameSymbol,
-    #[error("only ziesha fees are accepted!")]
-    OnlyZieshaFeesAccepted,
+    #[error("token has invalid metadata")]
+    TokenBadMetadata,
+    #[error("token supply exceeds its max supply")]
+    TokenSupplyCapExceeded,
     #[error("transaction memo is too long")]
     MemoTooLong,
     #[error("Wrong validator has built the block!")]
@@ -112,6 +128,10 @@ pub enum BlockchainError {
     SingleUpdateAllowedPerContract,
     #[error("validator is not registered")]
     ValidatorNotRegistered,
+    #[error("validator commision is higher than allowed")]
+    InvalidCommision,
+    #[error("validator commision change is larger than allowed")]
+    CommisionChangeTooLarge,
     #[error("blockchain is empty")]
     BlockchainEmpty,
     #[error("invalid number of rollbacks in the patch")]
@@ -120,4 +140,44 @@ pub enum BlockchainError {
     ParseDbKeyError(#[from] ParseDbKeyError),
     #[error("mpn-address cannot be used")]
     MpnAddressCannotBeUsed,
+    #[error("transaction history is not indexed on this node")]
+    TxHistoryDisabled,
+    #[error("transaction history page is out of range")]
+    TxHistoryPageOutOfRange,
+    #[error("snapshot height is invalid")]
+    InvalidSnapshotHeight,
+    #[error("snapshot version is not supported")]
+    SnapshotVersionMismatch,
+    #[error("snapshot checksum does not match its data")]
+    SnapshotChecksumMismatch,
+    #[error("database is not empty")]
+    DatabaseNotEmpty,
+    #[error("snapshot io error happened: {0}")]
+    SnapshotIoError(#[from] std::io::Error),
+    #[error("snapshot serialization error happened: {0}")]
+    SnapshotSerializationError(#[from] bincode::Error),
+    #[error("multisig policy is invalid")]
+    InvalidMultisigPolicy,
+    #[error("multisig policy already registered")]
+    MultisigAlreadyRegistered,
+    #[error("multisig policy not registered for the account")]
+    MultisigNotRegistered,
+    #[error("multisig threshold not met")]
+    MultisigThresholdNotMet,
+    #[error("vesting schedule is invalid")]
+    InvalidVesting,
+    #[error("vesting not found")]
+    VestingNotFound,
+    #[error("only the beneficiary can claim the vesting")]
+    NotVestingBeneficiary,
+    #[error("nothing is released from the vesting yet")]
+    NothingToClaim,
+    #[error("no undelegated funds to reclaim")]
+    UnbondingNotFound,
+    #[error("undelegated funds are still in their unbonding period")]
+    UnbondingInProgress,
+    #[error("slashing evidence is invalid")]
+    InvalidSlashingEvidence,
+    #[error("validator is already slashed for the given slot")]
+    ValidatorAlreadySlashed,
 }
@@ -1,7 +1,7 @@
-use super::{Blockchain, BlockchainError, TransactionStats};
+use super::{Blockchain, BlockchainError, TransactionRemoval, TransactionStats};
 use crate::core::{
-    Address, Amount, GeneralTransaction, MpnDeposit, MpnWithdraw, NonceGroup, Signature,
-    TransactionAndDelta,
+    hash::Hash, Amount, GeneralTransaction, Hasher, Money, MpnDeposit, MpnWithdraw, NonceGroup,
+    Signature, TokenId, TransactionAndDelta,
 };
 use crate::db::KvStore;
 use crate::zk::MpnTransaction;
@@ -44,6 +44,52 @@ impl Nonced for TransactionAndDelta {
     }
 }
 
+/// Price of a token in Ziesha, as the fraction `num / den`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenPrice {
+    num: u64,
+    den: u64,
+}
+
+impl TokenPrice {
+    pub fn new(num: u64, den: u64) -> Option<Self> {
+        (den != 0).then_some(Self { num, den })
+    }
+}
+
+/// Custom tokens this node accepts as tx fees, along with their prices. This
+/// is a local policy of the node and not part of consensus: Ziesha is always
+/// accepted and txs paying their fees in tokens not listed here are not
+/// selected.
+#[derive(Debug, Clone, Default)]
+pub struct FeeTokenPrices(HashMap<TokenId, TokenPrice>);
+
+impl FeeTokenPrices {
+    pub fn new(prices: HashMap<TokenId, TokenPrice>) -> Self {
+        Self(prices)
+    }
+    /// Value of the given fee in Ziesha, or `None` if the token is not accepted
+    pub fn fee_in_ziesha(&self, fee: &Money) -> Option<Amount> {
+        if fee.token_id == TokenId::Ziesha {
+            Some(fee.amount)
+        } else {
+            self.0.get(&fee.token_id).map(|price| {
+                let value = u64::from(fee.amount) as u128 * price.num as u128 / price.den as u128;
+                Amount(u64::try_from(value).unwrap_or(u64::MAX))
+            })
+        }
+    }
+}
+
+// Fees paid in tokens not accepted by the node are worth nothing
+fn ziesha_fee(prices: &FeeTokenPrices, tx: &GeneralTransaction) -> u64 {
+    prices.fee_in_ziesha(&tx.fee()).unwrap_or_default().into()
+}
+
+fn fee_per_byte(prices: &FeeTokenPrices, tx: &GeneralTransaction) -> f64 {
+    ziesha_fee(prices, tx) as f64 / tx.size() as f64
+}
+
 #[derive(Debug, Clone)]
 pub struct SingleMempool {
     nonce: u32,
@@ -81,6 +127,21 @@ impl SingleMempool {
             self.txs.push_back((tx, stats));
         }
     }
+    /// Replaces the tx with the same nonce, in case the new tx pays a higher fee.
+    /// Returns the replaced tx.
+    fn replace(
+        &mut self,
+        prices: &FeeTokenPrices,
+        tx: GeneralTransaction,
+        stats: TransactionStats,
+    ) -> Option<(GeneralTransaction, TransactionStats)> {
+        let pos = self.txs.iter().position(|(t, _)| t.nonce() == tx.nonce())?;
+        if ziesha_fee(prices, &tx) > ziesha_fee(prices, &self.txs[pos].0) {
+            Some(std::mem::replace(&mut self.txs[pos], (tx, stats)))
+        } else {
+            None
+        }
+    }
     fn update_nonce(&mut self, nonce: u32) {
         while let Some(first_nonce) = self.first_nonce() {
             if first_nonce <= nonce {
@@ -115,14 +176,22 @@ impl SingleMempool {
 #[derive(Clone, Debug)]
 pub struct Mempool {
     min_balance_per_tx: Amount,
+    max_size: usize,
+    fee_token_prices: FeeTokenPrices,
     txs: HashMap<NonceGroup, SingleMempool>,
     rejected: HashMap<GeneralTransaction, TransactionStats>,
 }
 
 impl Mempool {
-    pub fn new(min_balance_per_tx: Amount) -> Self {
+    pub fn new(
+        min_balance_per_tx: Amount,
+        max_size: usize,
+        fee_token_prices: FeeTokenPrices,
+    ) -> Self {
         Self {
             min_balance_per_tx,
+            max_size,
+            fee_token_prices,
             txs: Default::default(),
             rejected: Default::default(),
         }
@@ -134,9 +203,9 @@ impl Mempool {
     pub fn refresh<K: KvStore, B: Blockchain<K>>(
         &mut self,
         blockchain: &B,
-        _local_ts: u32,
+        local_ts: u32,
         _max_time_alive: Option<u32>,
-        _max_time_remember: Option<u32>,
+        max_time_remember: Option<u32>,
     ) -> Result<(), BlockchainError> {
         let mpn_contract_id = blockchain.config().mpn_config.mpn_contract_id;
         for (ng, mempool) in self.txs.iter_mut() {
@@ -150,10 +219,70 @@ impl Mempool {
             };
             mempool.update_nonce(nonce);
         }
+        if let Some(max_time_remember) = max_time_remember {
+            self.rejected
+                .retain(|_, stats| local_ts.saturating_sub(stats.first_seen) < max_time_remember);
+        }
         Ok(())
     }
-    pub fn chain_address_limit(&self, _addr: Address) -> usize {
-        100
+    pub fn sender_limit<K: KvStore, B: Blockchain<K>>(
+        &self,
+        blockchain: &B,
+        nonce_group: &NonceGroup,
+    ) -> Result<usize, BlockchainError> {
+        let ziesha_balance: u64 = match nonce_group {
+            NonceGroup::TransactionAndDelta(addr) | NonceGroup::MpnDeposit(addr) => blockchain
+                .get_balance(addr.clone(), TokenId::Ziesha)?
+                .into(),
+            NonceGroup::MpnTransaction(addr) | NonceGroup::MpnWithdraw(addr) => blockchain
+                .get_mpn_account(addr.clone())?
+                .tokens
+                .values()
+                .filter(|m| m.token_id == TokenId::Ziesha)
+                .map(|m| u64::from(m.amount))
+                .sum(),
+        };
+
+        // Allow 1tx in mempool per `min_balance_per_tx` Ziesha
+        // Min: 1 Max: 1000
+        Ok((ziesha_balance / self.min_balance_per_tx.0).clamp(1, 1000) as usize)
+    }
+    fn remove(
+        &mut self,
+        tx: GeneralTransaction,
+        mut stats: TransactionStats,
+        reason: TransactionRemoval,
+    ) {
+        stats.removed = Some(reason);
+        self.rejected.insert(tx, stats);
+    }
+    /// Evicts the tx with the lowest fee-per-byte among the last txs of the other
+    /// senders. Local txs are never evicted. Returns false if there is nothing
+    /// cheaper than `min_fee_per_byte` to evict.
+    fn evict(&mut self, except: &NonceGroup, min_fee_per_byte: Option<f64>) -> bool {
+        let cheapest = self
+            .txs
+            .iter()
+            .filter(|(ng, _)| *ng != except)
+            .filter_map(|(ng, all)| all.txs.back().map(|(tx, stats)| (ng, tx, stats)))
+            .filter(|(_, _, stats)| !stats.is_local)
+            .map(|(ng, tx, _)| (ng.clone(), fee_per_byte(&self.fee_token_prices, tx)))
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        match cheapest {
+            Some((ng, fpb)) if min_fee_per_byte.map(|min| fpb < min).unwrap_or(true) => {
+                if let Some((tx, stats)) = self.txs.get_mut(&ng).and_then(|all| all.txs.pop_back())
+                {
+                    log::info!(
+                        "{} evicted its transaction on nonce {}",
+                        tx.sender(),
+                        tx.nonce()
+                    );
+                    self.remove(tx, stats, TransactionRemoval::Evicted);
+                }
+                true
+            }
+            _ => false,
+        }
     }
     pub fn add_tx<K: KvStore, B: Blockchain<K>>(
         &mut self,
@@ -169,64 +298,66 @@ impl Mempool {
         if self.rejected.contains_key(&tx) || !tx.verify_signature() {
             return Ok(());
         }
-        let nonce = match tx.nonce_group() {
+        let nonce_group = tx.nonce_group();
+        let nonce = match nonce_group.clone() {
             NonceGroup::TransactionAndDelta(addr) => blockchain.get_nonce(addr)?,
             NonceGroup::MpnDeposit(addr) => blockchain.get_deposit_nonce(addr, mpn_contract_id)?,
             NonceGroup::MpnTransaction(addr) => blockchain.get_mpn_account(addr)?.tx_nonce,
             NonceGroup::MpnWithdraw(addr) => blockchain.get_mpn_account(addr)?.withdraw_nonce,
         };
-        if self
-            .txs
-            .get_mut(&tx.nonce_group())
-            .map(|all| {
-                all.update_nonce(nonce);
-                if is_local && !all.applicable(&tx) {
-                    all.reset(tx.nonce());
-                }
-                if let Some((first_tx, stats)) = all.first_tx() {
-                    // TODO: config.replace_tx_threshold instead of 60
-                    if now > stats.first_seen + 60 && first_tx != &tx {
-                        log::info!(
-                            "{} replaced its transaction on nonce {}",
-                            tx.sender(),
-                            tx.nonce()
-                        );
-                        all.reset(tx.nonce());
-                    }
-                }
-                !all.applicable(&tx)
-            })
-            .unwrap_or_default()
-        {
-            return Ok(());
-        }
 
         // Do not accept old txs in the mempool
         if tx.nonce() <= nonce {
             return Ok(());
         }
 
-        let count_limit = false;
-        /*let ziesha_balance = blockchain.get_balance(tx.sender(), TokenId::Ziesha)?;
-
-        // Allow 1tx in mempool per Ziesha
-        // Min: 1 Max: 1000
-        let limit = std::cmp::max(
-            std::cmp::min(
-                Into::<u64>::into(ziesha_balance) / self.min_balance_per_tx.0,
-                1000,
-            ),
-            1,
-        ) as usize;*/
+        let limit = self.sender_limit(blockchain, &nonce_group)?;
 
         let all = self
             .txs
-            .entry(tx.nonce_group().clone())
+            .entry(nonce_group.clone())
             .or_insert(SingleMempool::new(nonce));
+        all.update_nonce(nonce);
+        if is_local && !all.applicable(&tx) {
+            all.reset(tx.nonce());
+        }
 
-        if is_local || !count_limit {
-            all.insert(tx.clone(), TransactionStats::new(is_local, now));
+        if !all.applicable(&tx) {
+            // Replace-by-fee
+            if let Some((replaced, stats)) = all.replace(
+                &self.fee_token_prices,
+                tx.clone(),
+                TransactionStats::new(is_local, now),
+            ) {
+                log::info!(
+                    "{} replaced its transaction on nonce {}",
+                    tx.sender(),
+                    tx.nonce()
+                );
+                self.remove(replaced, stats, TransactionRemoval::Replaced);
+            }
+            return Ok(());
         }
+
+        if !is_local && all.len() >= limit {
+            return Ok(());
+        }
+
+        let min_fee_per_byte = if is_local {
+            None
+        } else {
+            Some(fee_per_byte(&self.fee_token_prices, &tx))
+        };
+        while self.len() >= self.max_size {
+            if !self.evict(&nonce_group, min_fee_per_byte) {
+                return Ok(());
+            }
+        }
+
+        self.txs
+            .entry(nonce_group)
+            .or_insert(SingleMempool::new(nonce))
+            .insert(tx, TransactionStats::new(is_local, now));
         Ok(())
     }
     pub fn all(&self) -> impl Iterator<Item = &(GeneralTransaction, TransactionStats)> {
@@ -307,6 +438,34 @@ impl Mempool {
     pub fn len(&self) -> usize {
         self.txs.values().map(|c| c.len()).sum()
     }
+    pub fn rejected_len(&self) -> usize {
+        self.rejected.len()
+    }
+    pub fn contains(&self, tx: &GeneralTransaction) -> bool {
+        self.txs
+            .get(&tx.nonce_group())
+            .map(|c| c.txs.iter().any(|(t, _)| t == tx))
+            .unwrap_or(false)
+    }
+    pub fn get_tx(
+        &self,
+        tx_hash: &<Hasher as Hash>::Output,
+    ) -> Option<(&TransactionAndDelta, &TransactionStats)> {
+        self.tx_deltas().find(|(tx, _)| &tx.tx.hash() == tx_hash)
+    }
+    pub fn get_rejected_tx(
+        &self,
+        tx_hash: &<Hasher as Hash>::Output,
+    ) -> Option<(&TransactionAndDelta, &TransactionStats)> {
+        self.rejected.iter().find_map(|(t, s)| {
+            if let GeneralTransaction::TransactionAndDelta(tx) = t {
+                if &tx.tx.hash() == tx_hash {
+                    return Some((tx, s));
+                }
+            }
+            None
+        })
+    }
 }
 
 #[cfg(test)]
@@ -318,15 +477,27 @@ mod tests {
     use crate::wallet::TxBuilder;
 
     fn dummy_tx(wallet: &TxBuilder, nonce: u32) -> GeneralTransaction {
+        dummy_tx_with_fee(wallet, nonce, 0)
+    }
+
+    fn dummy_tx_with_fee(wallet: &TxBuilder, nonce: u32, fee: u64) -> GeneralTransaction {
         GeneralTransaction::TransactionAndDelta(wallet.create_transaction(
             "".into(),
             wallet.get_address(),
             Money::ziesha(200),
-            Money::ziesha(0),
+            Money::ziesha(fee),
             nonce,
         ))
     }
 
+    fn tx_hash(tx: &GeneralTransaction) -> <Hasher as Hash>::Output {
+        if let GeneralTransaction::TransactionAndDelta(tx_delta) = tx {
+            tx_delta.tx.hash()
+        } else {
+            unreachable!()
+        }
+    }
+
     #[test]
     fn test_mempool_check_correct_account_nonce() {
         let chain = KvStoreChain::new(
@@ -337,7 +508,7 @@ mod tests {
         let abc = TxBuilder::new(&Vec::from("ABC"));
 
         for i in 0..5 {
-            let mut mempool = Mempool::new(Amount(1));
+            let mut mempool = Mempool::new(Amount(1), 1000, Default::default());
             mempool.add_tx(&chain, dummy_tx(&abc, i), false, 0).unwrap();
 
             let snapshot = mempool.all().collect::<Vec<_>>();
@@ -355,7 +526,7 @@ mod tests {
         .unwrap();
         let abc = TxBuilder::new(&Vec::from("ABC"));
         let other = TxBuilder::new(&Vec::from("DELEGATOR"));
-        let mut mempool = Mempool::new(Amount(1));
+        let mut mempool = Mempool::new(Amount(1), 1000, Default::default());
 
         mempool.add_tx(&chain, dummy_tx(&abc, 1), false, 0).unwrap();
         assert_eq!(mempool.all().collect::<Vec<_>>().len(), 1);
@@ -385,4 +556,155 @@ mod tests {
             .unwrap();
         assert_eq!(mempool.all().collect::<Vec<_>>().len(), 6);
     }
+
+    #[test]
+    fn test_mempool_get_tx_by_hash() {
+        let chain = KvStoreChain::new(
+            RamKvStore::new(),
+            crate::config::blockchain::get_test_blockchain_config(),
+        )
+        .unwrap();
+        let abc = TxBuilder::new(&Vec::from("ABC"));
+        let mut mempool = Mempool::new(Amount(1), 1000, Default::default());
+
+        let tx = dummy_tx(&abc, 1);
+        let tx_hash = tx_hash(&tx);
+        assert!(mempool.get_tx(&tx_hash).is_none());
+        mempool.add_tx(&chain, tx, false, 10).unwrap();
+        let (found, stats) = mempool.get_tx(&tx_hash).unwrap();
+        assert_eq!(found.tx.hash(), tx_hash);
+        assert_eq!(stats.first_seen, 10);
+        assert!(mempool.get_rejected_tx(&tx_hash).is_none());
+    }
+
+    #[test]
+    fn test_mempool_replace_by_fee() {
+        let chain = KvStoreChain::new(
+            RamKvStore::new(),
+            crate::config::blockchain::get_test_blockchain_config(),
+        )
+        .unwrap();
+        let abc = TxBuilder::new(&Vec::from("ABC"));
+        let mut mempool = Mempool::new(Amount(1), 1000, Default::default());
+
+        let tx1 = dummy_tx_with_fee(&abc, 1, 1);
+        let tx2 = dummy_tx_with_fee(&abc, 2, 1);
+        mempool.add_tx(&chain, tx1.clone(), false, 0).unwrap();
+        mempool.add_tx(&chain, tx2.clone(), false, 0).unwrap();
+
+        // Same fee, not replaced
+        let tx1_same_fee = GeneralTransaction::TransactionAndDelta(abc.create_transaction(
+            "Hey".into(),
+            abc.get_address(),
+            Money::ziesha(100),
+            Money::ziesha(1),
+            1,
+        ));
+        mempool
+            .add_tx(&chain, tx1_same_fee.clone(), false, 0)
+            .unwrap();
+        assert!(mempool.get_tx(&tx_hash(&tx1)).is_some());
+        assert!(mempool.get_tx(&tx_hash(&tx1_same_fee)).is_none());
+
+        // Higher fee replaces the tx on the same nonce
+        let tx1_higher_fee = dummy_tx_with_fee(&abc, 1, 2);
+        mempool
+            .add_tx(&chain, tx1_higher_fee.clone(), false, 10)
+            .unwrap();
+        assert_eq!(mempool.len(), 2);
+        assert!(mempool.get_tx(&tx_hash(&tx1_higher_fee)).is_some());
+        assert!(mempool.get_tx(&tx_hash(&tx2)).is_some());
+        assert!(mempool.get_tx(&tx_hash(&tx1)).is_none());
+        let (_, stats) = mempool.get_rejected_tx(&tx_hash(&tx1)).unwrap();
+        assert_eq!(stats.removed, Some(TransactionRemoval::Replaced));
+
+        // Replaced tx is not accepted again
+        mempool.add_tx(&chain, tx1.clone(), false, 20).unwrap();
+        assert!(mempool.get_tx(&tx_hash(&tx1)).is_none());
+
+        // Rejected txs are forgotten after some time
+        mempool.refresh(&chain, 40, None, Some(30)).unwrap();
+        assert!(mempool.get_rejected_tx(&tx_hash(&tx1)).is_none());
+    }
+
+    #[test]
+    fn test_mempool_evicts_lowest_fee_per_byte() {
+        let chain = KvStoreChain::new(
+            RamKvStore::new(),
+            crate::config::blockchain::get_test_blockchain_config(),
+        )
+        .unwrap();
+        let abc = TxBuilder::new(&Vec::from("ABC"));
+        let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+        let cba = TxBuilder::new(&Vec::from("CBA"));
+        let dcba = TxBuilder::new(&Vec::from("DCBA"));
+        let local = TxBuilder::new(&Vec::from("LOCAL"));
+        let mut mempool = Mempool::new(Amount(1), 2, Default::default());
+
+        let abc_tx = dummy_tx_with_fee(&abc, 1, 1);
+        let delegator_tx = dummy_tx_with_fee(&delegator, 4, 3);
+        mempool.add_tx(&chain, abc_tx.clone(), false, 0).unwrap();
+        mempool
+            .add_tx(&chain, delegator_tx.clone(), false, 0)
+            .unwrap();
+        assert_eq!(mempool.len(), 2);
+
+        // Cheapest tx gets evicted
+        let cba_tx = dummy_tx_with_fee(&cba, 1, 2);
+        mempool.add_tx(&chain, cba_tx.clone(), false, 0).unwrap();
+        assert_eq!(mempool.len(), 2);
+        assert!(mempool.get_tx(&tx_hash(&cba_tx)).is_some());
+        assert!(mempool.get_tx(&tx_hash(&abc_tx)).is_none());
+        let (_, stats) = mempool.get_rejected_tx(&tx_hash(&abc_tx)).unwrap();
+        assert_eq!(stats.removed, Some(TransactionRemoval::Evicted));
+
+        // Cheaper txs are not accepted when mempool is full
+        let dcba_tx = dummy_tx_with_fee(&dcba, 1, 1);
+        mempool.add_tx(&chain, dcba_tx.clone(), false, 0).unwrap();
+        assert_eq!(mempool.len(), 2);
+        assert!(mempool.get_tx(&tx_hash(&dcba_tx)).is_none());
+
+        // Local txs are always accepted, and never evicted
+        let local_tx = dummy_tx_with_fee(&local, 1, 0);
+        mempool.add_tx(&chain, local_tx.clone(), true, 0).unwrap();
+        assert_eq!(mempool.len(), 2);
+        assert!(mempool.get_tx(&tx_hash(&local_tx)).is_some());
+        assert!(mempool.get_tx(&tx_hash(&cba_tx)).is_none());
+        mempool
+            .add_tx(&chain, dummy_tx_with_fee(&dcba, 1, 10), false, 0)
+            .unwrap();
+        assert!(mempool.get_tx(&tx_hash(&local_tx)).is_some());
+        assert!(mempool.get_tx(&tx_hash(&delegator_tx)).is_none());
+    }
+
+    #[test]
+    fn test_mempool_sender_limit() {
+        let chain = KvStoreChain::new(
+            RamKvStore::new(),
+            crate::config::blockchain::get_test_blockchain_config(),
+        )
+        .unwrap();
+        let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+        let mut mempool = Mempool::new(Money::ziesha(10).amount, 1000, Default::default());
+
+        // 25 Ziesha allows 2 txs in the mempool
+        for i in 4..7 {
+            mempool
+                .add_tx(&chain, dummy_tx(&delegator, i), false, 0)
+                .unwrap();
+        }
+        assert_eq!(mempool.len(), 2);
+
+        // Limit does not apply to local txs
+        mempool
+            .add_tx(&chain, dummy_tx(&delegator, 6), true, 0)
+            .unwrap();
+        assert_eq!(mempool.len(), 3);
+
+        // Senders with no balance are still allowed to have a single tx
+        let cba = TxBuilder::new(&Vec::from("CBA"));
+        mempool.add_tx(&chain, dummy_tx(&cba, 1), false, 0).unwrap();
+        mempool.add_tx(&chain, dummy_tx(&cba, 2), false, 0).unwrap();
+        assert_eq!(mempool.len(), 4);
+    }
 }
@@ -5,12 +5,16 @@ pub use mempool::*;
 mod config;
 pub use config::BlockchainConfig;
 mod ops;
+mod snapshot;
+pub use snapshot::*;
 
 use crate::core::{
     hash::Hash, Address, Amount, Block, ContractAccount, ContractDeposit, ContractId,
-    ContractUpdate, ContractWithdraw, Delegate, Hasher, Header, Money, MpnAddress, ProofOfStake,
-    RegularSendEntry, Signature, Staker, Token, TokenId, TokenUpdate, Transaction,
-    TransactionAndDelta, TransactionData, ValidatorProof, Vrf, ZkHasher as CoreZkHasher,
+    ContractUpdate, ContractWithdraw, Delegate, DelegatorReward, EpochStaker, Hasher, Header,
+    Money, MpnAddress, MultisigPolicy, ProofOfStake, RegularSendEntry, Signature, Staker, Token,
+    TokenId, TokenUpdate, Transaction, TransactionAndDelta, TransactionData, Unbonding,
+    ValidatorProof, ValidatorReward, Vesting, VestingAccount, VestingId, Vrf,
+    ZkHasher as CoreZkHasher,
 };
 use crate::crypto::VerifiableRandomFunction;
 use crate::db::{keys, KvStore, RamMirrorKvStore, WriteOp};
@@ -22,6 +26,7 @@ use rayon::prelude::*;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TransactionValidity {
@@ -30,11 +35,20 @@ pub enum TransactionValidity {
     Valid,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TransactionRemoval {
+    /// Evicted in favor of txs paying a higher fee-per-byte, while mempool was full
+    Evicted,
+    /// Replaced by a tx with the same nonce, paying a higher fee
+    Replaced,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransactionStats {
     pub first_seen: u32,
     pub validity: TransactionValidity,
     pub is_local: bool,
+    pub removed: Option<TransactionRemoval>,
 }
 
 impl TransactionStats {
@@ -43,10 +57,32 @@ impl TransactionStats {
             first_seen,
             validity: TransactionValidity::Unknown,
             is_local,
+            removed: None,
         }
     }
 }
 
+/// A confirmed transaction, along with the header-level data of its block
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionLocation {
+    pub height: u64,
+    pub index: u32,
+    pub block_hash: <Hasher as Hash>::Output,
+    pub timestamp: u32,
+    /// Number of blocks on top of the block, including the block itself
+    pub confirmations: u64,
+    /// `None` when the body of the block is pruned
+    pub tx: Option<Transaction>,
+}
+
+/// A confirmed transaction with the Merkle proof of its inclusion in the block
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionWithProof {
+    pub height: u64,
+    pub tx: Transaction,
+    pub proof: Vec<<Hasher as Hash>::Output>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ZkBlockchainPatch {
     pub patches: HashMap<ContractId, zk::ZkStatePatch>,
@@ -65,7 +101,7 @@ pub struct BlockAndPatch {
     pub patch: ZkBlockchainPatch,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TxSideEffect {
     StateChange {
         contract_id: ContractId,
@@ -74,11 +110,29 @@ pub enum TxSideEffect {
     Nothing,
 }
 
+/// Maximum number of entries in a page of transaction history
+pub const MAX_TX_HISTORY_PAGE_SIZE: usize = 1000;
+
+/// Receipt of a transaction included in a block. There is no success flag:
+/// `apply_block` rejects the whole block when one of its transactions fails,
+/// so every transaction with a receipt has been applied successfully.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionReceipt {
+    pub height: u64,
+    pub index: u32,
+    pub fee: Money,
+    pub side_effect: TxSideEffect,
+}
+
 pub trait Blockchain<K: KvStore> {
     fn database(&self) -> &K;
     fn epoch_slot(&self, timestamp: u32) -> (u32, u32);
     fn get_stake(&self, addr: Address) -> Result<Amount, BlockchainError>;
     fn get_stakers(&self) -> Result<Vec<(Address, Amount)>, BlockchainError>;
+    /// Stakers taking part in the elections of an epoch. Stakes are fixed
+    /// when the first block of the epoch is applied, the current stakes are
+    /// returned for epochs that have not started yet.
+    fn get_epoch_stakers(&self, epoch: u32) -> Result<Vec<EpochStaker>, BlockchainError>;
     fn get_delegatees(
         &self,
         delegator: Address,
@@ -109,8 +163,24 @@ pub trait Blockchain<K: KvStore> {
     fn db_checksum(&self) -> Result<String, BlockchainError>;
 
     fn get_token(&self, token_id: TokenId) -> Result<Option<Token>, BlockchainError>;
+    fn get_multisig_policy(&self, addr: Address)
+        -> Result<Option<MultisigPolicy>, BlockchainError>;
+    fn get_vesting(&self, vesting_id: VestingId)
+        -> Result<Option<VestingAccount>, BlockchainError>;
+    /// Vestings of a beneficiary which are not fully claimed yet
+    fn get_vestings(
+        &self,
+        beneficiary: Address,
+    ) -> Result<Vec<(VestingId, VestingAccount)>, BlockchainError>;
 
     fn get_balance(&self, addr: Address, token_id: TokenId) -> Result<Amount, BlockchainError>;
+    /// Amount of the owner's token the spender is still allowed to transfer
+    fn get_allowance(
+        &self,
+        owner: Address,
+        spender: Address,
+        token_id: TokenId,
+    ) -> Result<Amount, BlockchainError>;
     fn get_contract_balance(
         &self,
         contract_id: ContractId,
@@ -121,6 +191,28 @@ pub trait Blockchain<K: KvStore> {
         delegator: Address,
         delegatee: Address,
     ) -> Result<Delegate, BlockchainError>;
+    fn get_unbonding(
+        &self,
+        delegator: Address,
+        delegatee: Address,
+    ) -> Result<Option<Unbonding>, BlockchainError>;
+    /// Total amount of a token which is slashed and out of circulation.
+    /// Slashings don't lower the token supply (Burns do), so the circulating
+    /// amount is `supply - burned`
+    fn get_burned(&self, token_id: TokenId) -> Result<Amount, BlockchainError>;
+    /// Total amount of a token ever minted, including its initial supply. This
+    /// is what `max_supply` caps, so burnt amounts can't be minted again
+    fn get_minted(&self, token_id: TokenId) -> Result<Amount, BlockchainError>;
+    /// Ziesha rewards of the blocks produced by a validator, per epoch
+    fn get_validator_rewards(
+        &self,
+        validator: Address,
+    ) -> Result<Vec<ValidatorReward>, BlockchainError>;
+    /// Ziesha paid to a delegator, per epoch and validator
+    fn get_delegator_rewards(
+        &self,
+        delegator: Address,
+    ) -> Result<Vec<DelegatorReward>, BlockchainError>;
     fn get_staker(&self, addr: Address) -> Result<Option<Staker>, BlockchainError>;
     fn get_nonce(&self, addr: Address) -> Result<u32, BlockchainError>;
     fn get_mpn_account(&self, addr: MpnAddress) -> Result<zk::MpnAccount, BlockchainError>;
@@ -145,9 +237,23 @@ pub trait Blockchain<K: KvStore> {
         contract_id: ContractId,
         locator: zk::ZkDataLocator,
     ) -> Result<zk::ZkScalar, BlockchainError>;
+    /// Reads a piece of contract state, along with a proof against the
+    /// compressed-state of the contract
+    fn prove_state(
+        &self,
+        contract_id: ContractId,
+        locator: zk::ZkDataLocator,
+    ) -> Result<(zk::ZkScalar, zk::ZkStateProof), BlockchainError>;
     fn next_reward(&self) -> Result<Amount, BlockchainError>;
+    /// Estimated yearly return of staked Ziesha as a ratio of the stake,
+    /// assuming a block in every slot and the current block reward. Validator
+    /// commisions are not deducted.
+    fn get_staking_apy(&self) -> Result<f64, BlockchainError>;
     fn will_extend(&self, from: u64, headers: &[Header]) -> Result<bool, BlockchainError>;
     fn extend(&mut self, from: u64, blocks: &[Block]) -> Result<(), BlockchainError>;
+    /// Same as `extend`, but verifies the stateless parts of the blocks in
+    /// parallel first. Suitable for applying large batches of blocks.
+    fn import(&mut self, from: u64, blocks: &[Block]) -> Result<(), BlockchainError>;
     fn rollback(&mut self) -> Result<(), BlockchainError>;
     fn draft_block(
         &self,
@@ -156,7 +262,18 @@ pub trait Blockchain<K: KvStore> {
         wallet: &TxBuilder,
         check: bool,
     ) -> Result<Option<BlockAndPatch>, BlockchainError>;
+    /// Same as `draft_block`, but also selects txs paying their fees in the
+    /// custom tokens accepted by the node
+    fn draft_block_with_fee_prices(
+        &self,
+        timestamp: u32,
+        mempool: &[TransactionAndDelta],
+        wallet: &TxBuilder,
+        check: bool,
+        fee_token_prices: &FeeTokenPrices,
+    ) -> Result<Option<BlockAndPatch>, BlockchainError>;
     fn get_height(&self) -> Result<u64, BlockchainError>;
+    fn get_pruned_height(&self) -> Result<u64, BlockchainError>;
     fn get_tip(&self) -> Result<Header, BlockchainError>;
     fn get_headers(&self, since: u64, count: u64) -> Result<Vec<Header>, BlockchainError>;
     fn get_blocks(&self, since: u64, count: u64) -> Result<Vec<Block>, BlockchainError>;
@@ -175,11 +292,42 @@ pub trait Blockchain<K: KvStore> {
     ) -> Result<ZkBlockchainPatch, BlockchainError>;
     fn update_states(&mut self, patch: &ZkBlockchainPatch) -> Result<(), BlockchainError>;
     fn check_tx(&self, tx: &Transaction) -> Result<(), BlockchainError>;
+
+    /// Location of a confirmed transaction. Still succeeds when the body of
+    /// the block is pruned, only without the transaction itself.
+    fn get_transaction(
+        &self,
+        tx_hash: <Hasher as Hash>::Output,
+    ) -> Result<Option<TransactionLocation>, BlockchainError>;
+    /// Merkle proof of inclusion of a transaction in its block. Fails with
+    /// `BlockPruned` when the body of the block is pruned.
+    fn prove_transaction(
+        &self,
+        tx_hash: <Hasher as Hash>::Output,
+    ) -> Result<Option<TransactionWithProof>, BlockchainError>;
+    /// Transactions involving an address, newest first. Pages are at most
+    /// `MAX_TX_HISTORY_PAGE_SIZE` entries long
+    fn get_tx_history(
+        &self,
+        addr: Address,
+        page: usize,
+        page_size: usize,
+    ) -> Result<Vec<(u64, u32, <Hasher as Hash>::Output)>, BlockchainError>;
+    /// Height of the first block indexed since the history was enabled. The
+    /// history is incomplete for older blocks.
+    fn get_tx_history_since(&self) -> Result<u64, BlockchainError>;
+    fn get_tx_receipt(
+        &self,
+        tx_hash: <Hasher as Hash>::Output,
+    ) -> Result<Option<TransactionReceipt>, BlockchainError>;
 }
 
 pub struct KvStoreChain<K: KvStore> {
     config: BlockchainConfig,
     database: K,
+    /// When set, contract update proofs are collected here instead of being
+    /// checked one by one, so that they can be verified in a batch
+    proof_batch: Option<Arc<Mutex<Vec<zk::ZkProofCheck>>>>,
 }
 
 impl<K: KvStore> KvStoreChain<K> {
@@ -190,6 +338,7 @@ impl<K: KvStore> KvStoreChain<K> {
         let mut chain = KvStoreChain::<K> {
             database,
             config: config.clone(),
+            proof_batch: None,
         };
         if chain.get_height()? == 0 {
             chain.apply_block(&config.genesis.block)?;
@@ -197,6 +346,13 @@ impl<K: KvStore> KvStoreChain<K> {
         } else if config.genesis.block != chain.get_block(0)? {
             return Err(BlockchainError::DifferentGenesis);
         }
+        if !config.tx_history {
+            // Blocks applied from now on are not indexed, the history has to
+            // start over once it's enabled again.
+            chain
+                .database
+                .update(&[WriteOp::Remove(keys::tx_history_since())])?;
+        }
 
         Ok(chain)
     }
@@ -205,6 +361,7 @@ impl<K: KvStore> KvStoreChain<K> {
         KvStoreChain {
             database: self.database.mirror(),
             config: self.config.clone(),
+            proof_batch: self.proof_batch.clone(),
         }
     }
 
@@ -236,7 +393,11 @@ impl<K: KvStore> KvStoreChain<K> {
             {
                 Some(b) => b.try_into()?,
                 None => {
-                    return Err(BlockchainError::Inconsistency);
+                    return Err(if self.config.pruning.is_some() {
+                        BlockchainError::CompressedStatePruned
+                    } else {
+                        BlockchainError::Inconsistency
+                    });
                 }
             },
         )
@@ -258,6 +419,16 @@ impl<K: KvStore> KvStoreChain<K> {
         ops::apply_tx(self, tx, allow_treasury)
     }
 
+    fn index_tx(
+        &mut self,
+        height: u64,
+        index: u32,
+        tx: &Transaction,
+        side_effect: &TxSideEffect,
+    ) -> Result<(), BlockchainError> {
+        ops::index_tx(self, height, index, tx, side_effect)
+    }
+
     fn get_changed_states(
         &self,
     ) -> Result<HashMap<ContractId, ZkCompressedStateChange>, BlockchainError> {
@@ -270,23 +441,151 @@ impl<K: KvStore> KvStoreChain<K> {
 
     fn pay_validator_and_delegators(
         &mut self,
+        timestamp: u32,
         validator: Address,
         fee_sum: Amount,
     ) -> Result<Amount, BlockchainError> {
-        ops::pay_validator_and_delegators(self, validator, fee_sum)
+        ops::pay_validator_and_delegators(self, timestamp, validator, fee_sum)
+    }
+
+    fn pay_token_fees(
+        &mut self,
+        timestamp: u32,
+        validator: Address,
+        token_fees: &[Money],
+    ) -> Result<(), BlockchainError> {
+        ops::pay_token_fees(self, timestamp, validator, token_fees)
+    }
+
+    fn prune_blocks(&mut self, height: u64) -> Result<(), BlockchainError> {
+        ops::prune_blocks(self, height)
     }
 
     fn select_transactions(
         &self,
+        timestamp: u32,
         validator: Address,
         txs: &[TransactionAndDelta],
         check: bool,
+        fee_token_prices: &FeeTokenPrices,
     ) -> Result<Vec<TransactionAndDelta>, BlockchainError> {
-        ops::select_transactions(self, validator, txs, check)
+        ops::select_transactions(self, timestamp, validator, txs, check, fee_token_prices)
     }
 
     fn apply_block(&mut self, block: &Block) -> Result<(), BlockchainError> {
-        ops::apply_block(self, block)
+        ops::apply_block(self, block, None)
+    }
+
+    fn verify_blocks(
+        &self,
+        blocks: &[Block],
+    ) -> Result<Vec<ops::BlockVerification>, BlockchainError> {
+        ops::verify_blocks(self, blocks)
+    }
+
+    fn apply_verified_block(
+        &mut self,
+        block: &Block,
+        verification: &ops::BlockVerification,
+    ) -> Result<(), BlockchainError> {
+        ops::apply_block(self, block, Some(verification))
+    }
+
+    fn extend_blocks(
+        &mut self,
+        from: u64,
+        blocks: &[Block],
+        pipelined: bool,
+    ) -> Result<(), BlockchainError> {
+        // Nothing to extend with, the chain is left as is
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        let headers = blocks.iter().map(|b| b.header.clone()).collect::<Vec<_>>();
+        if !self.will_extend(from, &headers)? {
+            return Err(BlockchainError::WeakerFork);
+        }
+
+        let (ops, _) = self.isolated(|chain| {
+            let curr_height = chain.get_height()?;
+
+            if from == 0 {
+                return Err(BlockchainError::ExtendFromGenesis);
+            } else if from > curr_height {
+                return Err(BlockchainError::ExtendFromFuture);
+            }
+
+            while chain.get_height()? > from {
+                chain.rollback()?;
+            }
+
+            if pipelined {
+                let verifications = chain.verify_blocks(blocks)?;
+                for (block, verification) in blocks.iter().zip(verifications.iter()) {
+                    chain.apply_verified_block(block, verification)?;
+                }
+            } else {
+                for block in blocks.iter() {
+                    chain.apply_block(block)?;
+                }
+            }
+
+            Ok(())
+        })?;
+
+        self.database.update(&ops)?;
+        Ok(())
+    }
+
+    /// Like `is_validator`, but skips checking the VRF proof when it is
+    /// already known to be valid for the given public-key
+    fn check_validator(
+        &self,
+        timestamp: u32,
+        addr: Address,
+        proof: ValidatorProof,
+        verified_vrf_pub_key: Option<&<Vrf as VerifiableRandomFunction>::Pub>,
+    ) -> Result<bool, BlockchainError> {
+        let (epoch, slot) = self.epoch_slot(timestamp);
+        let stakers = self.get_epoch_stakers(epoch)?;
+        let sum_stakes = stakers.iter().map(|s| u64::from(s.stake)).sum::<u64>();
+
+        if let Some(staker) = stakers.into_iter().find(|s| s.address == addr) {
+            let chance = (u64::from(staker.stake) as f64 / sum_stakes as f64) as f32;
+            if let ValidatorProof::Proof {
+                vrf_output,
+                vrf_proof,
+            } = proof
+            {
+                if Into::<f32>::into(vrf_output.clone()) <= chance {
+                    if verified_vrf_pub_key == Some(&staker.vrf_pub_key) {
+                        return Ok(true);
+                    }
+                    return Ok(Vrf::verify(
+                        &staker.vrf_pub_key,
+                        format!("{}-{}", epoch, slot).as_bytes(),
+                        &vrf_output,
+                        &vrf_proof,
+                    ));
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Sum of the stakes of the VRF-proven validators of the given headers.
+    /// Stakes are measured on the current state, so that both sides of a fork
+    /// are weighted the same way.
+    fn fork_weight(&self, headers: &[Header]) -> Result<u128, BlockchainError> {
+        let mut weight = 0u128;
+        for h in headers.iter() {
+            let pos = &h.proof_of_stake;
+            if self.is_validator(pos.timestamp, pos.validator.clone(), pos.proof.clone())? {
+                weight += u64::from(self.get_stake(pos.validator.clone())?) as u128;
+            }
+        }
+        Ok(weight)
     }
 }
 
@@ -312,6 +611,10 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         if index >= self.get_height()? {
             return Err(BlockchainError::BlockNotFound);
         }
+        // Genesis block is never pruned
+        if index > 0 && index < self.get_pruned_height()? {
+            return Err(BlockchainError::BlockPruned);
+        }
         Ok(match self.database.get(keys::block(index))? {
             Some(b) => b.try_into()?,
             None => {
@@ -395,6 +698,44 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         })
     }
 
+    fn get_multisig_policy(
+        &self,
+        addr: Address,
+    ) -> Result<Option<MultisigPolicy>, BlockchainError> {
+        Ok(match self.database.get(keys::multisig_policy(&addr))? {
+            Some(b) => Some(b.try_into()?),
+            None => None,
+        })
+    }
+
+    fn get_vesting(
+        &self,
+        vesting_id: VestingId,
+    ) -> Result<Option<VestingAccount>, BlockchainError> {
+        Ok(match self.database.get(keys::vesting(&vesting_id))? {
+            Some(b) => Some(b.try_into()?),
+            None => None,
+        })
+    }
+
+    fn get_vestings(
+        &self,
+        beneficiary: Address,
+    ) -> Result<Vec<(VestingId, VestingAccount)>, BlockchainError> {
+        let prefix = keys::vesting_beneficiary_prefix(&beneficiary);
+        let mut vestings = Vec::new();
+        for (k, _) in self.database.pairs(prefix.clone().into())?.into_iter() {
+            let vesting_id: VestingId = k.0[prefix.len()..]
+                .parse()
+                .map_err(|_| BlockchainError::Inconsistency)?;
+            let account = self
+                .get_vesting(vesting_id)?
+                .ok_or(BlockchainError::Inconsistency)?;
+            vestings.push((vesting_id, account));
+        }
+        Ok(vestings)
+    }
+
     fn get_balance(&self, addr: Address, token_id: TokenId) -> Result<Amount, BlockchainError> {
         Ok(
             match self.database.get(keys::account_balance(&addr, token_id))? {
@@ -404,6 +745,23 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         )
     }
 
+    fn get_allowance(
+        &self,
+        owner: Address,
+        spender: Address,
+        token_id: TokenId,
+    ) -> Result<Amount, BlockchainError> {
+        Ok(
+            match self
+                .database
+                .get(keys::allowance(&owner, &spender, token_id))?
+            {
+                Some(b) => b.try_into()?,
+                None => 0.into(),
+            },
+        )
+    }
+
     fn get_nonce(&self, addr: Address) -> Result<u32, BlockchainError> {
         Ok(match self.database.get(keys::nonce(&addr))? {
             Some(b) => b.try_into()?,
@@ -447,6 +805,63 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         )
     }
 
+    fn get_unbonding(
+        &self,
+        delegator: Address,
+        delegatee: Address,
+    ) -> Result<Option<Unbonding>, BlockchainError> {
+        Ok(
+            match self.database.get(keys::unbonding(&delegator, &delegatee))? {
+                Some(b) => Some(b.try_into()?),
+                None => None,
+            },
+        )
+    }
+
+    fn get_burned(&self, token_id: TokenId) -> Result<Amount, BlockchainError> {
+        Ok(match self.database.get(keys::burned(token_id))? {
+            Some(b) => b.try_into()?,
+            None => Amount(0),
+        })
+    }
+
+    fn get_minted(&self, token_id: TokenId) -> Result<Amount, BlockchainError> {
+        Ok(match self.database.get(keys::minted(token_id))? {
+            Some(b) => b.try_into()?,
+            None => Amount(0),
+        })
+    }
+
+    fn get_validator_rewards(
+        &self,
+        validator: Address,
+    ) -> Result<Vec<ValidatorReward>, BlockchainError> {
+        let mut rewards = Vec::new();
+        for (_, v) in self
+            .database
+            .pairs(keys::validator_reward_prefix(&validator).into())?
+            .into_iter()
+        {
+            rewards.push(v.try_into()?);
+        }
+        Ok(rewards)
+    }
+
+    fn get_delegator_rewards(
+        &self,
+        delegator: Address,
+    ) -> Result<Vec<DelegatorReward>, BlockchainError> {
+        let mut rewards = Vec::new();
+        for (_, v) in self
+            .database
+            .pairs(keys::delegator_reward_prefix(&delegator).into())?
+            .into_iter()
+        {
+            rewards.push(v.try_into()?);
+        }
+        Ok(rewards)
+    }
+
     fn get_mpn_account(&self, addr: MpnAddress) -> Result<zk::MpnAccount, BlockchainError> {
         let index = addr.account_index(self.config().mpn_config.log4_tree_size);
         let acc = zk::KvStoreStateManager::<CoreZkHasher>::get_mpn_account(
@@ -474,16 +889,18 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
     }
 
     fn will_extend(&self, from: u64, headers: &[Header]) -> Result<bool, BlockchainError> {
-        if from + headers.len() as u64 <= self.get_height()? {
-            return Ok(false);
-        }
+        let curr_height = self.get_height()?;
 
         if from == 0 {
             return Err(BlockchainError::ExtendFromGenesis);
-        } else if from > self.get_height()? {
+        } else if from > curr_height {
             return Err(BlockchainError::ExtendFromFuture);
         }
 
+        if curr_height - from > self.config.max_reorg_depth {
+            return Err(BlockchainError::ReorgTooDeep);
+        }
+
         let mut last_header = self.get_header(from - 1)?;
 
         for h in headers.iter() {
@@ -502,31 +919,23 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
             last_header = h.clone();
         }
 
-        Ok(true)
+        if from == curr_height {
+            return Ok(!headers.is_empty());
+        }
+
+        // Fork-choice: the fork with more accumulated stake weight wins, the
+        // longer one wins when both forks are equally heavy.
+        let local_headers = self.get_headers(from, curr_height - from)?;
+        let local_weight = self.fork_weight(&local_headers)?;
+        let fork_weight = self.fork_weight(headers)?;
+        Ok(fork_weight > local_weight
+            || (fork_weight == local_weight && headers.len() > local_headers.len()))
     }
     fn extend(&mut self, from: u64, blocks: &[Block]) -> Result<(), BlockchainError> {
-        let (ops, _) = self.isolated(|chain| {
-            let curr_height = chain.get_height()?;
-
-            if from == 0 {
-                return Err(BlockchainError::ExtendFromGenesis);
-            } else if from > curr_height {
-                return Err(BlockchainError::ExtendFromFuture);
-            }
-
-            while chain.get_height()? > from {
-                chain.rollback()?;
-            }
-
-            for block in blocks.iter() {
-                chain.apply_block(block)?;
-            }
-
-            Ok(())
-        })?;
-
-        self.database.update(&ops)?;
-        Ok(())
+        self.extend_blocks(from, blocks, false)
+    }
+    fn import(&mut self, from: u64, blocks: &[Block]) -> Result<(), BlockchainError> {
+        self.extend_blocks(from, blocks, true)
     }
     fn get_height(&self) -> Result<u64, BlockchainError> {
         Ok(match self.database.get(keys::height())? {
@@ -534,6 +943,12 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
             None => 0,
         })
     }
+    fn get_pruned_height(&self) -> Result<u64, BlockchainError> {
+        Ok(match self.database.get(keys::pruned_height())? {
+            Some(b) => b.try_into()?,
+            None => 0,
+        })
+    }
     fn get_headers(&self, since: u64, count: u64) -> Result<Vec<Header>, BlockchainError> {
         let mut blks: Vec<Header> = Vec::new();
         let until = std::cmp::min(self.get_height()?, since + count);
@@ -554,6 +969,18 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         let supply = self.get_balance(Default::default(), TokenId::Ziesha)?;
         Ok(supply / self.config.reward_ratio)
     }
+    fn get_staking_apy(&self) -> Result<f64, BlockchainError> {
+        let total_stake = self
+            .get_stakers()?
+            .iter()
+            .map(|(_, a)| u64::from(*a))
+            .sum::<u64>();
+        if total_stake == 0 {
+            return Ok(0.0);
+        }
+        let blocks_per_year = (365 * 24 * 60 * 60) as f64 / self.config.slot_duration as f64;
+        Ok(u64::from(self.next_reward()?) as f64 * blocks_per_year / total_stake as f64)
+    }
     fn draft_block(
         &self,
         timestamp: u32,
@@ -561,7 +988,25 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         wallet: &TxBuilder,
         check: bool,
     ) -> Result<Option<BlockAndPatch>, BlockchainError> {
-        ops::draft_block(self, timestamp, mempool, wallet, check)
+        ops::draft_block(
+            self,
+            timestamp,
+            mempool,
+            wallet,
+            check,
+            &FeeTokenPrices::default(),
+        )
+    }
+
+    fn draft_block_with_fee_prices(
+        &self,
+        timestamp: u32,
+        mempool: &[TransactionAndDelta],
+        wallet: &TxBuilder,
+        check: bool,
+        fee_token_prices: &FeeTokenPrices,
+    ) -> Result<Option<BlockAndPatch>, BlockchainError> {
+        ops::draft_block(self, timestamp, mempool, wallet, check, fee_token_prices)
     }
 
     fn update_states(&mut self, patch: &ZkBlockchainPatch) -> Result<(), BlockchainError> {
@@ -580,6 +1025,32 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         )?)
     }
 
+    fn prove_state(
+        &self,
+        contract_id: ContractId,
+        locator: zk::ZkDataLocator,
+    ) -> Result<(zk::ZkScalar, zk::ZkStateProof), BlockchainError> {
+        // Full-state of the contract should be in sync with its compressed-state
+        let account = self.get_contract_account(contract_id)?;
+        if self.get_outdated_contracts()?.contains(&contract_id)
+            || zk::KvStoreStateManager::<CoreZkHasher>::root(&self.database, contract_id)?
+                != account.compressed_state
+        {
+            return Err(BlockchainError::StatesUnavailable);
+        }
+        let value = zk::KvStoreStateManager::<CoreZkHasher>::get_data(
+            &self.database,
+            contract_id,
+            &locator,
+        )?;
+        let proof = zk::KvStoreStateManager::<CoreZkHasher>::prove_data(
+            &self.database,
+            contract_id,
+            &locator,
+        )?;
+        Ok((value, proof))
+    }
+
     fn generate_state_patch(
         &self,
         heights: HashMap<ContractId, u64>,
@@ -610,6 +1081,24 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
             let bal: Delegate = v.try_into().unwrap();
             amount_sum += bal.amount;
         }
+        for (_, v) in self
+            .database
+            .pairs(keys::unbondings_prefix().into())?
+            .into_iter()
+        {
+            let unbonding: Unbonding = v.try_into().unwrap();
+            amount_sum += unbonding.amount;
+        }
+        for (_, v) in self
+            .database
+            .pairs(keys::vesting_prefix().into())?
+            .into_iter()
+        {
+            let acc: VestingAccount = v.try_into().unwrap();
+            if acc.vesting.amount.token_id == TokenId::Ziesha {
+                amount_sum += acc.locked();
+            }
+        }
         Ok(amount_sum)
     }
 
@@ -619,33 +1108,7 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         addr: Address,
         proof: ValidatorProof,
     ) -> Result<bool, BlockchainError> {
-        let (epoch, slot) = self.epoch_slot(timestamp);
-        let stakers = self.get_stakers()?;
-        let sum_stakes = stakers.iter().map(|(_, a)| u64::from(*a)).sum::<u64>();
-        let stakers: HashMap<Address, f32> = stakers
-            .into_iter()
-            .map(|(k, v)| (k, (u64::from(v) as f64 / sum_stakes as f64) as f32))
-            .collect();
-
-        if let Some(chance) = stakers.get(&addr) {
-            if let Some(staker_info) = self.get_staker(addr.clone())? {
-                if let ValidatorProof::Proof {
-                    vrf_output,
-                    vrf_proof,
-                } = proof
-                {
-                    if Into::<f32>::into(vrf_output.clone()) <= *chance {
-                        return Ok(Vrf::verify(
-                            &staker_info.vrf_pub_key,
-                            format!("{}-{}", epoch, slot).as_bytes(),
-                            &vrf_output,
-                            &vrf_proof,
-                        ));
-                    }
-                }
-            }
-        }
-        Ok(false)
+        self.check_validator(timestamp, addr, proof, None)
     }
     fn validator_status(
         &self,
@@ -653,15 +1116,15 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         wallet: &TxBuilder,
     ) -> Result<ValidatorProof, BlockchainError> {
         let (epoch, slot) = self.epoch_slot(timestamp);
-        let stakers = self.get_stakers()?;
-        let sum_stakes = stakers.iter().map(|(_, a)| u64::from(*a)).sum::<u64>();
-        let stakers: HashMap<Address, f32> = stakers
+        let stakers = self.get_epoch_stakers(epoch)?;
+        let sum_stakes = stakers.iter().map(|s| u64::from(s.stake)).sum::<u64>();
+        if let Some(staker) = stakers
             .into_iter()
-            .map(|(k, v)| (k, (u64::from(v) as f64 / sum_stakes as f64) as f32))
-            .collect();
-        if let Some(chance) = stakers.get(&wallet.get_address()) {
+            .find(|s| s.address == wallet.get_address())
+        {
+            let chance = (u64::from(staker.stake) as f64 / sum_stakes as f64) as f32;
             let (vrf_output, vrf_proof) = wallet.generate_random(epoch, slot);
-            if Into::<f32>::into(vrf_output.clone()) <= *chance {
+            if Into::<f32>::into(vrf_output.clone()) <= chance {
                 Ok(ValidatorProof::Proof {
                     vrf_output,
                     vrf_proof,
@@ -696,6 +1159,23 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         Ok(stakers)
     }
 
+    fn get_epoch_stakers(&self, epoch: u32) -> Result<Vec<EpochStaker>, BlockchainError> {
+        if let Some(stakers) = self.database.get(keys::epoch_stakers(epoch))? {
+            return Ok(stakers.try_into()?);
+        }
+        let mut stakers = Vec::new();
+        for (address, stake) in self.get_stakers()? {
+            if let Some(staker) = self.get_staker(address.clone())? {
+                stakers.push(EpochStaker {
+                    address,
+                    vrf_pub_key: staker.vrf_pub_key,
+                    stake,
+                });
+            }
+        }
+        Ok(stakers)
+    }
+
     fn get_delegators(
         &self,
         delegatee: Address,
@@ -752,8 +1232,11 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
         &self.database
     }
     fn min_validator_reward(&self, validator: Address) -> Result<Amount, BlockchainError> {
-        let (_, result) =
-            self.isolated(|chain| Ok(chain.pay_validator_and_delegators(validator, Amount(0))?))?;
+        // The next block is estimated to be in the epoch of the tip
+        let (_, timestamp) = ops::chain_time(self)?;
+        let (_, result) = self.isolated(|chain| {
+            Ok(chain.pay_validator_and_delegators(timestamp, validator, Amount(0))?)
+        })?;
         Ok(result)
     }
     fn check_tx(&self, tx: &Transaction) -> Result<(), BlockchainError> {
@@ -762,6 +1245,113 @@ impl<K: KvStore> Blockchain<K> for KvStoreChain<K> {
 
         Ok(())
     }
+
+    fn get_transaction(
+        &self,
+        tx_hash: <Hasher as Hash>::Output,
+    ) -> Result<Option<TransactionLocation>, BlockchainError> {
+        let (height, index): (u64, u32) = match self.database.get(keys::tx_location(&tx_hash))? {
+            Some(b) => b.try_into()?,
+            None => {
+                return Ok(None);
+            }
+        };
+        let header = self.get_header(height)?;
+        let tx = match self.get_block(height) {
+            Ok(block) => Some(
+                block
+                    .body
+                    .get(index as usize)
+                    .cloned()
+                    .ok_or(BlockchainError::Inconsistency)?,
+            ),
+            Err(BlockchainError::BlockPruned) => None,
+            Err(e) => {
+                return Err(e);
+            }
+        };
+        Ok(Some(TransactionLocation {
+            height,
+            index,
+            block_hash: header.hash(),
+            timestamp: header.proof_of_stake.timestamp,
+            confirmations: self.get_height()? - height,
+            tx,
+        }))
+    }
+    fn prove_transaction(
+        &self,
+        tx_hash: <Hasher as Hash>::Output,
+    ) -> Result<Option<TransactionWithProof>, BlockchainError> {
+        let (height, index): (u64, u32) = match self.database.get(keys::tx_location(&tx_hash))? {
+            Some(b) => b.try_into()?,
+            None => {
+                return Ok(None);
+            }
+        };
+        let block = self.get_block(height)?;
+        let tx = block
+            .body
+            .get(index as usize)
+            .cloned()
+            .ok_or(BlockchainError::Inconsistency)?;
+        Ok(Some(TransactionWithProof {
+            height,
+            tx,
+            proof: block.merkle_tree().prove(index as usize),
+        }))
+    }
+
+    fn get_tx_history(
+        &self,
+        addr: Address,
+        page: usize,
+        page_size: usize,
+    ) -> Result<Vec<(u64, u32, <Hasher as Hash>::Output)>, BlockchainError> {
+        if !self.config.tx_history {
+            return Err(BlockchainError::TxHistoryDisabled);
+        }
+        if page_size > MAX_TX_HISTORY_PAGE_SIZE {
+            return Err(BlockchainError::TxHistoryPageOutOfRange);
+        }
+        let offset = page
+            .checked_mul(page_size)
+            .ok_or(BlockchainError::TxHistoryPageOutOfRange)?;
+        // The db is iterated lazily, only the keys of the page are parsed
+        self.database
+            .pairs(keys::AddressHistoryDbKey::prefix(&addr).into())?
+            .into_iter()
+            .skip(offset)
+            .take(page_size)
+            .map(|(k, _)| {
+                let entry = keys::AddressHistoryDbKey::try_from(k)?;
+                Ok((entry.height, entry.index, entry.tx_hash))
+            })
+            .collect()
+    }
+
+    fn get_tx_history_since(&self) -> Result<u64, BlockchainError> {
+        if !self.config.tx_history {
+            return Err(BlockchainError::TxHistoryDisabled);
+        }
+        Ok(match self.database.get(keys::tx_history_since())? {
+            Some(b) => b.try_into()?,
+            None => self.get_height()?,
+        })
+    }
+
+    fn get_tx_receipt(
+        &self,
+        tx_hash: <Hasher as Hash>::Output,
+    ) -> Result<Option<TransactionReceipt>, BlockchainError> {
+        if !self.config.tx_history {
+            return Err(BlockchainError::TxHistoryDisabled);
+        }
+        Ok(match self.database.get(keys::tx_receipt(&tx_hash))? {
+            Some(b) => Some(b.try_into()?),
+            None => None,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -3,10 +3,15 @@ use super::*;
 pub fn apply_block<K: KvStore>(
     chain: &mut KvStoreChain<K>,
     block: &Block,
+    verification: Option<&BlockVerification>,
 ) -> Result<(), BlockchainError> {
     let (ops, _) = chain.isolated(|chain| {
         let curr_height = chain.get_height()?;
 
+        // Proofs of the contract updates are verified all at once
+        let proof_batch: Arc<Mutex<Vec<zk::ZkProofCheck>>> = Default::default();
+        chain.proof_batch = Some(proof_batch.clone());
+
         if let Some(height_limit) = chain.config.testnet_height_limit {
             if block.header.number >= height_limit {
                 return Err(BlockchainError::TestnetHeightLimitReached);
@@ -15,33 +20,53 @@ pub fn apply_block<K: KvStore>(
 
         let is_genesis = block.header.number == 0;
 
+        if chain.config.tx_history && chain.database.get(keys::tx_history_since())?.is_none() {
+            chain.database.update(&[WriteOp::Put(
+                keys::tx_history_since(),
+                block.header.number.into(),
+            )])?;
+        }
+
         if curr_height > 0 {
-            if block.merkle_tree().root() != block.header.block_root {
+            if verification.is_none() && block.merkle_tree().root() != block.header.block_root {
                 return Err(BlockchainError::InvalidMerkleRoot);
             }
+            if verification.is_none() && !block.header.verify_signature() {
+                return Err(BlockchainError::InvalidHeaderSignature);
+            }
 
             chain.will_extend(curr_height, &[block.header.clone()])?;
         }
 
         if !is_genesis {
+            // Stakes of an epoch are fixed by its first block
+            let (epoch, _) = chain.epoch_slot(block.header.proof_of_stake.timestamp);
+            if chain.database.get(keys::epoch_stakers(epoch))?.is_none() {
+                let stakers = chain.get_epoch_stakers(epoch)?;
+                chain
+                    .database
+                    .update(&[WriteOp::Put(keys::epoch_stakers(epoch), stakers.into())])?;
+            }
             if chain.config.check_validator
-                && !chain.is_validator(
+                && !chain.check_validator(
                     block.header.proof_of_stake.timestamp,
                     block.header.proof_of_stake.validator.clone(),
                     block.header.proof_of_stake.proof.clone(),
+                    verification.and_then(|v| v.vrf_pub_key.as_ref()),
                 )?
             {
                 return Err(BlockchainError::UnelectedValidator);
             }
-            // WARN: Sum will be invalid if fees are not in Ziesha
             let fee_sum = Amount(
                 block
                     .body
                     .iter()
+                    .filter(|t| t.fee.token_id == TokenId::Ziesha)
                     .map(|t| -> u64 { t.fee.amount.into() })
                     .sum(),
             );
             chain.pay_validator_and_delegators(
+                block.header.proof_of_stake.timestamp,
                 block.header.proof_of_stake.validator.clone(),
                 fee_sum,
             )?;
@@ -52,7 +77,10 @@ pub fn apply_block<K: KvStore>(
         let mut state_updates: HashMap<ContractId, ZkCompressedStateChange> = HashMap::new();
         let mut outdated_contracts = chain.get_outdated_contracts()?;
 
-        if !is_genesis && !block.body.par_iter().all(|tx| tx.verify_signature()) {
+        if !is_genesis
+            && verification.is_none()
+            && !block.body.par_iter().all(|tx| tx.verify_signature())
+        {
             return Err(BlockchainError::SignatureError);
         }
 
@@ -60,7 +88,7 @@ pub fn apply_block<K: KvStore>(
         let mut num_mpn_contract_deposits = 0;
         let mut num_mpn_contract_withdraws = 0;
 
-        for tx in block.body.iter() {
+        for (index, tx) in block.body.iter().enumerate() {
             // Count MPN updates
             if let TransactionData::UpdateContract {
                 contract_id,
@@ -86,10 +114,18 @@ pub fn apply_block<K: KvStore>(
 
             body_size += tx.size();
             // All genesis block txs are allowed to get from Treasury
+            let side_effect = chain.apply_tx(tx, is_genesis)?;
+            chain.database.update(&[WriteOp::Put(
+                keys::tx_location(&tx.hash()),
+                (block.header.number, index as u32).into(),
+            )])?;
+            if chain.config.tx_history {
+                chain.index_tx(block.header.number, index as u32, tx, &side_effect)?;
+            }
             if let TxSideEffect::StateChange {
                 contract_id,
                 state_change,
-            } = chain.apply_tx(tx, is_genesis)?
+            } = side_effect
             {
                 state_size_delta +=
                     state_change.state.size() as isize - state_change.prev_state.size() as isize;
@@ -104,6 +140,37 @@ pub fn apply_block<K: KvStore>(
             }
         }
 
+        chain.proof_batch = None;
+        let proof_checks = std::mem::take(&mut *proof_batch.lock().unwrap());
+        if !zk::check_proofs(&proof_checks) {
+            // Find the invalid proof
+            if let Some(i) = proof_checks.iter().position(|c| !c.check()) {
+                log::warn!("Contract update #{} has an invalid proof!", i);
+                return Err(BlockchainError::IncorrectZkProof);
+            }
+        }
+
+        if !is_genesis {
+            let mut token_fees: Vec<Money> = Vec::new();
+            for tx in block.body.iter() {
+                if tx.fee.token_id != TokenId::Ziesha {
+                    if let Some(fee) = token_fees
+                        .iter_mut()
+                        .find(|f| f.token_id == tx.fee.token_id)
+                    {
+                        fee.amount += tx.fee.amount;
+                    } else {
+                        token_fees.push(tx.fee);
+                    }
+                }
+            }
+            chain.pay_token_fees(
+                block.header.proof_of_stake.timestamp,
+                block.header.proof_of_stake.validator.clone(),
+                &token_fees,
+            )?;
+        }
+
         if !is_genesis
             && (num_mpn_function_calls < chain.config.mpn_config.mpn_num_update_batches
                 || num_mpn_contract_deposits < chain.config.mpn_config.mpn_num_deposit_batches
@@ -120,6 +187,14 @@ pub fn apply_block<K: KvStore>(
             return Err(BlockchainError::StateDeltaTooBig);
         }
 
+        if chain.config.pruning.is_some() && !state_updates.is_empty() {
+            // Kept until the block is pruned, for pruning the states it replaced
+            chain.database.update(&[WriteOp::Put(
+                keys::block_contract_updates(block.header.number),
+                state_updates.clone().into(),
+            )])?;
+        }
+
         chain.database.update(&[
             WriteOp::Put(keys::height(), (curr_height + 1).into()),
             WriteOp::Put(
@@ -145,6 +220,10 @@ pub fn apply_block<K: KvStore>(
             },
         ])?;
 
+        // Pruned data should not be restored on rollbacks, so it's removed
+        // after the rollback diff is taken.
+        chain.prune_blocks(block.header.number)?;
+
         Ok(())
     })?;
 
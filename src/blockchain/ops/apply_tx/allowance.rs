@@ -0,0 +1,41 @@
+use super::*;
+
+pub fn approve<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    tx_src: Address,
+    spender: Address,
+    token_id: TokenId,
+    amount: Amount,
+) -> Result<(), BlockchainError> {
+    let key = keys::allowance(&tx_src, &spender, token_id);
+    chain.database.update(&[if amount == Amount(0) {
+        WriteOp::Remove(key)
+    } else {
+        WriteOp::Put(key, amount.into())
+    }])?;
+    Ok(())
+}
+
+pub fn transfer_from<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    tx_src: Address,
+    owner: Address,
+    entries: &[RegularSendEntry],
+) -> Result<(), BlockchainError> {
+    for entry in entries {
+        let allowance =
+            chain.get_allowance(owner.clone(), tx_src.clone(), entry.amount.token_id)?;
+        if allowance < entry.amount.amount {
+            return Err(BlockchainError::AllowanceInsufficient);
+        }
+        approve(
+            chain,
+            owner.clone(),
+            tx_src.clone(),
+            entry.amount.token_id,
+            allowance - entry.amount.amount,
+        )?;
+        regular_send::regular_send(chain, owner.clone(), std::slice::from_ref(entry))?;
+    }
+    Ok(())
+}
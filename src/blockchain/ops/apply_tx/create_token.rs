@@ -12,6 +12,16 @@ pub fn create_token<K: KvStore>(
         if !token.validate() {
             return Err(BlockchainError::TokenBadNameSymbol);
         }
+        if let Some(metadata) = &token.metadata {
+            if !metadata.validate() {
+                return Err(BlockchainError::TokenBadMetadata);
+            }
+        }
+        if let Some(max_supply) = token.max_supply {
+            if token.supply > max_supply {
+                return Err(BlockchainError::TokenSupplyCapExceeded);
+            }
+        }
         chain.database.update(&[WriteOp::Put(
             keys::account_balance(&tx_src, token_id),
             token.supply.into(),
@@ -19,6 +29,9 @@ pub fn create_token<K: KvStore>(
         chain
             .database
             .update(&[WriteOp::Put(keys::token(&token_id), token.into())])?;
+        chain
+            .database
+            .update(&[WriteOp::Put(keys::minted(token_id), token.supply.into())])?;
     }
     Ok(())
 }
@@ -48,6 +61,8 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: None,
+            max_supply: None,
+            metadata: None,
         };
         let (ops, _) = chain
             .isolated(|chain| Ok(create_token(chain, addr, token_id, &tkn)?))
@@ -58,6 +73,10 @@ mod tests {
                 "ACB-edae9736792cbdbab2c72068eb41c6ef2e6cab372ca123f834bd7eb59fcecad640-0x0001020304050607080900010203040506070809000102030405060708090001".into(),
                 Amount(12345).into(),
             ),
+            WriteOp::Put(
+                "MNT-0x0001020304050607080900010203040506070809000102030405060708090001".into(),
+                Amount(12345).into(),
+            ),
             WriteOp::Put(
                 "TKN-0x0001020304050607080900010203040506070809000102030405060708090001".into(),
                 (&tkn).into(),
@@ -90,6 +109,8 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: None,
+            max_supply: None,
+            metadata: None,
         };
         assert!(matches!(
             chain.isolated(|chain| {
@@ -121,6 +142,8 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: None,
+            max_supply: None,
+            metadata: None,
         };
         let bad_tokens = vec![
             Token {
@@ -129,6 +152,8 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                max_supply: None,
+                metadata: None,
             },
             Token {
                 name: "KeyvanCoin".into(),
@@ -136,6 +161,8 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                max_supply: None,
+                metadata: None,
             },
             Token {
                 name: "کیوان".into(),
@@ -143,6 +170,8 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                max_supply: None,
+                metadata: None,
             },
             Token {
                 name: "KeyvanCoin".into(),
@@ -150,6 +179,8 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                max_supply: None,
+                metadata: None,
             },
             Token {
                 name: "KeyvanCoin".into(),
@@ -157,6 +188,8 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                max_supply: None,
+                metadata: None,
             },
             Token {
                 name: "KeyvanCoinAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".into(),
@@ -164,6 +197,8 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                max_supply: None,
+                metadata: None,
             },
             Token {
                 name: "KeyvanCoin".into(),
@@ -171,6 +206,8 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: None,
+                max_supply: None,
+                metadata: None,
             },
         ];
         assert!(chain
@@ -1,94 +1,169 @@
 use super::*;
 
-pub fn delegate<K: KvStore>(
+/// Sets the amount delegated by `delegator` to `delegatee`, keeping the
+/// delegation rankings in sync
+pub fn set_delegate<K: KvStore>(
     chain: &mut KvStoreChain<K>,
-    tx_src: Address,
+    delegator: &Address,
+    delegatee: &Address,
     amount: Amount,
-    to: Address,
-    reverse: bool,
 ) -> Result<(), BlockchainError> {
-    let mut src_bal = chain.get_balance(tx_src.clone(), TokenId::Ziesha)?;
-    if !reverse {
-        if src_bal < amount {
-            return Err(BlockchainError::BalanceInsufficient);
-        }
-        src_bal -= amount;
-    } else {
-        src_bal += amount;
-    }
-    chain.database.update(&[WriteOp::Put(
-        keys::account_balance(&tx_src, TokenId::Ziesha),
-        src_bal.into(),
-    )])?;
-
-    let mut delegate = chain.get_delegate(tx_src.clone(), to.clone())?;
-    let old_delegate = delegate.amount;
-    if !reverse {
-        delegate.amount += amount;
-    } else {
-        if delegate.amount < amount {
-            return Err(BlockchainError::BalanceInsufficient);
-        }
-        delegate.amount -= amount;
-    }
-    chain.database.update(&[WriteOp::Put(
-        keys::delegate(&tx_src, &to),
-        delegate.clone().into(),
-    )])?;
-
-    let old_stake = chain.get_stake(to.clone())?;
-    let new_stake = old_stake + amount;
+    let old_delegate = chain
+        .get_delegate(delegator.clone(), delegatee.clone())?
+        .amount;
     chain.database.update(&[
+        WriteOp::Put(
+            keys::delegate(delegator, delegatee),
+            Delegate { amount }.into(),
+        ),
         WriteOp::Remove(
             keys::DelegateeRankDbKey {
-                delegator: tx_src.clone(),
-                delegatee: to.clone(),
+                delegator: delegator.clone(),
+                delegatee: delegatee.clone(),
                 amount: old_delegate,
             }
             .into(),
         ),
         WriteOp::Put(
             keys::DelegateeRankDbKey {
-                delegator: tx_src.clone(),
-                delegatee: to.clone(),
-                amount: delegate.amount,
+                delegator: delegator.clone(),
+                delegatee: delegatee.clone(),
+                amount,
             }
             .into(),
             ().into(),
         ),
         WriteOp::Remove(
             keys::DelegatorRankDbKey {
-                delegator: tx_src.clone(),
-                delegatee: to.clone(),
+                delegator: delegator.clone(),
+                delegatee: delegatee.clone(),
                 amount: old_delegate,
             }
             .into(),
         ),
         WriteOp::Put(
             keys::DelegatorRankDbKey {
-                delegator: tx_src.clone(),
-                delegatee: to.clone(),
-                amount: delegate.amount,
+                delegator: delegator.clone(),
+                delegatee: delegatee.clone(),
+                amount,
             }
             .into(),
             ().into(),
         ),
+    ])?;
+    Ok(())
+}
+
+/// Sets the total stake of a validator, keeping the staker rankings in sync
+pub fn set_stake<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    staker: &Address,
+    stake: Amount,
+) -> Result<(), BlockchainError> {
+    let old_stake = chain.get_stake(staker.clone())?;
+    chain.database.update(&[
         WriteOp::Remove(
             keys::StakerRankDbKey {
-                address: to.clone(),
+                address: staker.clone(),
                 amount: old_stake,
             }
             .into(),
         ),
         WriteOp::Put(
             keys::StakerRankDbKey {
-                address: to.clone(),
-                amount: new_stake,
+                address: staker.clone(),
+                amount: stake,
             }
             .into(),
             ().into(),
         ),
-        WriteOp::Put(keys::stake(&to), new_stake.into()),
+        WriteOp::Put(keys::stake(staker), stake.into()),
+    ])?;
+    Ok(())
+}
+
+pub fn delegate<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    tx_src: Address,
+    amount: Amount,
+    to: Address,
+    reverse: bool,
+) -> Result<(), BlockchainError> {
+    if !reverse {
+        let delegate = chain.get_delegate(tx_src.clone(), to.clone())?;
+        let stake = chain.get_stake(to.clone())?;
+        let mut src_bal = chain.get_balance(tx_src.clone(), TokenId::Ziesha)?;
+        if src_bal < amount {
+            return Err(BlockchainError::BalanceInsufficient);
+        }
+        src_bal -= amount;
+        chain.database.update(&[WriteOp::Put(
+            keys::account_balance(&tx_src, TokenId::Ziesha),
+            src_bal.into(),
+        )])?;
+        set_delegate(chain, &tx_src, &to, delegate.amount + amount)?;
+        set_stake(chain, &to, stake + amount)?;
+    } else {
+        undelegate(chain, &tx_src, &to, amount)?;
+    }
+    Ok(())
+}
+
+/// Moves `amount` of the delegation of `delegator` to `delegatee` into
+/// unbonding
+pub fn undelegate<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    delegator: &Address,
+    delegatee: &Address,
+    amount: Amount,
+) -> Result<(), BlockchainError> {
+    let delegate = chain.get_delegate(delegator.clone(), delegatee.clone())?;
+    let stake = chain.get_stake(delegatee.clone())?;
+    if delegate.amount < amount {
+        return Err(BlockchainError::BalanceInsufficient);
+    }
+    set_delegate(chain, delegator, delegatee, delegate.amount - amount)?;
+    set_stake(chain, delegatee, stake - amount)?;
+
+    // Undelegated funds stay slashable until the unbonding period is over
+    let (_, timestamp) = chain_time(chain)?;
+    let (epoch, _) = chain.epoch_slot(timestamp);
+    let mut unbonding = chain
+        .get_unbonding(delegator.clone(), delegatee.clone())?
+        .unwrap_or(Unbonding {
+            amount: Amount(0),
+            unlock_epoch: 0,
+        });
+    unbonding.amount += amount;
+    unbonding.unlock_epoch = epoch + chain.config.unbonding_epochs;
+    chain.database.update(&[WriteOp::Put(
+        keys::unbonding(delegator, delegatee),
+        unbonding.into(),
+    )])?;
+    Ok(())
+}
+
+pub fn reclaim_delegate<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    tx_src: Address,
+    from: Address,
+) -> Result<(), BlockchainError> {
+    let unbonding = chain
+        .get_unbonding(tx_src.clone(), from.clone())?
+        .ok_or(BlockchainError::UnbondingNotFound)?;
+    let (_, timestamp) = chain_time(chain)?;
+    let (epoch, _) = chain.epoch_slot(timestamp);
+    if epoch < unbonding.unlock_epoch {
+        return Err(BlockchainError::UnbondingInProgress);
+    }
+    let mut src_bal = chain.get_balance(tx_src.clone(), TokenId::Ziesha)?;
+    src_bal += unbonding.amount;
+    chain.database.update(&[
+        WriteOp::Put(
+            keys::account_balance(&tx_src, TokenId::Ziesha),
+            src_bal.into(),
+        ),
+        WriteOp::Remove(keys::unbonding(&tx_src, &from)),
     ])?;
     Ok(())
 }
@@ -1,13 +1,33 @@
+mod allowance;
 mod create_contract;
 mod create_token;
 mod delegate;
+mod register_multisig;
 mod regular_send;
+mod slash_validator;
+mod unregister_staker;
 mod update_contract;
 mod update_staker;
 mod update_token;
+mod vesting;
 
 use super::*;
 
+/// Height and timestamp of the latest block before the transaction. Lock-times
+/// are checked against it, so that the result is the same when drafting and
+/// applying the block.
+pub(crate) fn chain_time<K: KvStore>(
+    chain: &KvStoreChain<K>,
+) -> Result<(u64, u32), BlockchainError> {
+    let height = chain.get_height()?;
+    let timestamp = if height > 0 {
+        chain.get_tip()?.proof_of_stake.timestamp
+    } else {
+        0
+    };
+    Ok((height, timestamp))
+}
+
 pub fn apply_tx<K: KvStore>(
     chain: &mut KvStoreChain<K>,
     tx: &Transaction,
@@ -20,16 +40,21 @@ pub fn apply_tx<K: KvStore>(
             return Err(BlockchainError::IllegalTreasuryAccess);
         }
 
-        if tx.fee.token_id != TokenId::Ziesha {
-            return Err(BlockchainError::OnlyZieshaFeesAccepted);
-        }
-
         if tx.memo.len() > chain.config.max_memo_length {
             return Err(BlockchainError::MemoTooLong);
         }
 
         let tx_src = tx.src.clone().unwrap_or_default(); // Default is treasury account!
 
+        if let Signature::MultiSigned(sigs) = &tx.sig {
+            let policy = chain
+                .get_multisig_policy(tx_src.clone())?
+                .ok_or(BlockchainError::MultisigNotRegistered)?;
+            if !policy.is_satisfied_by(sigs.iter().map(|(pk, _)| pk)) {
+                return Err(BlockchainError::MultisigThresholdNotMet);
+            }
+        }
+
         let mut acc_nonce = chain.get_nonce(tx_src.clone())?;
         let mut acc_bal = chain.get_balance(tx_src.clone(), tx.fee.token_id)?;
 
@@ -80,6 +105,34 @@ pub fn apply_tx<K: KvStore>(
             TransactionData::UpdateToken { token_id, update } => {
                 update_token::update_token(chain, tx_src, token_id, update)?;
             }
+            TransactionData::RegisterMultisig { policy } => {
+                register_multisig::register_multisig(chain, policy)?;
+            }
+            TransactionData::CreateVesting { vesting } => {
+                vesting::create_vesting(chain, tx_src, VestingId::new(tx), vesting)?;
+            }
+            TransactionData::ClaimVesting { vesting_id } => {
+                vesting::claim_vesting(chain, tx_src, vesting_id)?;
+            }
+            TransactionData::ReclaimDelegate { from } => {
+                delegate::reclaim_delegate(chain, tx_src, from.clone())?;
+            }
+            TransactionData::SlashValidator { first, second } => {
+                slash_validator::slash_validator(chain, first, second)?;
+            }
+            TransactionData::UnregisterStaker => {
+                unregister_staker::unregister_staker(chain, tx_src)?;
+            }
+            TransactionData::Approve {
+                spender,
+                token_id,
+                amount,
+            } => {
+                allowance::approve(chain, tx_src, spender.clone(), *token_id, *amount)?;
+            }
+            TransactionData::TransferFrom { owner, entries } => {
+                allowance::transfer_from(chain, tx_src, owner.clone(), entries)?;
+            }
             TransactionData::RegularSend { entries } => {
                 regular_send::regular_send(chain, tx_src, entries)?;
             }
@@ -0,0 +1,18 @@
+use super::*;
+
+pub fn register_multisig<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    policy: &MultisigPolicy,
+) -> Result<(), BlockchainError> {
+    if !policy.validate() {
+        return Err(BlockchainError::InvalidMultisigPolicy);
+    }
+    let address = policy.address();
+    if chain.get_multisig_policy(address.clone())?.is_some() {
+        return Err(BlockchainError::MultisigAlreadyRegistered);
+    }
+    chain
+        .database
+        .update(&[WriteOp::Put(keys::multisig_policy(&address), policy.into())])?;
+    Ok(())
+}
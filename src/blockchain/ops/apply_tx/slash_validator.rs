@@ -0,0 +1,66 @@
+use super::*;
+
+/// Burns a share of the stake of a validator which has signed two different
+/// headers for the same slot, height and parent. (Consecutive blocks of a
+/// single slot are legitimate and not a proof of misbehavior.) Funds which are
+/// being unbonded from the validator are slashed too.
+pub fn slash_validator<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    first: &Header,
+    second: &Header,
+) -> Result<(), BlockchainError> {
+    let validator = first.proof_of_stake.validator.clone();
+    let (epoch, slot) = chain.epoch_slot(first.proof_of_stake.timestamp);
+    if second.proof_of_stake.validator != validator
+        || chain.epoch_slot(second.proof_of_stake.timestamp) != (epoch, slot)
+        || first.number != second.number
+        || first.parent_hash != second.parent_hash
+        || first == second
+        || !first.verify_signature()
+        || !second.verify_signature()
+    {
+        return Err(BlockchainError::InvalidSlashingEvidence);
+    }
+    if chain
+        .database
+        .get(keys::slashed(&validator, epoch, slot))?
+        .is_some()
+    {
+        return Err(BlockchainError::ValidatorAlreadySlashed);
+    }
+
+    let ratio = chain.config.slashing_ratio as u128;
+    let cut = |amount: Amount| Amount((u64::from(amount) as u128 * ratio / 255) as u64);
+
+    let mut stake_burned = Amount(0);
+    for (delegator, amount) in chain.get_delegators(validator.clone(), None)? {
+        let burned = cut(amount);
+        delegate::set_delegate(chain, &delegator, &validator, amount - burned)?;
+        stake_burned += burned;
+    }
+    let stake = chain.get_stake(validator.clone())?;
+    delegate::set_stake(chain, &validator, stake - stake_burned)?;
+
+    let mut unbonding_burned = Amount(0);
+    let unbondings = chain
+        .database
+        .pairs(keys::unbonding_prefix(&validator).into())?
+        .into_iter()
+        .collect::<Vec<_>>();
+    for (k, v) in unbondings {
+        let mut unbonding: Unbonding = v.try_into()?;
+        let burned = cut(unbonding.amount);
+        unbonding.amount -= burned;
+        unbonding_burned += burned;
+        chain
+            .database
+            .update(&[WriteOp::Put(k, unbonding.into())])?;
+    }
+
+    let total_burned = chain.get_burned(TokenId::Ziesha)? + stake_burned + unbonding_burned;
+    chain.database.update(&[
+        WriteOp::Put(keys::slashed(&validator, epoch, slot), ().into()),
+        WriteOp::Put(keys::burned(TokenId::Ziesha), total_burned.into()),
+    ])?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use super::*;
+
+pub fn unregister_staker<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    tx_src: Address,
+) -> Result<(), BlockchainError> {
+    if chain.get_staker(tx_src.clone())?.is_none() {
+        return Err(BlockchainError::ValidatorNotRegistered);
+    }
+
+    for (delegator, amount) in chain.get_delegators(tx_src.clone(), None)? {
+        delegate::undelegate(chain, &delegator, &tx_src, amount)?;
+        // Nothing is delegated to the staker anymore, so the empty delegation
+        // and its rank entries are dropped
+        chain.database.update(&[
+            WriteOp::Remove(keys::delegate(&delegator, &tx_src)),
+            WriteOp::Remove(
+                keys::DelegateeRankDbKey {
+                    delegator: delegator.clone(),
+                    delegatee: tx_src.clone(),
+                    amount: Amount(0),
+                }
+                .into(),
+            ),
+            WriteOp::Remove(
+                keys::DelegatorRankDbKey {
+                    delegator: delegator.clone(),
+                    delegatee: tx_src.clone(),
+                    amount: Amount(0),
+                }
+                .into(),
+            ),
+        ])?;
+    }
+
+    let stake = chain.get_stake(tx_src.clone())?;
+    chain.database.update(&[
+        WriteOp::Remove(keys::staker(&tx_src)),
+        WriteOp::Remove(
+            keys::StakerRankDbKey {
+                address: tx_src.clone(),
+                amount: stake,
+            }
+            .into(),
+        ),
+        WriteOp::Remove(keys::stake(&tx_src)),
+    ])?;
+    Ok(())
+}
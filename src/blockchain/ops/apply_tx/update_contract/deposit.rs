@@ -207,6 +207,8 @@ mod tests {
             Amount(100000),
             3,
             None,
+            None,
+            None,
             Money::ziesha(0),
             1,
         );
@@ -269,7 +271,7 @@ mod tests {
 
         let expected_ops = vec![
             WriteOp::Put(
-                "ACB-ed8c19c6a4cf1460e961f7bae8eea54d437b9edac27cbeb09be32ae367adf9098a-0x113c5707d6ab03dd78c9f8455f3b4096c8c5e9312076aafe38d79c4d8eb2d096"
+                "ACB-ed8c19c6a4cf1460e961f7bae8eea54d437b9edac27cbeb09be32ae367adf9098a-0x3b3e819afac8742cad69ef0752c3ae38c51761ea68d2f0fc34eeb96ac255498f"
                     .into(),
                 Amount(90000).into(),
             ),
@@ -279,7 +281,7 @@ mod tests {
                 Amount(9679).into(),
             ),
             WriteOp::Put(
-                "CAB-cb27ac3a71d1351da873d7af0457f8032512b76c673bf6a95be7e74b3c0d02c6-0x113c5707d6ab03dd78c9f8455f3b4096c8c5e9312076aafe38d79c4d8eb2d096"
+                "CAB-cb27ac3a71d1351da873d7af0457f8032512b76c673bf6a95be7e74b3c0d02c6-0x3b3e819afac8742cad69ef0752c3ae38c51761ea68d2f0fc34eeb96ac255498f"
                     .into(),
                 Amount(10000).into(),
             ),
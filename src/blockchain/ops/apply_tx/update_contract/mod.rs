@@ -76,14 +76,17 @@ pub fn update_contract<K: KvStore>(
         };
 
         let mut cont_account = chain.get_contract_account(*contract_id)?;
-        if !zk::check_proof(
-            &circuit,
-            prev_account.height,
-            cont_account.compressed_state.state_hash,
-            aux_data.state_hash,
-            next_state.state_hash,
-            &proof,
-        ) {
+        let check = zk::ZkProofCheck {
+            vk: circuit,
+            prev_height: prev_account.height,
+            prev_state: cont_account.compressed_state.state_hash,
+            aux_data: aux_data.state_hash,
+            next_state: next_state.state_hash,
+            proof,
+        };
+        if let Some(batch) = &chain.proof_batch {
+            batch.lock().unwrap().push(check);
+        } else if !check.check() {
             return Err(BlockchainError::IncorrectZkProof);
         }
         cont_account.compressed_state = next_state;
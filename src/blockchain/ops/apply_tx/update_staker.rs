@@ -1,5 +1,5 @@
 use super::*;
-use crate::core::{Address, Vrf};
+use crate::core::{Address, CommisionChange, Vrf};
 use crate::crypto::VerifiableRandomFunction;
 
 pub fn update_staker<K: KvStore>(
@@ -8,19 +8,61 @@ pub fn update_staker<K: KvStore>(
     vrf_pub_key: <Vrf as VerifiableRandomFunction>::Pub,
     commision: u8,
 ) -> Result<(), BlockchainError> {
-    let commision = std::cmp::min(commision, chain.config.max_validator_commision);
+    if commision > chain.config.max_validator_commision {
+        return Err(BlockchainError::InvalidCommision);
+    }
+
+    let (_, timestamp) = chain_time(chain)?;
+    let (epoch, _) = chain.epoch_slot(timestamp);
 
-    chain.database.update(&[WriteOp::Put(
-        keys::staker(&tx_src),
-        Staker {
+    let staker = match chain.get_staker(tx_src.clone())? {
+        Some(staker) => {
+            // Changes are announced a notice delay before being charged, and
+            // each change is limited relative to the one charged in the epoch
+            // before it, so the commision can't move more than
+            // `max_commision_change` from one epoch to the next. Announcing
+            // again in the same epoch replaces the previous announcement.
+            let effective_epoch = epoch + std::cmp::max(chain.config.commision_change_delay, 1);
+            let mut pending_commisions = staker
+                .pending_commisions
+                .iter()
+                .filter(|change| {
+                    change.effective_epoch > epoch && change.effective_epoch < effective_epoch
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            let current = staker.commision_at(epoch);
+            let latest = pending_commisions
+                .last()
+                .map(|change| change.commision)
+                .unwrap_or(current);
+            if latest.abs_diff(commision) > chain.config.max_commision_change {
+                return Err(BlockchainError::CommisionChangeTooLarge);
+            }
+            if commision != latest {
+                pending_commisions.push(CommisionChange {
+                    commision,
+                    effective_epoch,
+                });
+            }
+            Staker {
+                vrf_pub_key,
+                commision: current,
+                pending_commisions,
+            }
+        }
+        None => Staker {
             vrf_pub_key,
             commision,
-        }
-        .into(),
-    )])?;
+            pending_commisions: Vec::new(),
+        },
+    };
+
+    chain
+        .database
+        .update(&[WriteOp::Put(keys::staker(&tx_src), staker.into())])?;
     Ok(())
 }
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -28,7 +70,7 @@ mod tests {
 
     #[test]
     fn test_update_staker() {
-        let chain = KvStoreChain::new(
+        let mut chain = KvStoreChain::new(
             RamKvStore::new(),
             crate::config::blockchain::get_test_blockchain_config(),
         )
@@ -47,14 +89,17 @@ mod tests {
             Staker {
                 vrf_pub_key: abc.get_vrf_public_key(),
                 commision: 10,
+                pending_commisions: Vec::new(),
             }
             .into(),
         )];
         assert_eq!(ops, expected_ops);
+        chain.database.update(&ops).unwrap();
 
+        // Commision changes are announced before being charged
         let (ops, _) = chain
             .isolated(|chain| {
-                update_staker(chain, abc.get_address(), abc.get_vrf_public_key(), 255)?;
+                update_staker(chain, abc.get_address(), abc.get_vrf_public_key(), 13)?;
                 Ok(())
             })
             .unwrap();
@@ -62,10 +107,30 @@ mod tests {
             "SKR-edf7860e4ff620f392165924386a895e9c82b156e65c16f86cb1ce0085455c4d24".into(),
             Staker {
                 vrf_pub_key: abc.get_vrf_public_key(),
-                commision: 26,
+                commision: 10,
+                pending_commisions: vec![CommisionChange {
+                    commision: 13,
+                    effective_epoch: 6,
+                }],
             }
             .into(),
         )];
         assert_eq!(ops, expected_ops);
+
+        assert!(matches!(
+            chain.isolated(|chain| {
+                update_staker(chain, abc.get_address(), abc.get_vrf_public_key(), 14)?;
+                Ok(())
+            }),
+            Err(BlockchainError::CommisionChangeTooLarge)
+        ));
+
+        assert!(matches!(
+            chain.isolated(|chain| {
+                update_staker(chain, abc.get_address(), abc.get_vrf_public_key(), 255)?;
+                Ok(())
+            }),
+            Err(BlockchainError::InvalidCommision)
+        ));
     }
 }
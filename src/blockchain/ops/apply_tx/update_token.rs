@@ -1,45 +1,81 @@
 use super::*;
 
+fn check_minter(token: &Token, tx_src: &Address) -> Result<(), BlockchainError> {
+    match &token.minter {
+        Some(minter) if minter == tx_src => Ok(()),
+        Some(_) => Err(BlockchainError::TokenUpdatePermissionDenied),
+        None => Err(BlockchainError::TokenNotUpdatable),
+    }
+}
+
 pub fn update_token<K: KvStore>(
     chain: &mut KvStoreChain<K>,
     tx_src: Address,
     token_id: &TokenId,
     update: &TokenUpdate,
 ) -> Result<(), BlockchainError> {
-    if let Some(mut token) = chain.get_token(*token_id)? {
-        if let Some(minter) = token.minter.clone() {
-            if minter == tx_src {
-                match update {
-                    TokenUpdate::Mint { amount } => {
-                        let mut bal = chain.get_balance(tx_src.clone(), *token_id)?;
-                        if bal + *amount < bal || token.supply + *amount < token.supply {
-                            return Err(BlockchainError::TokenSupplyOverflow);
-                        }
-                        bal += *amount;
-                        token.supply += *amount;
-                        chain
-                            .database
-                            .update(&[WriteOp::Put(keys::token(token_id), (&token).into())])?;
-                        chain.database.update(&[WriteOp::Put(
-                            keys::account_balance(&tx_src, *token_id),
-                            bal.into(),
-                        )])?;
-                    }
-                    TokenUpdate::ChangeMinter { minter } => {
-                        token.minter = Some(minter.clone());
-                        chain
-                            .database
-                            .update(&[WriteOp::Put(keys::token(token_id), (&token).into())])?;
-                    }
+    let mut token = chain
+        .get_token(*token_id)?
+        .ok_or(BlockchainError::TokenNotFound)?;
+    match update {
+        TokenUpdate::Mint { amount } => {
+            check_minter(&token, &tx_src)?;
+            let mut bal = chain.get_balance(tx_src.clone(), *token_id)?;
+            let mut minted = chain.get_minted(*token_id)?;
+            if bal + *amount < bal
+                || token.supply + *amount < token.supply
+                || minted + *amount < minted
+            {
+                return Err(BlockchainError::TokenSupplyOverflow);
+            }
+            bal += *amount;
+            token.supply += *amount;
+            minted += *amount;
+            // Burnt amounts still count towards the cap
+            if let Some(max_supply) = token.max_supply {
+                if minted > max_supply {
+                    return Err(BlockchainError::TokenSupplyCapExceeded);
                 }
-            } else {
-                return Err(BlockchainError::TokenUpdatePermissionDenied);
             }
-        } else {
-            return Err(BlockchainError::TokenNotUpdatable);
+            chain
+                .database
+                .update(&[WriteOp::Put(keys::token(token_id), (&token).into())])?;
+            chain
+                .database
+                .update(&[WriteOp::Put(keys::minted(*token_id), minted.into())])?;
+            chain.database.update(&[WriteOp::Put(
+                keys::account_balance(&tx_src, *token_id),
+                bal.into(),
+            )])?;
+        }
+        TokenUpdate::ChangeMinter { minter } => {
+            check_minter(&token, &tx_src)?;
+            token.minter = Some(minter.clone());
+            chain
+                .database
+                .update(&[WriteOp::Put(keys::token(token_id), (&token).into())])?;
+        }
+        TokenUpdate::RenounceMinter => {
+            check_minter(&token, &tx_src)?;
+            token.minter = None;
+            chain
+                .database
+                .update(&[WriteOp::Put(keys::token(token_id), (&token).into())])?;
+        }
+        TokenUpdate::Burn { amount } => {
+            let bal = chain.get_balance(tx_src.clone(), *token_id)?;
+            if bal < *amount {
+                return Err(BlockchainError::BalanceInsufficient);
+            }
+            token.supply -= *amount;
+            chain.database.update(&[
+                WriteOp::Put(
+                    keys::account_balance(&tx_src, *token_id),
+                    (bal - *amount).into(),
+                ),
+                WriteOp::Put(keys::token(token_id), (&token).into()),
+            ])?;
         }
-    } else {
-        return Err(BlockchainError::TokenNotFound);
     }
     Ok(())
 }
@@ -103,6 +139,8 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: None,
+            max_supply: None,
+            metadata: None,
         };
         super::create_token::create_token(&mut chain, addr_1.clone(), token_id_1, &tkn).unwrap();
         let tkn2 = Token {
@@ -111,6 +149,8 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: Some(addr_2),
+            max_supply: None,
+            metadata: None,
         };
         super::create_token::create_token(&mut chain, addr_1.clone(), token_id_2, &tkn2).unwrap();
         assert!(matches!(
@@ -157,6 +197,8 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: Some(addr.clone()),
+            max_supply: None,
+            metadata: None,
         };
         super::create_token::create_token(&mut chain, addr.clone(), token_id, &tkn).unwrap();
 
@@ -178,6 +220,11 @@ mod tests {
                 "ACB-edae9736792cbdbab2c72068eb41c6ef2e6cab372ca123f834bd7eb59fcecad640-0x0001020304050607080900010203040506070809000102030405060708090001".into(),
                 Amount(12445).into()
             ),
+            WriteOp::Put(
+                "MNT-0x0001020304050607080900010203040506070809000102030405060708090001"
+                    .into(),
+                Amount(12445).into()
+            ),
             WriteOp::Put(
                 "TKN-0x0001020304050607080900010203040506070809000102030405060708090001"
                     .into(),
@@ -187,6 +234,8 @@ mod tests {
                     supply: Amount(12445),
                     decimals: 2,
                     minter: Some(addr),
+                    max_supply: None,
+                    metadata: None,
                 }).into()
             ),
         ];
@@ -218,6 +267,8 @@ mod tests {
             supply: Amount(12345),
             decimals: 2,
             minter: Some(addr.clone()),
+            max_supply: None,
+            metadata: None,
         };
         super::create_token::create_token(&mut chain, addr.clone(), token_id, &tkn).unwrap();
 
@@ -242,6 +293,8 @@ mod tests {
                 supply: Amount(12345),
                 decimals: 2,
                 minter: Some(new_addr),
+                max_supply: None,
+                metadata: None,
             })
                 .into(),
         )];
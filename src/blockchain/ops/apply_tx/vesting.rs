@@ -0,0 +1,76 @@
+use super::*;
+
+pub fn create_vesting<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    tx_src: Address,
+    vesting_id: VestingId,
+    vesting: &Vesting,
+) -> Result<(), BlockchainError> {
+    if !vesting.validate() {
+        return Err(BlockchainError::InvalidVesting);
+    }
+    let mut src_bal = chain.get_balance(tx_src.clone(), vesting.amount.token_id)?;
+    if src_bal < vesting.amount.amount {
+        return Err(BlockchainError::BalanceInsufficient);
+    }
+    src_bal -= vesting.amount.amount;
+    chain.database.update(&[
+        WriteOp::Put(
+            keys::account_balance(&tx_src, vesting.amount.token_id),
+            src_bal.into(),
+        ),
+        WriteOp::Put(
+            keys::vesting(&vesting_id),
+            (&VestingAccount {
+                vesting: vesting.clone(),
+                claimed: Amount(0),
+            })
+                .into(),
+        ),
+        WriteOp::Put(
+            keys::vesting_beneficiary(&vesting.beneficiary, &vesting_id),
+            ().into(),
+        ),
+    ])?;
+    Ok(())
+}
+
+pub fn claim_vesting<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    tx_src: Address,
+    vesting_id: &VestingId,
+) -> Result<(), BlockchainError> {
+    let mut account = chain
+        .get_vesting(*vesting_id)?
+        .ok_or(BlockchainError::VestingNotFound)?;
+    if account.vesting.beneficiary != tx_src {
+        return Err(BlockchainError::NotVestingBeneficiary);
+    }
+    let (height, timestamp) = chain_time(chain)?;
+    let released = account.vesting.released(height, timestamp);
+    if released <= account.claimed {
+        return Err(BlockchainError::NothingToClaim);
+    }
+    let claimable = released - account.claimed;
+    account.claimed += claimable;
+
+    let token_id = account.vesting.amount.token_id;
+    let mut dst_bal = chain.get_balance(tx_src.clone(), token_id)?;
+    dst_bal += claimable;
+    chain.database.update(&[WriteOp::Put(
+        keys::account_balance(&tx_src, token_id),
+        dst_bal.into(),
+    )])?;
+
+    if account.locked() == Amount(0) {
+        chain.database.update(&[
+            WriteOp::Remove(keys::vesting(vesting_id)),
+            WriteOp::Remove(keys::vesting_beneficiary(&tx_src, vesting_id)),
+        ])?;
+    } else {
+        chain
+            .database
+            .update(&[WriteOp::Put(keys::vesting(vesting_id), (&account).into())])?;
+    }
+    Ok(())
+}
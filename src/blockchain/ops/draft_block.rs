@@ -6,6 +6,7 @@ pub fn draft_block<K: KvStore>(
     mempool: &[TransactionAndDelta],
     wallet: &TxBuilder,
     check: bool,
+    fee_token_prices: &FeeTokenPrices,
 ) -> Result<Option<BlockAndPatch>, BlockchainError> {
     let height = chain.get_height()?;
     if height == 0 {
@@ -25,7 +26,13 @@ pub fn draft_block<K: KvStore>(
 
     let last_header = chain.get_header(height - 1)?;
 
-    let tx_and_deltas = chain.select_transactions(wallet.get_address(), mempool, check)?;
+    let tx_and_deltas = chain.select_transactions(
+        timestamp,
+        wallet.get_address(),
+        mempool,
+        check,
+        fee_token_prices,
+    )?;
 
     let mut txs = Vec::new();
 
@@ -62,11 +69,13 @@ pub fn draft_block<K: KvStore>(
                 timestamp,
                 validator: wallet.get_address(),
                 proof: validator_status,
+                sig: None,
             },
         },
         body: txs,
     };
     blk.header.block_root = blk.merkle_tree().root();
+    wallet.sign_header(&mut blk.header);
 
     match chain.isolated(|chain| {
         chain.apply_block(&blk)?; // Check if everything is ok
@@ -0,0 +1,90 @@
+use super::*;
+
+fn involved_addresses(tx: &Transaction) -> Vec<Address> {
+    let mut addrs = vec![tx.src.clone().unwrap_or_default()];
+    match &tx.data {
+        TransactionData::RegularSend { entries } => {
+            addrs.extend(entries.iter().map(|e| e.dst.clone()));
+        }
+        TransactionData::Approve { spender, .. } => {
+            addrs.push(spender.clone());
+        }
+        TransactionData::TransferFrom { owner, entries } => {
+            addrs.push(owner.clone());
+            addrs.extend(entries.iter().map(|e| e.dst.clone()));
+        }
+        TransactionData::Delegate { to, .. } => {
+            addrs.push(to.clone());
+        }
+        TransactionData::ReclaimDelegate { from } => {
+            addrs.push(from.clone());
+        }
+        TransactionData::SlashValidator { first, .. } => {
+            addrs.push(first.proof_of_stake.validator.clone());
+        }
+        TransactionData::CreateVesting { vesting } => {
+            addrs.push(vesting.beneficiary.clone());
+        }
+        TransactionData::UpdateToken {
+            update: TokenUpdate::ChangeMinter { minter },
+            ..
+        } => {
+            addrs.push(minter.clone());
+        }
+        TransactionData::UpdateContract { updates, .. } => {
+            for update in updates.iter() {
+                match update {
+                    ContractUpdate::Deposit { deposits, .. } => {
+                        addrs.extend(deposits.iter().map(|d| d.src.clone()));
+                    }
+                    ContractUpdate::Withdraw { withdraws, .. } => {
+                        addrs.extend(withdraws.iter().map(|w| w.dst.clone()));
+                    }
+                    ContractUpdate::FunctionCall { .. } => {}
+                }
+            }
+        }
+        _ => {}
+    }
+    let mut unique = Vec::new();
+    for addr in addrs {
+        if !unique.contains(&addr) {
+            unique.push(addr);
+        }
+    }
+    unique
+}
+
+pub fn index_tx<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    height: u64,
+    index: u32,
+    tx: &Transaction,
+    side_effect: &TxSideEffect,
+) -> Result<(), BlockchainError> {
+    let tx_hash = tx.hash();
+    let mut ops = vec![WriteOp::Put(
+        keys::tx_receipt(&tx_hash),
+        (&TransactionReceipt {
+            height,
+            index,
+            fee: tx.fee,
+            side_effect: side_effect.clone(),
+        })
+            .into(),
+    )];
+    for address in involved_addresses(tx) {
+        ops.push(WriteOp::Put(
+            keys::AddressHistoryDbKey {
+                address,
+                height,
+                index,
+                tx_hash,
+            }
+            .into(),
+            ().into(),
+        ));
+    }
+    chain.database.update(&ops)?;
+    Ok(())
+}
@@ -19,3 +19,9 @@ mod rollback;
 pub use rollback::*;
 mod pay_validator_and_delegators;
 pub use pay_validator_and_delegators::*;
+mod index_tx;
+pub use index_tx::*;
+mod prune;
+pub use prune::*;
+mod verify_blocks;
+pub use verify_blocks::*;
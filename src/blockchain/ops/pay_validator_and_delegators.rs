@@ -1,19 +1,58 @@
 use super::*;
 
+/// Rewards are paid before the txs of the block are applied, so the epoch is
+/// derived from the timestamp of the block being applied and not the tip.
 pub fn pay_validator_and_delegators<K: KvStore>(
     chain: &mut KvStoreChain<K>,
+    timestamp: u32,
     validator: Address,
     fee_sum: Amount,
+) -> Result<Amount, BlockchainError> {
+    let next_reward = chain.next_reward()? + fee_sum;
+    distribute_reward(
+        chain,
+        timestamp,
+        validator,
+        Money {
+            amount: next_reward,
+            token_id: TokenId::Ziesha,
+        },
+    )
+}
+
+/// Fees paid in tokens other than Ziesha are collected by the Treasury while the
+/// block txs are applied, so they can only be distributed after that.
+pub fn pay_token_fees<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    timestamp: u32,
+    validator: Address,
+    token_fees: &[Money],
+) -> Result<(), BlockchainError> {
+    for fee in token_fees.iter() {
+        if fee.amount > Amount(0) {
+            distribute_reward(chain, timestamp, validator.clone(), *fee)?;
+        }
+    }
+    Ok(())
+}
+
+fn distribute_reward<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    timestamp: u32,
+    validator: Address,
+    reward: Money,
 ) -> Result<Amount, BlockchainError> {
     let staker = chain
         .get_staker(validator.clone())?
         .ok_or(BlockchainError::ValidatorNotRegistered)?;
 
+    let (epoch, _) = chain.epoch_slot(timestamp);
+    let commision = staker.commision_at(epoch);
+
     let treasury_nonce = chain.get_nonce(Default::default())?;
 
-    let next_reward = chain.next_reward()? + fee_sum;
     let stakers_reward =
-        u64::from(next_reward) as f64 * ((u8::MAX - staker.commision) as f64 / u8::MAX as f64); // WARN: Hardcoded!
+        u64::from(reward.amount) as f64 * ((u8::MAX - commision) as f64 / u8::MAX as f64); // WARN: Hardcoded!
 
     let delegators = chain.get_delegators(validator.clone(), None)?;
     let total_f64 = delegators
@@ -30,11 +69,21 @@ pub fn pay_validator_and_delegators<K: KvStore>(
         })
         .collect::<Vec<_>>();
 
-    let validator_reward = next_reward
+    let validator_reward = reward.amount
         - payments
             .iter()
             .map(|(_, a)| *a)
             .fold(Amount(0), |a, b| a + b);
+    if reward.token_id == TokenId::Ziesha {
+        record_rewards(
+            chain,
+            epoch,
+            &validator,
+            reward.amount,
+            validator_reward,
+            &payments,
+        )?;
+    }
     payments.push((validator.clone(), validator_reward));
     for (i, (addr, amnt)) in payments.into_iter().enumerate() {
         chain.apply_tx(
@@ -46,7 +95,7 @@ pub fn pay_validator_and_delegators<K: KvStore>(
                         dst: addr,
                         amount: Money {
                             amount: amnt,
-                            token_id: TokenId::Ziesha,
+                            token_id: reward.token_id,
                         },
                     }],
                 },
@@ -59,3 +108,47 @@ pub fn pay_validator_and_delegators<K: KvStore>(
     }
     Ok(validator_reward)
 }
+
+/// Adds the Ziesha rewards of a block to the reward reports of the epoch
+fn record_rewards<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    epoch: u32,
+    validator: &Address,
+    reward: Amount,
+    commision: Amount,
+    payments: &[(Address, Amount)],
+) -> Result<(), BlockchainError> {
+    let mut report = chain
+        .database
+        .get(keys::validator_reward(validator, epoch))?
+        .map(|v| v.try_into())
+        .transpose()?
+        .unwrap_or(ValidatorReward {
+            epoch,
+            reward: Amount(0),
+            commision: Amount(0),
+        });
+    report.reward += reward;
+    report.commision += commision;
+    let mut ops = vec![WriteOp::Put(
+        keys::validator_reward(validator, epoch),
+        report.into(),
+    )];
+    for (delegator, amount) in payments.iter() {
+        let key = keys::delegator_reward(delegator, epoch, validator);
+        let mut payout = chain
+            .database
+            .get(key.clone())?
+            .map(|v| v.try_into())
+            .transpose()?
+            .unwrap_or(DelegatorReward {
+                epoch,
+                validator: validator.clone(),
+                amount: Amount(0),
+            });
+        payout.amount += *amount;
+        ops.push(WriteOp::Put(key, payout.into()));
+    }
+    chain.database.update(&ops)?;
+    Ok(())
+}
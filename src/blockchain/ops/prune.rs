@@ -0,0 +1,45 @@
+use super::*;
+
+/// Deletes the body, rollback diff and merkle tree of the block falling out of
+/// the pruning window after applying block `height`. Headers are kept. The
+/// compressed states replaced in that block are deleted too, so that only the
+/// states which were current within the window remain.
+pub fn prune_blocks<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    height: u64,
+) -> Result<(), BlockchainError> {
+    let window = if let Some(window) = chain.config.pruning {
+        window
+    } else {
+        return Ok(());
+    };
+    // Genesis block is never pruned
+    if height <= window {
+        return Ok(());
+    }
+    let index = height - window;
+    if index < chain.get_pruned_height()? {
+        return Ok(());
+    }
+    let mut ops = vec![
+        WriteOp::Remove(keys::block(index)),
+        WriteOp::Remove(keys::rollback(index)),
+        WriteOp::Remove(keys::merkle(index)),
+        WriteOp::Put(keys::pruned_height(), (index + 1).into()),
+    ];
+    if let Some(b) = chain.database.get(keys::block_contract_updates(index))? {
+        let state_updates: HashMap<ContractId, ZkCompressedStateChange> = b.try_into()?;
+        for (cid, change) in state_updates.iter() {
+            // The empty state at height 0 is never stored
+            if change.prev_height > 0 {
+                ops.push(WriteOp::Remove(keys::compressed_state_at(
+                    cid,
+                    change.prev_height,
+                )));
+            }
+        }
+        ops.push(WriteOp::Remove(keys::block_contract_updates(index)));
+    }
+    chain.database.update(&ops)?;
+    Ok(())
+}
@@ -11,7 +11,11 @@ pub fn rollback<K: KvStore>(chain: &mut KvStoreChain<K>) -> Result<(), Blockchai
         let rollback: Vec<WriteOp> = match chain.database.get(keys::rollback(height - 1))? {
             Some(b) => b.try_into()?,
             None => {
-                return Err(BlockchainError::Inconsistency);
+                return Err(if height - 1 < chain.get_pruned_height()? {
+                    BlockchainError::RollbackPruned
+                } else {
+                    BlockchainError::Inconsistency
+                });
             }
         };
 
@@ -2,16 +2,17 @@ use super::*;
 
 pub fn select_transactions<K: KvStore>(
     chain: &KvStoreChain<K>,
+    timestamp: u32,
     validator: Address,
     txs: &[TransactionAndDelta],
     check: bool,
+    fee_token_prices: &FeeTokenPrices,
 ) -> Result<Vec<TransactionAndDelta>, BlockchainError> {
     let mut sorted = txs
         .iter()
-        .filter(|t| t.tx.fee.token_id == TokenId::Ziesha)
+        .filter(|t| fee_token_prices.fee_in_ziesha(&t.tx.fee).is_some())
         .cloned()
         .collect::<Vec<_>>();
-    // WARN: Sort will be invalid if not all fees are specified in Ziesha
     sorted.sort_unstable_by_key(|tx| {
         let cost = tx.tx.size();
         let is_mpn = if let TransactionData::UpdateContract { contract_id, .. } = &tx.tx.data {
@@ -21,7 +22,11 @@ pub fn select_transactions<K: KvStore>(
         };
         (
             is_mpn,
-            Into::<u64>::into(tx.tx.fee.amount) / cost as u64,
+            Into::<u64>::into(
+                fee_token_prices
+                    .fee_in_ziesha(&tx.tx.fee)
+                    .unwrap_or_default(),
+            ) / cost as u64,
             -(tx.tx.nonce as i32),
         )
     });
@@ -30,7 +35,7 @@ pub fn select_transactions<K: KvStore>(
     }
     let (_, result) = chain.isolated(|chain| {
         // Safe to consider a 0 fee-sum
-        chain.pay_validator_and_delegators(validator, Amount(0))?;
+        chain.pay_validator_and_delegators(timestamp, validator, Amount(0))?;
 
         let mut result = Vec::new();
         let mut block_sz = 0usize;
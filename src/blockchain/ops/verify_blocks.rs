@@ -0,0 +1,60 @@
+use super::*;
+
+/// Outcome of the checks of a block that don't depend on the chain state
+#[derive(Debug, Clone)]
+pub struct BlockVerification {
+    /// VRF public-key which the validator proof of the block has been
+    /// verified against, if it was valid
+    pub vrf_pub_key: Option<<Vrf as VerifiableRandomFunction>::Pub>,
+}
+
+/// Checks merkle roots, transaction and header signatures and validator proofs
+/// of a batch of blocks in parallel, so that only the state transitions of the
+/// blocks remain to be applied sequentially. Validator proofs are checked against the
+/// VRF keys of the validators on the current state.
+pub fn verify_blocks<K: KvStore>(
+    chain: &KvStoreChain<K>,
+    blocks: &[Block],
+) -> Result<Vec<BlockVerification>, BlockchainError> {
+    let vrf_inputs = blocks
+        .iter()
+        .map(|b| {
+            let (epoch, slot) = chain.epoch_slot(b.header.proof_of_stake.timestamp);
+            let vrf_pub_key = chain
+                .get_staker(b.header.proof_of_stake.validator.clone())?
+                .map(|s| s.vrf_pub_key);
+            Ok((vrf_pub_key, format!("{}-{}", epoch, slot)))
+        })
+        .collect::<Result<Vec<_>, BlockchainError>>()?;
+
+    blocks
+        .par_iter()
+        .zip(vrf_inputs.into_par_iter())
+        .map(|(block, (vrf_pub_key, vrf_input))| {
+            if block.header.number == 0 {
+                return Ok(BlockVerification { vrf_pub_key: None });
+            }
+            if block.merkle_tree().root() != block.header.block_root {
+                return Err(BlockchainError::InvalidMerkleRoot);
+            }
+            if !block.body.par_iter().all(|tx| tx.verify_signature()) {
+                return Err(BlockchainError::SignatureError);
+            }
+            if !block.header.verify_signature() {
+                return Err(BlockchainError::InvalidHeaderSignature);
+            }
+            let vrf_pub_key = vrf_pub_key.filter(|pub_key| {
+                if let ValidatorProof::Proof {
+                    vrf_output,
+                    vrf_proof,
+                } = &block.header.proof_of_stake.proof
+                {
+                    Vrf::verify(pub_key, vrf_input.as_bytes(), vrf_output, vrf_proof)
+                } else {
+                    false
+                }
+            });
+            Ok(BlockVerification { vrf_pub_key })
+        })
+        .collect()
+}
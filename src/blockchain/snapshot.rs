@@ -0,0 +1,148 @@
+use super::{Blockchain, BlockchainConfig, BlockchainError, KvStoreChain};
+use crate::db::{keys, Blob, KvStore, StringKey, WriteOp};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+pub const SNAPSHOT_VERSION: u32 = 3;
+
+/// Number of database writes applied at once while importing
+const IMPORT_BATCH_SIZE: usize = 1024;
+
+/// Beginning of a snapshot file. It's followed by `pairs` bincode-encoded
+/// `(StringKey, Blob)` pairs of the database, in key order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotHeader {
+    pub version: u32,
+    pub height: u64,
+    pub pairs: u64,
+    /// `db_checksum` of the database at `height`, so it can be compared with
+    /// the checksum reported by other nodes. It can still be recomputed by
+    /// anyone who changes the data, so snapshots should only be imported from
+    /// a trusted source.
+    pub checksum: String,
+}
+
+/// `db_checksum` computed while the pairs are streamed, instead of collecting
+/// the whole database in memory: SHA3-256 of the bincode-encoded list of the
+/// pairs, in key order.
+struct Checksum(Sha3_256);
+
+impl Checksum {
+    fn new(pairs: u64) -> Self {
+        let mut hasher = Sha3_256::new();
+        // Length prefix of the list
+        hasher.update(pairs.to_le_bytes());
+        Self(hasher)
+    }
+    fn update(&mut self, pair: &(StringKey, Blob)) -> Result<(), BlockchainError> {
+        self.0.update(bincode::serialize(pair)?);
+        Ok(())
+    }
+    fn finalize(self) -> String {
+        hex::encode(self.0.finalize())
+    }
+}
+
+fn read_header<R: Read>(reader: &mut R) -> Result<SnapshotHeader, BlockchainError> {
+    let header: SnapshotHeader = bincode::deserialize_from(reader)?;
+    if header.version != SNAPSHOT_VERSION {
+        return Err(BlockchainError::SnapshotVersionMismatch);
+    }
+    Ok(header)
+}
+
+impl<K: KvStore> KvStoreChain<K> {
+    /// Writes the database as it was at the given height (Current height if
+    /// `None`). The database is streamed three times, for counting the pairs,
+    /// for the checksum and for the pairs themselves, instead of being loaded
+    /// in memory.
+    pub fn export_snapshot<W: Write>(
+        &self,
+        height: Option<u64>,
+        mut writer: W,
+    ) -> Result<SnapshotHeader, BlockchainError> {
+        let curr_height = self.get_height()?;
+        let height = height.unwrap_or(curr_height);
+        if height == 0 || height > curr_height {
+            return Err(BlockchainError::InvalidSnapshotHeight);
+        }
+
+        let mut fork = self.fork_on_ram();
+        while fork.get_height()? > height {
+            fork.rollback()?;
+        }
+
+        let pairs = fork.database.pairs("".into())?.into_iter().count() as u64;
+        let mut checksum = Checksum::new(pairs);
+        for pair in fork.database.pairs("".into())?.into_iter() {
+            checksum.update(&pair)?;
+        }
+        let header = SnapshotHeader {
+            version: SNAPSHOT_VERSION,
+            height,
+            pairs,
+            checksum: checksum.finalize(),
+        };
+        bincode::serialize_into(&mut writer, &header)?;
+        for pair in fork.database.pairs("".into())?.into_iter() {
+            bincode::serialize_into(&mut writer, &pair)?;
+        }
+        writer.flush()?;
+        Ok(header)
+    }
+
+    /// Boots a blockchain from a snapshot, only keeping the rollback data of the
+    /// last `keep_rollbacks` blocks. Older blocks are marked as pruned. The
+    /// snapshot is read twice, so that nothing is written unless its checksum
+    /// matches.
+    pub fn import_snapshot<R: Read + Seek>(
+        mut database: K,
+        config: BlockchainConfig,
+        mut reader: R,
+        keep_rollbacks: u64,
+    ) -> Result<KvStoreChain<K>, BlockchainError> {
+        if database.pairs("".into())?.into_iter().next().is_some() {
+            return Err(BlockchainError::DatabaseNotEmpty);
+        }
+
+        let header = read_header(&mut reader)?;
+        let mut checksum = Checksum::new(header.pairs);
+        for _ in 0..header.pairs {
+            checksum.update(&bincode::deserialize_from(&mut reader)?)?;
+        }
+        if checksum.finalize() != header.checksum {
+            return Err(BlockchainError::SnapshotChecksumMismatch);
+        }
+
+        reader.seek(SeekFrom::Start(0))?;
+        read_header(&mut reader)?;
+        let mut ops = Vec::with_capacity(IMPORT_BATCH_SIZE);
+        for _ in 0..header.pairs {
+            let (k, v): (StringKey, Blob) = bincode::deserialize_from(&mut reader)?;
+            ops.push(WriteOp::Put(k, v));
+            if ops.len() == IMPORT_BATCH_SIZE {
+                database.update(&ops)?;
+                ops.clear();
+            }
+        }
+        let pruned_height = header.height.saturating_sub(keep_rollbacks);
+        for h in 0..pruned_height {
+            ops.push(WriteOp::Remove(keys::rollback(h)));
+            if ops.len() == IMPORT_BATCH_SIZE {
+                database.update(&ops)?;
+                ops.clear();
+            }
+        }
+        if pruned_height > 0 {
+            ops.push(WriteOp::Put(keys::pruned_height(), pruned_height.into()));
+        }
+        database.update(&ops)?;
+
+        let chain = KvStoreChain::new(database, config)?;
+        if chain.get_height()? != header.height {
+            return Err(BlockchainError::Inconsistency);
+        }
+        Ok(chain)
+    }
+}
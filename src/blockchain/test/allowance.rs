@@ -0,0 +1,105 @@
+use super::*;
+
+#[test]
+fn test_allowances() {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("DCBA"));
+    let charlie = TxBuilder::new(&Vec::from("CBA"));
+
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    let (tx, token_id) = alice.create_token(
+        "".into(),
+        "My Token".into(),
+        "MYT".into(),
+        Amount(1000),
+        0,
+        None,
+        None,
+        None,
+        Money::ziesha(0),
+        1,
+    );
+    apply_txs(&mut chain, 1, &[tx], &miner).unwrap();
+
+    let approve = |amount: u64, nonce: u32| {
+        alice.approve(
+            "".into(),
+            bob.get_address(),
+            Money::new(token_id, amount),
+            Money::ziesha(0),
+            nonce,
+        )
+    };
+    let transfer_from = |amount: u64, nonce: u32| {
+        bob.transfer_from(
+            "".into(),
+            alice.get_address(),
+            vec![RegularSendEntry {
+                dst: charlie.get_address(),
+                amount: Money::new(token_id, amount),
+            }],
+            Money::ziesha(0),
+            nonce,
+        )
+    };
+    let allowance = |chain: &KvStoreChain<db::RamKvStore>| {
+        chain
+            .get_allowance(alice.get_address(), bob.get_address(), token_id)
+            .unwrap()
+    };
+
+    // Nothing can be spent before an approval
+    assert!(matches!(
+        apply_txs(&mut chain, 2, &[transfer_from(1, 1)], &miner),
+        Err(BlockchainError::AllowanceInsufficient)
+    ));
+
+    apply_txs(&mut chain, 2, &[approve(100, 2)], &miner).unwrap();
+    assert_eq!(allowance(&chain), Amount(100));
+
+    apply_txs(&mut chain, 3, &[transfer_from(30, 1)], &miner).unwrap();
+    assert_eq!(allowance(&chain), Amount(70));
+    assert_eq!(
+        chain.get_balance(alice.get_address(), token_id).unwrap(),
+        Amount(970)
+    );
+    assert_eq!(
+        chain.get_balance(charlie.get_address(), token_id).unwrap(),
+        Amount(30)
+    );
+    assert_eq!(
+        chain.get_balance(bob.get_address(), token_id).unwrap(),
+        Amount(0)
+    );
+
+    // Spending more than the remaining allowance fails
+    assert!(matches!(
+        apply_txs(&mut chain, 4, &[transfer_from(71, 2)], &miner),
+        Err(BlockchainError::AllowanceInsufficient)
+    ));
+
+    // Rolling back restores the allowance
+    chain.rollback().unwrap();
+    assert_eq!(allowance(&chain), Amount(100));
+    assert_eq!(
+        chain.get_balance(charlie.get_address(), token_id).unwrap(),
+        Amount(0)
+    );
+    apply_txs(&mut chain, 3, &[transfer_from(30, 1)], &miner).unwrap();
+
+    // Approving zero revokes the allowance
+    apply_txs(&mut chain, 4, &[approve(0, 3)], &miner).unwrap();
+    assert_eq!(allowance(&chain), Amount(0));
+    assert!(matches!(
+        apply_txs(&mut chain, 5, &[transfer_from(1, 2)], &miner),
+        Err(BlockchainError::AllowanceInsufficient)
+    ));
+
+    rollback_till_empty(&mut chain).unwrap();
+}
@@ -0,0 +1,237 @@
+use super::*;
+
+fn is_proven<K: KvStore>(chain: &KvStoreChain<K>, timestamp: u32, wallet: &TxBuilder) -> bool {
+    !chain
+        .validator_status(timestamp, wallet)
+        .unwrap()
+        .is_unproven()
+}
+
+fn draft<K: KvStore>(chain: &KvStoreChain<K>, timestamp: u32, wallet: &TxBuilder) -> Block {
+    chain
+        .draft_block(timestamp, &[], wallet, true)
+        .unwrap()
+        .unwrap()
+        .block
+}
+
+#[test]
+fn test_heavier_fork_wins() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let validator2 = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    let proven_ts = (0..)
+        .map(|slot| slot * chain.config.slot_duration)
+        .find(|ts| is_proven(&chain, *ts, &validator))
+        .unwrap();
+    let unproven_ts = (0..)
+        .map(|slot| slot * chain.config.slot_duration)
+        .find(|ts| !is_proven(&chain, *ts, &validator2))
+        .unwrap();
+
+    // A single block by a VRF-proven validator
+    let mut heavy = chain.fork_on_ram();
+    let heavy_blk = draft(&heavy, proven_ts, &validator);
+    heavy.extend(1, std::slice::from_ref(&heavy_blk)).unwrap();
+
+    // Two blocks without a valid VRF proof
+    let mut light = chain.fork_on_ram();
+    let light_blk1 = draft(&light, unproven_ts, &validator2);
+    light.extend(1, std::slice::from_ref(&light_blk1)).unwrap();
+    let light_blk2 = draft(&light, unproven_ts, &validator2);
+    light.extend(2, std::slice::from_ref(&light_blk2)).unwrap();
+    assert_eq!(light.get_height().unwrap(), 3);
+
+    // The longer fork is not accepted since it carries less stake weight
+    assert!(!heavy
+        .will_extend(1, &[light_blk1.header.clone(), light_blk2.header.clone()])
+        .unwrap());
+    assert!(matches!(
+        heavy.extend(1, &[light_blk1, light_blk2]),
+        Err(BlockchainError::WeakerFork)
+    ));
+    assert_eq!(heavy.get_height().unwrap(), 2);
+
+    // The shorter, heavier fork replaces the longer one
+    assert!(light
+        .will_extend(1, std::slice::from_ref(&heavy_blk.header))
+        .unwrap());
+    light.extend(1, std::slice::from_ref(&heavy_blk)).unwrap();
+    assert_eq!(light.get_height().unwrap(), 2);
+    assert_eq!(light.get_header(1).unwrap(), heavy_blk.header);
+
+    rollback_till_empty(&mut heavy).unwrap();
+    rollback_till_empty(&mut light).unwrap();
+    drop((heavy, light));
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_fork_choice_by_stake() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let validator2 = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    // VALIDATOR ends up with three times the stake of VALIDATOR2
+    let delegations = [
+        alice.delegate(
+            "".into(),
+            validator.get_address(),
+            Amount(5975),
+            false,
+            Money::ziesha(0),
+            1,
+        ),
+        alice.delegate(
+            "".into(),
+            validator2.get_address(),
+            Amount(1975),
+            false,
+            Money::ziesha(0),
+            2,
+        ),
+    ];
+    let blk = chain
+        .draft_block(1, &delegations, &validator, true)
+        .unwrap()
+        .unwrap()
+        .block;
+    assert_eq!(blk.body.len(), 2);
+    chain.extend(1, &[blk]).unwrap();
+    assert_eq!(
+        chain.get_stake(validator.get_address()).unwrap(),
+        Amount(6000)
+    );
+    assert_eq!(
+        chain.get_stake(validator2.get_address()).unwrap(),
+        Amount(2000)
+    );
+
+    // Both validators are proven, on a later epoch
+    let epoch_duration = chain.config.slot_duration * chain.config.slot_per_epoch;
+    let proven_ts = |wallet: &TxBuilder| {
+        (0..)
+            .map(|slot| epoch_duration + slot * chain.config.slot_duration)
+            .find(|ts| is_proven(&chain, *ts, wallet))
+            .unwrap()
+    };
+    let heavy_ts = proven_ts(&validator);
+    let light_ts = proven_ts(&validator2);
+
+    let mut heavy = chain.fork_on_ram();
+    let heavy_blk = draft(&heavy, heavy_ts, &validator);
+    heavy.extend(2, std::slice::from_ref(&heavy_blk)).unwrap();
+
+    let mut light = chain.fork_on_ram();
+    let light_blk1 = draft(&light, light_ts, &validator2);
+    light.extend(2, std::slice::from_ref(&light_blk1)).unwrap();
+    let light_blk2 = draft(&light, light_ts, &validator2);
+    light.extend(3, std::slice::from_ref(&light_blk2)).unwrap();
+
+    // Two blocks of the smaller staker weigh less than one of the bigger one
+    assert!(!heavy
+        .will_extend(2, &[light_blk1.header.clone(), light_blk2.header.clone()])
+        .unwrap());
+    assert!(matches!(
+        heavy.extend(2, &[light_blk1, light_blk2]),
+        Err(BlockchainError::WeakerFork)
+    ));
+    assert_eq!(heavy.get_height().unwrap(), 3);
+
+    assert!(light
+        .will_extend(2, std::slice::from_ref(&heavy_blk.header))
+        .unwrap());
+    light.extend(2, std::slice::from_ref(&heavy_blk)).unwrap();
+    assert_eq!(light.get_height().unwrap(), 3);
+    assert_eq!(light.get_header(2).unwrap(), heavy_blk.header);
+
+    rollback_till_empty(&mut heavy).unwrap();
+    rollback_till_empty(&mut light).unwrap();
+    drop((heavy, light));
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_extend_with_no_blocks() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+    let blk = draft(&chain, 10, &validator);
+    chain.extend(1, &[blk]).unwrap();
+
+    chain.extend(2, &[]).unwrap();
+    chain.extend(1, &[]).unwrap();
+    assert_eq!(chain.get_height().unwrap(), 2);
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_max_reorg_depth() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.max_reorg_depth = 2;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    let mut base = chain.fork_on_ram();
+    let blk = draft(&base, 10, &validator);
+    base.extend(1, &[blk]).unwrap();
+
+    let mut local = base.fork_on_ram();
+    for i in 0..3 {
+        let blk = draft(&local, 20 + i, &validator);
+        local.apply_block(&blk).unwrap();
+    }
+    assert_eq!(local.get_height().unwrap(), 5);
+
+    let mut fork = base.fork_on_ram();
+    let mut fork_blocks = Vec::new();
+    for i in 0..5 {
+        let blk = draft(&fork, 30 + i, &validator);
+        fork.apply_block(&blk).unwrap();
+        fork_blocks.push(blk);
+    }
+    assert_eq!(fork.get_height().unwrap(), 7);
+
+    // Rolling back 3 blocks is not allowed
+    let fork_headers = fork_blocks
+        .iter()
+        .map(|b| b.header.clone())
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        local.will_extend(2, &fork_headers),
+        Err(BlockchainError::ReorgTooDeep)
+    ));
+    assert!(matches!(
+        local.extend(2, &fork_blocks),
+        Err(BlockchainError::ReorgTooDeep)
+    ));
+    assert_eq!(local.get_height().unwrap(), 5);
+
+    // Once the local chain is not that far ahead of the fork-point, the
+    // heavier/longer fork is accepted
+    local.rollback().unwrap();
+    assert!(local.will_extend(2, &fork_headers).unwrap());
+    local.extend(2, &fork_blocks).unwrap();
+    assert_eq!(local.get_height().unwrap(), 7);
+
+    rollback_till_empty(&mut local).unwrap();
+    rollback_till_empty(&mut fork).unwrap();
+    drop((local, fork));
+    rollback_till_empty(&mut base).unwrap();
+    drop(base);
+    rollback_till_empty(&mut chain).unwrap();
+}
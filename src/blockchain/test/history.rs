@@ -0,0 +1,229 @@
+use super::*;
+
+#[test]
+fn test_tx_history_and_receipts() {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("DCBA"));
+
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    assert!(chain
+        .get_tx_history(bob.get_address(), 0, 10)
+        .unwrap()
+        .is_empty());
+
+    let tx1 = alice.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(100),
+        Money::ziesha(2),
+        1,
+    );
+    let tx2 = alice.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(50),
+        Money::ziesha(1),
+        2,
+    );
+    chain
+        .apply_block(
+            &chain
+                .draft_block(1, std::slice::from_ref(&tx1), &miner, false)
+                .unwrap()
+                .unwrap()
+                .block,
+        )
+        .unwrap();
+    chain
+        .apply_block(
+            &chain
+                .draft_block(2, std::slice::from_ref(&tx2), &miner, false)
+                .unwrap()
+                .unwrap()
+                .block,
+        )
+        .unwrap();
+
+    // Newest transactions come first
+    let bob_history = chain.get_tx_history(bob.get_address(), 0, 10).unwrap();
+    assert_eq!(
+        bob_history,
+        vec![(2, 0, tx2.tx.hash()), (1, 0, tx1.tx.hash())]
+    );
+
+    // Alice also has the genesis funding transaction in her history
+    assert_eq!(
+        chain
+            .get_tx_history(alice.get_address(), 0, 10)
+            .unwrap()
+            .len(),
+        3
+    );
+    assert_eq!(
+        chain
+            .get_tx_history(alice.get_address(), 1, 2)
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(matches!(
+        chain.get_tx_history(alice.get_address(), 1, MAX_TX_HISTORY_PAGE_SIZE + 1),
+        Err(BlockchainError::TxHistoryPageOutOfRange)
+    ));
+    assert!(matches!(
+        chain.get_tx_history(alice.get_address(), usize::MAX, 2),
+        Err(BlockchainError::TxHistoryPageOutOfRange)
+    ));
+
+    assert_eq!(
+        chain.get_tx_receipt(tx1.tx.hash()).unwrap(),
+        Some(TransactionReceipt {
+            height: 1,
+            index: 0,
+            fee: Money::ziesha(2),
+            side_effect: TxSideEffect::Nothing,
+        })
+    );
+
+    chain.rollback().unwrap();
+    assert_eq!(
+        chain.get_tx_history(bob.get_address(), 0, 10).unwrap(),
+        vec![(1, 0, tx1.tx.hash())]
+    );
+    assert_eq!(chain.get_tx_receipt(tx2.tx.hash()).unwrap(), None);
+
+    assert_eq!(chain.get_tx_history_since().unwrap(), 0);
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_tx_history_disabled() {
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.tx_history = false;
+    let chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+
+    assert!(matches!(
+        chain.get_tx_history(alice.get_address(), 0, 10),
+        Err(BlockchainError::TxHistoryDisabled)
+    ));
+    assert!(chain
+        .database
+        .pairs(keys::AddressHistoryDbKey::prefix(&alice.get_address()).into())
+        .unwrap()
+        .into_iter()
+        .next()
+        .is_none());
+}
+
+#[test]
+fn test_tx_history_enabled_later() {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("DCBA"));
+
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.tx_history = false;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf.clone()).unwrap();
+    let tx1 = alice.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(100),
+        Money::ziesha(2),
+        1,
+    );
+    chain
+        .apply_block(
+            &chain
+                .draft_block(1, std::slice::from_ref(&tx1), &miner, false)
+                .unwrap()
+                .unwrap()
+                .block,
+        )
+        .unwrap();
+
+    conf.tx_history = true;
+    let mut chain = KvStoreChain::new(chain.database, conf.clone()).unwrap();
+    assert_eq!(chain.get_tx_history_since().unwrap(), 2);
+    let tx2 = alice.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(50),
+        Money::ziesha(1),
+        2,
+    );
+    chain
+        .apply_block(
+            &chain
+                .draft_block(2, std::slice::from_ref(&tx2), &miner, false)
+                .unwrap()
+                .unwrap()
+                .block,
+        )
+        .unwrap();
+    assert_eq!(chain.get_tx_history_since().unwrap(), 2);
+    assert_eq!(
+        chain.get_tx_history(bob.get_address(), 0, 10).unwrap(),
+        vec![(2, 0, tx2.tx.hash())]
+    );
+
+    // Disabling the history forgets where it started
+    conf.tx_history = false;
+    let chain = KvStoreChain::new(chain.database, conf.clone()).unwrap();
+    conf.tx_history = true;
+    let chain = KvStoreChain::new(chain.database, conf).unwrap();
+    assert_eq!(chain.get_tx_history_since().unwrap(), 3);
+}
+
+#[test]
+fn test_get_transaction_by_hash() {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("DCBA"));
+
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.tx_history = false;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    let tx = alice.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(100),
+        Money::ziesha(2),
+        1,
+    );
+    assert!(chain.get_transaction(tx.tx.hash()).unwrap().is_none());
+
+    chain
+        .apply_block(
+            &chain
+                .draft_block(1, std::slice::from_ref(&tx), &miner, false)
+                .unwrap()
+                .unwrap()
+                .block,
+        )
+        .unwrap();
+    let header = chain.get_header(1).unwrap();
+    assert_eq!(
+        chain.get_transaction(tx.tx.hash()).unwrap(),
+        Some(TransactionLocation {
+            height: 1,
+            index: 0,
+            block_hash: header.hash(),
+            timestamp: header.proof_of_stake.timestamp,
+            confirmations: 1,
+            tx: Some(tx.tx.clone()),
+        })
+    );
+
+    chain.rollback().unwrap();
+    assert!(chain.get_transaction(tx.tx.hash()).unwrap().is_none());
+
+    rollback_till_empty(&mut chain).unwrap();
+}
@@ -0,0 +1,93 @@
+use super::*;
+
+fn build_chain(num_blocks: u32) -> (KvStoreChain<db::RamKvStore>, Vec<Block>) {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+    for i in 1..=num_blocks {
+        let blk = chain
+            .draft_block(
+                i,
+                &[alice.create_transaction(
+                    "".into(),
+                    miner.get_address(),
+                    Money::ziesha(100),
+                    Money::ziesha(0),
+                    i,
+                )],
+                &miner,
+                true,
+            )
+            .unwrap()
+            .unwrap()
+            .block;
+        chain.apply_block(&blk).unwrap();
+    }
+    let blocks = chain.get_blocks(1, num_blocks as u64).unwrap();
+    (chain, blocks)
+}
+
+#[test]
+fn test_import_blocks() {
+    let (source, blocks) = build_chain(5);
+    assert_eq!(blocks.len(), 5);
+
+    let mut extended = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+    extended.extend(1, &blocks).unwrap();
+
+    let mut imported = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+    imported.import(1, &blocks).unwrap();
+
+    assert_eq!(imported.get_height().unwrap(), 6);
+    assert_eq!(
+        imported.db_checksum().unwrap(),
+        source.db_checksum().unwrap()
+    );
+    assert_eq!(
+        imported.db_checksum().unwrap(),
+        extended.db_checksum().unwrap()
+    );
+}
+
+#[test]
+fn test_import_invalid_blocks() {
+    let (_, blocks) = build_chain(3);
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+    let checksum = chain.db_checksum().unwrap();
+
+    let mut wrong_root = blocks.clone();
+    wrong_root[2].header.block_root = Default::default();
+    assert!(matches!(
+        chain.import(1, &wrong_root),
+        Err(BlockchainError::InvalidMerkleRoot)
+    ));
+
+    let mut wrong_sig = blocks.clone();
+    wrong_sig[2].body[0].nonce += 1;
+    wrong_sig[2].header.block_root = wrong_sig[2].merkle_tree().root();
+    assert!(matches!(
+        chain.import(1, &wrong_sig),
+        Err(BlockchainError::SignatureError)
+    ));
+
+    // Nothing is applied when any of the blocks is invalid
+    assert_eq!(chain.db_checksum().unwrap(), checksum);
+    chain.import(1, &blocks).unwrap();
+    assert_eq!(chain.get_height().unwrap(), 4);
+}
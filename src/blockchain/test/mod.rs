@@ -1,16 +1,31 @@
 use super::*;
 use crate::config::blockchain;
-use crate::core::{Hasher, Signature, Signer, TransactionData};
+use crate::core::{
+    CommisionChange, Hasher, Signature, Signer, TokenMetadata, TokenUpdate, TransactionData,
+};
 use crate::crypto::SignatureScheme;
 use crate::db;
 
+mod allowance;
 mod contract;
+mod fork_choice;
+mod history;
+mod import;
+mod multisig;
+mod pruning;
 mod rewards;
+mod slashing;
+mod snapshot;
+mod staking;
 mod tokens;
+mod vesting;
 
 fn rollback_till_empty<K: KvStore>(b: &mut KvStoreChain<K>) -> Result<(), BlockchainError> {
     while b.get_height()? > 0 {
-        assert_eq!(b.currency_in_circulation()?, Amount(2000000000000000000));
+        assert_eq!(
+            b.currency_in_circulation()? + b.get_burned(TokenId::Ziesha)?,
+            b.get_token(TokenId::Ziesha)?.unwrap().supply
+        );
         b.rollback()?;
     }
     assert_eq!(b.currency_in_circulation()?, Amount(0));
@@ -27,6 +42,20 @@ fn rollback_till_empty<K: KvStore>(b: &mut KvStoreChain<K>) -> Result<(), Blockc
     Ok(())
 }
 
+/// Drafts a block with the given txs and applies it
+fn apply_txs<K: KvStore>(
+    chain: &mut KvStoreChain<K>,
+    timestamp: u32,
+    txs: &[TransactionAndDelta],
+    miner: &TxBuilder,
+) -> Result<(), BlockchainError> {
+    let blk = chain
+        .draft_block(timestamp, txs, miner, false)?
+        .unwrap()
+        .block;
+    chain.apply_block(&blk)
+}
+
 #[test]
 fn test_get_header_and_get_block() {
     let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
@@ -0,0 +1,176 @@
+use super::*;
+use crate::wallet::MultisigEnvelope;
+
+#[test]
+fn test_multisig_account() {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let carol = TxBuilder::new(&Vec::from("CAROL"));
+    let dave = TxBuilder::new(&Vec::from("DAVE"));
+
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    let policy = MultisigPolicy {
+        threshold: 2,
+        signers: vec![alice.get_address(), bob.get_address(), carol.get_address()],
+    };
+    let multisig_addr = policy.address();
+
+    for invalid_policy in [
+        MultisigPolicy {
+            threshold: 0,
+            signers: vec![alice.get_address()],
+        },
+        MultisigPolicy {
+            threshold: 2,
+            signers: vec![alice.get_address()],
+        },
+        MultisigPolicy {
+            threshold: 1,
+            signers: vec![alice.get_address(), alice.get_address()],
+        },
+    ] {
+        assert!(matches!(
+            chain.draft_block(
+                1,
+                &[alice.register_multisig("".into(), invalid_policy, Money::ziesha(0), 1)],
+                &miner,
+                false,
+            ),
+            Err(BlockchainError::InvalidMultisigPolicy)
+        ));
+    }
+
+    for tx in [
+        alice.register_multisig("".into(), policy.clone(), Money::ziesha(0), 1),
+        alice.create_transaction(
+            "".into(),
+            multisig_addr.clone(),
+            Money::ziesha(1000),
+            Money::ziesha(0),
+            2,
+        ),
+    ] {
+        let blk = chain
+            .draft_block(1, &[tx], &miner, false)
+            .unwrap()
+            .unwrap()
+            .block;
+        chain.apply_block(&blk).unwrap();
+    }
+    assert_eq!(
+        chain.get_multisig_policy(multisig_addr.clone()).unwrap(),
+        Some(policy.clone())
+    );
+    assert_eq!(
+        chain
+            .get_balance(multisig_addr.clone(), TokenId::Ziesha)
+            .unwrap(),
+        Amount(1000)
+    );
+
+    assert!(matches!(
+        chain.draft_block(
+            2,
+            &[alice.register_multisig("".into(), policy.clone(), Money::ziesha(0), 3)],
+            &miner,
+            false,
+        ),
+        Err(BlockchainError::MultisigAlreadyRegistered)
+    ));
+
+    let mut envelope = MultisigEnvelope::new(
+        policy.clone(),
+        "".into(),
+        TransactionData::RegularSend {
+            entries: vec![RegularSendEntry {
+                dst: dave.get_address(),
+                amount: Money::ziesha(100),
+            }],
+        },
+        Money::ziesha(0),
+        1,
+    );
+    assert!(matches!(
+        envelope.sign(&dave),
+        Err(crate::wallet::WalletError::SignerMismatch)
+    ));
+    envelope.sign(&alice).unwrap();
+    envelope.sign(&alice).unwrap();
+    assert!(!envelope.is_complete());
+
+    // Threshold is enforced when applying the transaction
+    let mut single_signed = envelope.tx.clone();
+    single_signed.tx.sig = Signature::MultiSigned(envelope.sigs.clone());
+    assert!(single_signed.tx.verify_signature());
+    assert!(matches!(
+        chain.draft_block(2, &[single_signed.clone()], &miner, false),
+        Err(BlockchainError::MultisigThresholdNotMet)
+    ));
+    let mut duplicate_signed = single_signed.clone();
+    duplicate_signed.tx.sig =
+        Signature::MultiSigned([envelope.sigs.clone(), envelope.sigs.clone()].concat());
+    assert!(matches!(
+        chain.draft_block(2, &[duplicate_signed], &miner, false),
+        Err(BlockchainError::MultisigThresholdNotMet)
+    ));
+    assert!(matches!(
+        envelope.clone().finalize(),
+        Err(crate::wallet::WalletError::MultisigThresholdNotMet)
+    ));
+
+    // Multi-signatures are not accepted for regular accounts
+    let mut regular = alice.create_transaction(
+        "".into(),
+        dave.get_address(),
+        Money::ziesha(100),
+        Money::ziesha(0),
+        3,
+    );
+    regular.tx.sig = Signature::Unsigned;
+    let bytes = bincode::serialize(&regular.tx).unwrap();
+    regular.tx.sig = Signature::MultiSigned(vec![(alice.get_address(), alice.sign(&bytes))]);
+    assert!(regular.tx.verify_signature());
+    assert!(matches!(
+        chain.draft_block(2, &[regular], &miner, false),
+        Err(BlockchainError::MultisigNotRegistered)
+    ));
+
+    // Co-signers sign their own copies of the envelope
+    let mut bob_envelope = MultisigEnvelope::new(
+        policy.clone(),
+        "".into(),
+        envelope.tx.tx.data.clone(),
+        Money::ziesha(0),
+        1,
+    );
+    bob_envelope.sign(&bob).unwrap();
+    envelope.merge(bob_envelope).unwrap();
+    assert!(envelope.is_complete());
+    let tx = envelope.finalize().unwrap();
+    assert!(tx.tx.verify_signature());
+
+    let blk = chain
+        .draft_block(2, &[tx], &miner, false)
+        .unwrap()
+        .unwrap()
+        .block;
+    chain.apply_block(&blk).unwrap();
+    assert_eq!(
+        chain
+            .get_balance(multisig_addr.clone(), TokenId::Ziesha)
+            .unwrap(),
+        Amount(900)
+    );
+    assert_eq!(
+        chain
+            .get_balance(dave.get_address(), TokenId::Ziesha)
+            .unwrap(),
+        Amount(100)
+    );
+}
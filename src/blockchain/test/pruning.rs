@@ -0,0 +1,176 @@
+use super::*;
+
+#[test]
+fn test_pruned_blocks() {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.pruning = Some(3);
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    for i in 1..=6 {
+        chain
+            .apply_block(
+                &chain
+                    .draft_block(i, &[], &miner, false)
+                    .unwrap()
+                    .unwrap()
+                    .block,
+            )
+            .unwrap();
+    }
+    assert_eq!(chain.get_height().unwrap(), 7);
+    assert_eq!(chain.get_pruned_height().unwrap(), 4);
+
+    // Headers are kept, bodies older than the window are not
+    assert!(chain.get_block(0).is_ok());
+    for i in 1..4 {
+        assert!(chain.get_header(i).is_ok());
+        assert!(matches!(
+            chain.get_block(i),
+            Err(BlockchainError::BlockPruned)
+        ));
+        assert!(chain.database.get(keys::rollback(i)).unwrap().is_none());
+        assert!(chain.database.get(keys::merkle(i)).unwrap().is_none());
+    }
+    assert!(chain.get_block(4).is_ok());
+
+    // Rolling back within the window works
+    for _ in 0..3 {
+        chain.rollback().unwrap();
+    }
+    assert_eq!(chain.get_height().unwrap(), 4);
+    assert!(matches!(
+        chain.rollback(),
+        Err(BlockchainError::RollbackPruned)
+    ));
+    assert_eq!(chain.get_height().unwrap(), 4);
+}
+
+#[test]
+fn test_pruned_transaction_lookup() {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.pruning = Some(3);
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    let tx = alice.create_transaction(
+        "".into(),
+        alice.get_address(),
+        Money::ziesha(100),
+        Money::ziesha(0),
+        1,
+    );
+    for i in 1..=6 {
+        let txs = if i == 1 { vec![tx.clone()] } else { vec![] };
+        chain
+            .apply_block(
+                &chain
+                    .draft_block(i, &txs, &miner, false)
+                    .unwrap()
+                    .unwrap()
+                    .block,
+            )
+            .unwrap();
+    }
+    assert!(matches!(
+        chain.get_block(1),
+        Err(BlockchainError::BlockPruned)
+    ));
+
+    // The location and the header-level data survive the pruned body
+    let header = chain.get_header(1).unwrap();
+    assert_eq!(
+        chain.get_transaction(tx.tx.hash()).unwrap(),
+        Some(TransactionLocation {
+            height: 1,
+            index: 0,
+            block_hash: header.hash(),
+            timestamp: header.proof_of_stake.timestamp,
+            confirmations: 6,
+            tx: None,
+        })
+    );
+}
+
+#[test]
+fn test_pruning_disabled() {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+    for i in 1..=6 {
+        chain
+            .apply_block(
+                &chain
+                    .draft_block(i, &[], &miner, false)
+                    .unwrap()
+                    .unwrap()
+                    .block,
+            )
+            .unwrap();
+    }
+    assert_eq!(chain.get_pruned_height().unwrap(), 0);
+    assert!(chain.get_block(1).is_ok());
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_pruned_compressed_states() {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.pruning = Some(3);
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    let cid = chain.config().mpn_config.mpn_contract_id;
+    let state_model = chain.config().mpn_config.state_model();
+    let data = zk::ZkDataPairs(
+        [(zk::ZkDataLocator(vec![1, 0]), zk::ZkScalar::from(200))]
+            .into_iter()
+            .collect(),
+    );
+    let tx = alice.call_function(
+        "".into(),
+        cid,
+        0,
+        data.as_delta(),
+        state_model.compress::<CoreZkHasher>(&data).unwrap(),
+        zk::ZkProof::Dummy(true),
+        Money::ziesha(0),
+        Money::ziesha(0),
+        1,
+    );
+
+    for i in 1..=4 {
+        let txs = if i == 1 { vec![tx.clone()] } else { vec![] };
+        let draft = chain.draft_block(i, &txs, &miner, false).unwrap().unwrap();
+        chain.apply_block(&draft.block).unwrap();
+        chain.update_states(&draft.patch).unwrap();
+
+        // The state replaced in block 1 is kept while block 1 is in the window
+        let replaced = chain.get_compressed_state_at(cid, 1);
+        if i < 4 {
+            assert!(replaced.is_ok());
+        } else {
+            assert!(matches!(
+                replaced,
+                Err(BlockchainError::CompressedStatePruned)
+            ));
+        }
+    }
+    assert!(chain
+        .database
+        .get(keys::block_contract_updates(1))
+        .unwrap()
+        .is_none());
+    // The current state is never pruned
+    assert!(chain
+        .database
+        .get(keys::compressed_state_at(&cid, 2))
+        .unwrap()
+        .is_some());
+    assert!(chain.get_compressed_state_at(cid, 0).is_ok());
+}
@@ -117,3 +117,72 @@ fn test_correct_rewards() {
         Amount((expected_reward_3 - expected_validator_reward_3).0 * 3 / 5)
     ));
 }
+
+#[test]
+fn test_reward_reports() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+    let conf = blockchain::get_test_blockchain_config();
+    let epoch_duration = conf.slot_duration * conf.slot_per_epoch;
+    let blocks_per_year = (365 * 24 * 60 * 60) as f64 / conf.slot_duration as f64;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    let initial_balance = chain
+        .get_balance(delegator.get_address(), TokenId::Ziesha)
+        .unwrap();
+    let mut expected_rewards = Vec::new();
+    for (timestamp, epoch) in [(0, 0), (1, 0), (epoch_duration, 1), (epoch_duration + 1, 1)] {
+        expected_rewards.push((epoch, chain.next_reward().unwrap()));
+        let draft = chain
+            .draft_block(timestamp, &[], &validator, true)
+            .unwrap()
+            .unwrap();
+        chain.apply_block(&draft.block).unwrap();
+    }
+
+    let validator_rewards = chain
+        .get_validator_rewards(validator.get_address())
+        .unwrap();
+    let delegator_rewards = chain
+        .get_delegator_rewards(delegator.get_address())
+        .unwrap();
+    assert_eq!(validator_rewards.len(), 2);
+    assert_eq!(delegator_rewards.len(), 2);
+    for epoch in [0, 1] {
+        let reward = expected_rewards
+            .iter()
+            .filter(|(e, _)| *e == epoch)
+            .fold(Amount(0), |a, (_, r)| a + *r);
+        let report = &validator_rewards[epoch as usize];
+        let payout = &delegator_rewards[epoch as usize];
+        assert_eq!(report.epoch, epoch);
+        assert_eq!(report.reward, reward);
+        assert_eq!(payout.epoch, epoch);
+        assert_eq!(payout.validator, validator.get_address());
+        assert_eq!(report.commision + payout.amount, reward);
+    }
+
+    // Reports match what the delegator has actually received
+    assert_eq!(
+        chain
+            .get_balance(delegator.get_address(), TokenId::Ziesha)
+            .unwrap(),
+        initial_balance + delegator_rewards[0].amount + delegator_rewards[1].amount
+    );
+
+    // All of the stakers have 25 Ziesha staked
+    assert_eq!(
+        chain.get_staking_apy().unwrap(),
+        u64::from(chain.next_reward().unwrap()) as f64 * blocks_per_year / 75.0
+    );
+
+    rollback_till_empty(&mut chain).unwrap();
+    assert!(chain
+        .get_validator_rewards(validator.get_address())
+        .unwrap()
+        .is_empty());
+    assert!(chain
+        .get_delegator_rewards(delegator.get_address())
+        .unwrap()
+        .is_empty());
+}
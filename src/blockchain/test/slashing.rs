@@ -0,0 +1,299 @@
+use super::*;
+
+#[test]
+fn test_unbonding() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.unbonding_epochs = 2;
+    let epoch_duration = conf.slot_duration * conf.slot_per_epoch;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    let initial_balance = chain
+        .get_balance(alice.get_address(), TokenId::Ziesha)
+        .unwrap();
+    let initial_stake = chain.get_stake(validator.get_address()).unwrap();
+
+    apply_txs(
+        &mut chain,
+        1,
+        &[alice.delegate(
+            "".into(),
+            validator.get_address(),
+            Amount(1000),
+            false,
+            Money::ziesha(0),
+            1,
+        )],
+        &miner,
+    )
+    .unwrap();
+    apply_txs(
+        &mut chain,
+        2,
+        &[alice.delegate(
+            "".into(),
+            validator.get_address(),
+            Amount(400),
+            true,
+            Money::ziesha(0),
+            2,
+        )],
+        &miner,
+    )
+    .unwrap();
+
+    // Undelegated funds are neither staked, nor spendable
+    assert_eq!(
+        chain.get_stake(validator.get_address()).unwrap(),
+        initial_stake + Amount(600)
+    );
+    assert_eq!(
+        chain
+            .get_delegate(alice.get_address(), validator.get_address())
+            .unwrap()
+            .amount,
+        Amount(600)
+    );
+    assert_eq!(
+        chain
+            .get_balance(alice.get_address(), TokenId::Ziesha)
+            .unwrap(),
+        initial_balance - Amount(1000)
+    );
+    assert_eq!(
+        chain
+            .get_unbonding(alice.get_address(), validator.get_address())
+            .unwrap(),
+        Some(Unbonding {
+            amount: Amount(400),
+            unlock_epoch: 2
+        })
+    );
+
+    let reclaim = alice.reclaim_delegate("".into(), validator.get_address(), Money::ziesha(0), 3);
+    assert!(matches!(
+        apply_txs(
+            &mut chain,
+            epoch_duration,
+            std::slice::from_ref(&reclaim),
+            &miner
+        ),
+        Err(BlockchainError::UnbondingInProgress)
+    ));
+
+    apply_txs(&mut chain, 2 * epoch_duration, &[], &miner).unwrap();
+    apply_txs(&mut chain, 2 * epoch_duration + 1, &[reclaim], &miner).unwrap();
+    assert_eq!(
+        chain
+            .get_balance(alice.get_address(), TokenId::Ziesha)
+            .unwrap(),
+        initial_balance - Amount(600)
+    );
+    assert_eq!(
+        chain
+            .get_unbonding(alice.get_address(), validator.get_address())
+            .unwrap(),
+        None
+    );
+
+    assert!(matches!(
+        apply_txs(
+            &mut chain,
+            2 * epoch_duration + 2,
+            &[alice.reclaim_delegate("".into(), validator.get_address(), Money::ziesha(0), 4)],
+            &miner
+        ),
+        Err(BlockchainError::UnbondingNotFound)
+    ));
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_slashing() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let other_validator = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+    let reporter = TxBuilder::new(&Vec::from("REPORTER"));
+
+    let conf = blockchain::get_test_blockchain_config();
+    let ratio = conf.slashing_ratio as u64;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    apply_txs(
+        &mut chain,
+        1,
+        &[alice.delegate(
+            "".into(),
+            validator.get_address(),
+            Amount(1000),
+            false,
+            Money::ziesha(0),
+            1,
+        )],
+        &other_validator,
+    )
+    .unwrap();
+    apply_txs(
+        &mut chain,
+        2,
+        &[alice.delegate(
+            "".into(),
+            validator.get_address(),
+            Amount(200),
+            true,
+            Money::ziesha(0),
+            2,
+        )],
+        &other_validator,
+    )
+    .unwrap();
+
+    // Two different blocks proposed by the validator for the same slot
+    let first = chain
+        .draft_block(10, &[], &validator, false)
+        .unwrap()
+        .unwrap()
+        .block
+        .header;
+    let second = chain
+        .draft_block(
+            11,
+            &[alice.create_transaction(
+                "".into(),
+                reporter.get_address(),
+                Money::ziesha(1),
+                Money::ziesha(0),
+                3,
+            )],
+            &validator,
+            false,
+        )
+        .unwrap()
+        .unwrap()
+        .block
+        .header;
+    let unrelated = chain
+        .draft_block(11, &[], &other_validator, false)
+        .unwrap()
+        .unwrap()
+        .block
+        .header;
+    let mut forged = second.clone();
+    forged.block_root = Default::default();
+
+    for (a, b) in [
+        (first.clone(), first.clone()),
+        (first.clone(), unrelated),
+        (first.clone(), forged),
+    ] {
+        assert!(matches!(
+            apply_txs(
+                &mut chain,
+                12,
+                &[reporter.slash_validator("".into(), a, b, Money::ziesha(0), 1)],
+                &other_validator
+            ),
+            Err(BlockchainError::InvalidSlashingEvidence)
+        ));
+    }
+
+    let delegators = chain.get_delegators(validator.get_address(), None).unwrap();
+    let stake = chain.get_stake(validator.get_address()).unwrap();
+    let cut = |amount: Amount| Amount(u64::from(amount) * ratio / 255);
+
+    apply_txs(
+        &mut chain,
+        12,
+        &[reporter.slash_validator(
+            "".into(),
+            first.clone(),
+            second.clone(),
+            Money::ziesha(0),
+            1,
+        )],
+        &other_validator,
+    )
+    .unwrap();
+
+    let mut burned = Amount(0);
+    for (delegator, amount) in delegators {
+        assert_eq!(
+            chain
+                .get_delegate(delegator, validator.get_address())
+                .unwrap()
+                .amount,
+            amount - cut(amount)
+        );
+        burned += cut(amount);
+    }
+    assert_eq!(
+        chain.get_stake(validator.get_address()).unwrap(),
+        stake - burned
+    );
+    assert_eq!(
+        chain
+            .get_unbonding(alice.get_address(), validator.get_address())
+            .unwrap()
+            .unwrap()
+            .amount,
+        Amount(200) - cut(Amount(200))
+    );
+    burned += cut(Amount(200));
+    assert_eq!(chain.get_burned(TokenId::Ziesha).unwrap(), burned);
+    assert_eq!(
+        chain.currency_in_circulation().unwrap() + burned,
+        Amount(2000000000000000000)
+    );
+
+    assert!(matches!(
+        apply_txs(
+            &mut chain,
+            13,
+            &[reporter.slash_validator("".into(), second, first, Money::ziesha(0), 2)],
+            &other_validator
+        ),
+        Err(BlockchainError::ValidatorAlreadySlashed)
+    ));
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_slashing_consecutive_blocks() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let reporter = TxBuilder::new(&Vec::from("REPORTER"));
+
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    // Two consecutive blocks, honestly proposed by the validator in one slot
+    apply_txs(&mut chain, 10, &[], &validator).unwrap();
+    apply_txs(&mut chain, 11, &[], &validator).unwrap();
+    let first = chain.get_header(1).unwrap();
+    let second = chain.get_header(2).unwrap();
+    assert_eq!(
+        chain.epoch_slot(first.proof_of_stake.timestamp),
+        chain.epoch_slot(second.proof_of_stake.timestamp)
+    );
+
+    let stake = chain.get_stake(validator.get_address()).unwrap();
+    assert!(matches!(
+        apply_txs(
+            &mut chain,
+            12,
+            &[reporter.slash_validator("".into(), first, second, Money::ziesha(0), 1)],
+            &validator
+        ),
+        Err(BlockchainError::InvalidSlashingEvidence)
+    ));
+    assert_eq!(chain.get_stake(validator.get_address()).unwrap(), stake);
+
+    rollback_till_empty(&mut chain).unwrap();
+}
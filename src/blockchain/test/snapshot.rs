@@ -0,0 +1,160 @@
+use super::*;
+use std::io::Cursor;
+
+#[test]
+fn test_snapshot_round_trip() {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("DCBA"));
+
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    for i in 0..6 {
+        let tx = alice.create_transaction(
+            "".into(),
+            bob.get_address(),
+            Money::ziesha(10),
+            Money::ziesha(1),
+            i + 1,
+        );
+        chain
+            .apply_block(
+                &chain
+                    .draft_block(i * 10, &[tx], &miner, false)
+                    .unwrap()
+                    .unwrap()
+                    .block,
+            )
+            .unwrap();
+    }
+    assert_eq!(chain.get_height().unwrap(), 7);
+
+    assert!(matches!(
+        chain.export_snapshot(Some(0), std::io::sink()),
+        Err(BlockchainError::InvalidSnapshotHeight)
+    ));
+    assert!(matches!(
+        chain.export_snapshot(Some(8), std::io::sink()),
+        Err(BlockchainError::InvalidSnapshotHeight)
+    ));
+
+    let mut snapshot = Vec::new();
+    let header = chain.export_snapshot(Some(5), &mut snapshot).unwrap();
+    assert_eq!(header.version, SNAPSHOT_VERSION);
+    assert_eq!(header.height, 5);
+
+    let mut expected = chain.fork_on_ram();
+    expected.rollback().unwrap();
+    expected.rollback().unwrap();
+    assert_eq!(header.checksum, expected.db_checksum().unwrap());
+
+    // Full import has exactly the same data
+    let full = KvStoreChain::import_snapshot(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+        Cursor::new(&snapshot),
+        u64::MAX,
+    )
+    .unwrap();
+    assert_eq!(full.db_checksum().unwrap(), expected.db_checksum().unwrap());
+
+    // Only the last 2 rollbacks are kept
+    let mut pruned = KvStoreChain::import_snapshot(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+        Cursor::new(&snapshot),
+        2,
+    )
+    .unwrap();
+    assert_eq!(pruned.get_height().unwrap(), 5);
+    assert_eq!(pruned.get_pruned_height().unwrap(), 3);
+    assert_eq!(pruned.get_block(4).unwrap(), chain.get_block(4).unwrap());
+    assert_eq!(
+        pruned
+            .get_balance(bob.get_address(), TokenId::Ziesha)
+            .unwrap(),
+        Money::ziesha(40).amount
+    );
+
+    // Syncing resumes from the snapshot height
+    pruned
+        .extend(
+            5,
+            &[chain.get_block(5).unwrap(), chain.get_block(6).unwrap()],
+        )
+        .unwrap();
+    assert_eq!(pruned.db_checksum().unwrap(), {
+        let mut chain_without_old_rollbacks = chain.fork_on_ram();
+        chain_without_old_rollbacks
+            .database
+            .update(
+                &(0..3)
+                    .map(|h| WriteOp::Remove(keys::rollback(h)))
+                    .chain([WriteOp::Put(keys::pruned_height(), 3u64.into())])
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap();
+        chain_without_old_rollbacks.db_checksum().unwrap()
+    });
+
+    for _ in 0..4 {
+        pruned.rollback().unwrap();
+    }
+    assert!(matches!(
+        pruned.rollback(),
+        Err(BlockchainError::RollbackPruned)
+    ));
+}
+
+#[test]
+fn test_snapshot_verification() {
+    let chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+    let mut snapshot = Vec::new();
+    chain.export_snapshot(None, &mut snapshot).unwrap();
+    let import = |snapshot: &[u8]| {
+        KvStoreChain::import_snapshot(
+            db::RamKvStore::new(),
+            blockchain::get_test_blockchain_config(),
+            Cursor::new(snapshot),
+            10,
+        )
+    };
+
+    // The version is the first field of the header
+    let mut bad_version = snapshot.clone();
+    bad_version[0] += 1;
+    assert!(matches!(
+        import(&bad_version),
+        Err(BlockchainError::SnapshotVersionMismatch)
+    ));
+
+    let mut corrupted = snapshot.clone();
+    *corrupted.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        import(&corrupted),
+        Err(BlockchainError::SnapshotChecksumMismatch)
+    ));
+
+    assert!(matches!(
+        import(&snapshot[..snapshot.len() - 1]),
+        Err(BlockchainError::SnapshotSerializationError(_))
+    ));
+
+    assert!(matches!(
+        KvStoreChain::import_snapshot(
+            chain.database,
+            blockchain::get_test_blockchain_config(),
+            Cursor::new(&snapshot),
+            10,
+        ),
+        Err(BlockchainError::DatabaseNotEmpty)
+    ));
+}
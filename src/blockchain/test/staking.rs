@@ -0,0 +1,277 @@
+use super::*;
+
+#[test]
+fn test_commision_change() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+
+    let conf = blockchain::get_test_blockchain_config();
+    let epoch_duration = conf.slot_duration * conf.slot_per_epoch;
+    let delay = conf.commision_change_delay;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    // Reward received by the only delegator of the validator in a block
+    let delegator_reward = |chain: &mut KvStoreChain<db::RamKvStore>,
+                            timestamp: u32,
+                            txs: &[TransactionAndDelta]|
+     -> Result<u64, BlockchainError> {
+        let before = chain.get_balance(delegator.get_address(), TokenId::Ziesha)?;
+        apply_txs(chain, timestamp, txs, &validator)?;
+        let after = chain.get_balance(delegator.get_address(), TokenId::Ziesha)?;
+        Ok(u64::from(after - before))
+    };
+    let expected_reward = |chain: &KvStoreChain<db::RamKvStore>, commision: u8| -> u64 {
+        let reward = u64::from(chain.next_reward().unwrap());
+        (reward as f64 * ((u8::MAX - commision) as f64 / u8::MAX as f64)) as u64
+    };
+
+    let expected = expected_reward(&chain, 12);
+    assert_eq!(
+        delegator_reward(
+            &mut chain,
+            1,
+            &[validator.register_validator("".into(), 15, Money::ziesha(0), 2)]
+        )
+        .unwrap(),
+        expected
+    );
+    assert_eq!(
+        chain
+            .get_staker(validator.get_address())
+            .unwrap()
+            .unwrap()
+            .pending_commisions,
+        vec![CommisionChange {
+            commision: 15,
+            effective_epoch: delay,
+        }]
+    );
+
+    // Changes are limited relative to the commision currently charged
+    assert!(matches!(
+        delegator_reward(
+            &mut chain,
+            2,
+            &[validator.register_validator("".into(), 19, Money::ziesha(0), 3)]
+        ),
+        Err(BlockchainError::CommisionChangeTooLarge)
+    ));
+    assert!(matches!(
+        delegator_reward(
+            &mut chain,
+            2,
+            &[validator.register_validator("".into(), 255, Money::ziesha(0), 3)]
+        ),
+        Err(BlockchainError::InvalidCommision)
+    ));
+
+    // Repeating the latest announced commision keeps the pending changes,
+    // and a change can be announced on every epoch
+    for (i, commision) in [15, 17].into_iter().enumerate() {
+        let expected = expected_reward(&chain, 12);
+        assert_eq!(
+            delegator_reward(
+                &mut chain,
+                epoch_duration + i as u32,
+                &[validator.register_validator(
+                    "".into(),
+                    commision,
+                    Money::ziesha(0),
+                    3 + i as u32
+                )]
+            )
+            .unwrap(),
+            expected
+        );
+    }
+    assert_eq!(
+        chain
+            .get_staker(validator.get_address())
+            .unwrap()
+            .unwrap()
+            .pending_commisions,
+        vec![
+            CommisionChange {
+                commision: 15,
+                effective_epoch: delay,
+            },
+            CommisionChange {
+                commision: 17,
+                effective_epoch: delay + 1,
+            }
+        ]
+    );
+
+    let expected = expected_reward(&chain, 12);
+    assert_eq!(
+        delegator_reward(&mut chain, delay * epoch_duration - 1, &[]).unwrap(),
+        expected
+    );
+    // The first block of the epoch already charges the new commision
+    let expected = expected_reward(&chain, 15);
+    assert_eq!(
+        delegator_reward(&mut chain, delay * epoch_duration, &[]).unwrap(),
+        expected
+    );
+    let expected = expected_reward(&chain, 15);
+    assert_eq!(
+        delegator_reward(
+            &mut chain,
+            delay * epoch_duration + 1,
+            &[validator.register_validator("".into(), 18, Money::ziesha(0), 5)]
+        )
+        .unwrap(),
+        expected
+    );
+    let expected = expected_reward(&chain, 17);
+    assert_eq!(
+        delegator_reward(&mut chain, (delay + 1) * epoch_duration, &[]).unwrap(),
+        expected
+    );
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_unregister_staker() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR2"));
+    let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+
+    let conf = blockchain::get_test_blockchain_config();
+    let unbonding_epochs = conf.unbonding_epochs;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    apply_txs(
+        &mut chain,
+        1,
+        &[alice.delegate(
+            "".into(),
+            validator.get_address(),
+            Amount(1000),
+            false,
+            Money::ziesha(0),
+            1,
+        )],
+        &miner,
+    )
+    .unwrap();
+    apply_txs(
+        &mut chain,
+        2,
+        &[validator.unregister_validator("".into(), Money::ziesha(0), 2)],
+        &miner,
+    )
+    .unwrap();
+
+    assert!(chain
+        .get_stakers()
+        .unwrap()
+        .iter()
+        .all(|(addr, _)| *addr != validator.get_address()));
+    assert!(chain.get_staker(validator.get_address()).unwrap().is_none());
+    assert_eq!(chain.get_stake(validator.get_address()).unwrap(), Amount(0));
+    assert!(chain
+        .get_delegators(validator.get_address(), None)
+        .unwrap()
+        .is_empty());
+    assert!(chain
+        .get_delegatees(alice.get_address(), None)
+        .unwrap()
+        .is_empty());
+    for (addr, amount) in [
+        (alice.get_address(), Amount(1000)),
+        (delegator.get_address(), Amount(25)),
+    ] {
+        assert_eq!(
+            chain
+                .get_delegate(addr.clone(), validator.get_address())
+                .unwrap()
+                .amount,
+            Amount(0)
+        );
+        assert_eq!(
+            chain.get_unbonding(addr, validator.get_address()).unwrap(),
+            Some(Unbonding {
+                amount,
+                unlock_epoch: unbonding_epochs
+            })
+        );
+    }
+
+    assert!(matches!(
+        apply_txs(
+            &mut chain,
+            3,
+            &[validator.unregister_validator("".into(), Money::ziesha(0), 3)],
+            &miner
+        ),
+        Err(BlockchainError::ValidatorNotRegistered)
+    ));
+
+    // Validators may register again later
+    apply_txs(
+        &mut chain,
+        3,
+        &[validator.register_validator("".into(), 20, Money::ziesha(0), 3)],
+        &miner,
+    )
+    .unwrap();
+    assert_eq!(
+        chain
+            .get_staker(validator.get_address())
+            .unwrap()
+            .unwrap()
+            .commision,
+        20
+    );
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_epoch_stakers() {
+    let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+
+    let conf = blockchain::get_test_blockchain_config();
+    let epoch_duration = conf.slot_duration * conf.slot_per_epoch;
+    let mut chain = KvStoreChain::new(db::RamKvStore::new(), conf).unwrap();
+
+    let stake_at = |chain: &KvStoreChain<db::RamKvStore>, epoch: u32| {
+        chain
+            .get_epoch_stakers(epoch)
+            .unwrap()
+            .into_iter()
+            .find(|s| s.address == validator.get_address())
+            .unwrap()
+            .stake
+    };
+    let delegate = |amount: u64, nonce: u32| {
+        alice.delegate(
+            "".into(),
+            validator.get_address(),
+            Amount(amount),
+            false,
+            Money::ziesha(0),
+            nonce,
+        )
+    };
+
+    let initial_stake = chain.get_stake(validator.get_address()).unwrap();
+    apply_txs(&mut chain, 1, &[delegate(1000, 1)], &validator).unwrap();
+
+    // Elections of a started epoch are not affected by the stake changes
+    assert_eq!(stake_at(&chain, 0), initial_stake);
+    assert_eq!(stake_at(&chain, 1), initial_stake + Amount(1000));
+
+    apply_txs(&mut chain, epoch_duration, &[delegate(500, 2)], &validator).unwrap();
+    assert_eq!(stake_at(&chain, 1), initial_stake + Amount(1000));
+    assert_eq!(
+        chain.get_stake(validator.get_address()).unwrap(),
+        initial_stake + Amount(1500)
+    );
+
+    rollback_till_empty(&mut chain).unwrap();
+}
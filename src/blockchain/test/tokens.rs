@@ -19,6 +19,8 @@ fn test_token_balances() {
         Amount(12345),
         0,
         Some(alice.get_address()),
+        None,
+        None,
         Money::ziesha(0),
         1,
     );
@@ -134,3 +136,275 @@ fn test_token_balances() {
 
     rollback_till_empty(&mut chain).unwrap();
 }
+
+#[test]
+fn test_custom_token_fees() {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABCD"));
+    let bob = TxBuilder::new(&Vec::from("DCBA"));
+
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    let (token_create_tx, token_id) = alice.create_token(
+        "".into(),
+        "My Token".into(),
+        "MYT".into(),
+        Amount(12345),
+        0,
+        Some(alice.get_address()),
+        None,
+        None,
+        Money::ziesha(0),
+        1,
+    );
+    chain
+        .apply_block(
+            &chain
+                .draft_block(1, &[token_create_tx], &miner, false)
+                .unwrap()
+                .unwrap()
+                .block,
+        )
+        .unwrap();
+
+    let tx = alice.create_transaction(
+        "".into(),
+        bob.get_address(),
+        Money::new(token_id, 20),
+        Money::new(token_id, 100),
+        2,
+    );
+
+    // Token is not accepted as fee by the validator
+    assert!(chain
+        .draft_block(2, std::slice::from_ref(&tx), &miner, true)
+        .unwrap()
+        .unwrap()
+        .block
+        .body
+        .is_empty());
+
+    let prices = FeeTokenPrices::new(
+        [(token_id, TokenPrice::new(1, 2).unwrap())]
+            .into_iter()
+            .collect(),
+    );
+    assert_eq!(
+        prices.fee_in_ziesha(&Money::new(token_id, 100)),
+        Some(Amount(50))
+    );
+    let blk = chain
+        .draft_block_with_fee_prices(2, std::slice::from_ref(&tx), &miner, true, &prices)
+        .unwrap()
+        .unwrap()
+        .block;
+    assert_eq!(blk.body, vec![tx.tx.clone()]);
+    chain.apply_block(&blk).unwrap();
+
+    assert_eq!(
+        chain.get_balance(alice.get_address(), token_id).unwrap(),
+        Amount(12225)
+    );
+    assert_eq!(
+        chain.get_balance(bob.get_address(), token_id).unwrap(),
+        Amount(20)
+    );
+
+    // Fees are distributed between the validator and its delegators
+    assert_eq!(
+        chain.get_balance(Default::default(), token_id).unwrap(),
+        Amount(0)
+    );
+    let validator_share = chain.get_balance(miner.get_address(), token_id).unwrap();
+    let delegator_share = chain
+        .get_balance(delegator.get_address(), token_id)
+        .unwrap();
+    assert!(validator_share > Amount(0) && delegator_share > validator_share);
+    assert_eq!(validator_share + delegator_share, Amount(100));
+
+    rollback_till_empty(&mut chain).unwrap();
+}
+
+#[test]
+fn test_token_burn_and_supply_cap() {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("DCBA"));
+
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+
+    let metadata = TokenMetadata {
+        uri: "ipfs://QmToken".into(),
+        hash: Some([1u8; 32]),
+    };
+    for (supply, metadata, err) in [
+        (
+            Amount(2000),
+            Some(metadata.clone()),
+            BlockchainError::TokenSupplyCapExceeded,
+        ),
+        (
+            Amount(1000),
+            Some(TokenMetadata {
+                uri: "".into(),
+                hash: None,
+            }),
+            BlockchainError::TokenBadMetadata,
+        ),
+    ] {
+        let (tx, _) = alice.create_token(
+            "".into(),
+            "My Token".into(),
+            "MYT".into(),
+            supply,
+            0,
+            Some(alice.get_address()),
+            Some(Amount(1500)),
+            metadata,
+            Money::ziesha(0),
+            1,
+        );
+        assert_eq!(
+            apply_txs(&mut chain, 1, &[tx], &miner)
+                .unwrap_err()
+                .to_string(),
+            err.to_string()
+        );
+    }
+
+    let (tx, token_id) = alice.create_token(
+        "".into(),
+        "My Token".into(),
+        "MYT".into(),
+        Amount(1000),
+        0,
+        Some(alice.get_address()),
+        Some(Amount(1500)),
+        Some(metadata.clone()),
+        Money::ziesha(0),
+        1,
+    );
+    apply_txs(&mut chain, 1, &[tx], &miner).unwrap();
+    assert_eq!(
+        chain.get_token(token_id).unwrap().unwrap().metadata,
+        Some(metadata)
+    );
+    apply_txs(
+        &mut chain,
+        2,
+        &[alice.create_transaction(
+            "".into(),
+            bob.get_address(),
+            Money::new(token_id, 100),
+            Money::ziesha(0),
+            2,
+        )],
+        &miner,
+    )
+    .unwrap();
+
+    // Any holder can burn its own balance
+    let burn = |amount: u64, nonce: u32| {
+        bob.update_token(
+            "".into(),
+            token_id,
+            TokenUpdate::Burn {
+                amount: Amount(amount),
+            },
+            Money::ziesha(0),
+            nonce,
+        )
+    };
+    assert!(matches!(
+        apply_txs(&mut chain, 3, &[burn(101, 1)], &miner),
+        Err(BlockchainError::BalanceInsufficient)
+    ));
+    apply_txs(&mut chain, 3, &[burn(40, 1)], &miner).unwrap();
+    assert_eq!(
+        chain.get_balance(bob.get_address(), token_id).unwrap(),
+        Amount(60)
+    );
+    // Burning reduces the supply, but doesn't free up room under the max supply
+    assert_eq!(
+        chain.get_token(token_id).unwrap().unwrap().supply,
+        Amount(960)
+    );
+    assert_eq!(chain.get_minted(token_id).unwrap(), Amount(1000));
+    assert_eq!(chain.get_burned(token_id).unwrap(), Amount(0));
+
+    // Minting is capped by the max supply
+    let mint = |amount: u64, nonce: u32| {
+        alice.update_token(
+            "".into(),
+            token_id,
+            TokenUpdate::Mint {
+                amount: Amount(amount),
+            },
+            Money::ziesha(0),
+            nonce,
+        )
+    };
+    assert!(matches!(
+        apply_txs(&mut chain, 4, &[mint(501, 3)], &miner),
+        Err(BlockchainError::TokenSupplyCapExceeded)
+    ));
+    apply_txs(&mut chain, 4, &[mint(500, 3)], &miner).unwrap();
+    assert_eq!(
+        chain.get_token(token_id).unwrap().unwrap().supply,
+        Amount(1460)
+    );
+    assert_eq!(chain.get_minted(token_id).unwrap(), Amount(1500));
+
+    // Supply is frozen once the minter is renounced
+    apply_txs(
+        &mut chain,
+        5,
+        &[alice.update_token(
+            "".into(),
+            token_id,
+            TokenUpdate::RenounceMinter,
+            Money::ziesha(0),
+            4,
+        )],
+        &miner,
+    )
+    .unwrap();
+    assert_eq!(chain.get_token(token_id).unwrap().unwrap().minter, None);
+    assert!(matches!(
+        apply_txs(&mut chain, 6, &[mint(0, 5)], &miner),
+        Err(BlockchainError::TokenNotUpdatable)
+    ));
+
+    // Burnt Ziesha is taken out of the supply
+    apply_txs(
+        &mut chain,
+        6,
+        &[alice.update_token(
+            "".into(),
+            TokenId::Ziesha,
+            TokenUpdate::Burn {
+                amount: Amount(100),
+            },
+            Money::ziesha(0),
+            5,
+        )],
+        &miner,
+    )
+    .unwrap();
+    assert_eq!(chain.get_burned(TokenId::Ziesha).unwrap(), Amount(0));
+    assert_eq!(
+        chain.get_token(TokenId::Ziesha).unwrap().unwrap().supply,
+        Amount(2000000000000000000 - 100)
+    );
+
+    rollback_till_empty(&mut chain).unwrap();
+}
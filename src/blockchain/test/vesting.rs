@@ -0,0 +1,213 @@
+use super::*;
+use crate::core::LockTime;
+
+#[test]
+fn test_vesting_release() {
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let vesting = Vesting {
+        beneficiary: bob.get_address(),
+        amount: Money::ziesha(1000),
+        unlock: LockTime::Timestamp(100),
+        linear_until: None,
+    };
+    assert_eq!(vesting.released(1000, 99), Amount(0));
+    assert_eq!(vesting.released(0, 100), Amount(1000));
+
+    let linear = Vesting {
+        linear_until: Some(LockTime::Timestamp(200)),
+        ..vesting.clone()
+    };
+    assert!(linear.validate());
+    assert_eq!(linear.released(0, 150), Amount(500));
+    assert_eq!(linear.released(0, 199), Amount(990));
+    assert_eq!(linear.released(0, 300), Amount(1000));
+
+    for invalid in [
+        Vesting {
+            linear_until: Some(LockTime::Timestamp(100)),
+            ..vesting.clone()
+        },
+        Vesting {
+            linear_until: Some(LockTime::Height(200)),
+            ..vesting.clone()
+        },
+        Vesting {
+            amount: Money::ziesha(0),
+            ..vesting.clone()
+        },
+    ] {
+        assert!(!invalid.validate());
+    }
+}
+
+#[test]
+fn test_vesting_claim() {
+    let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+    let alice = TxBuilder::new(&Vec::from("ABC"));
+    let bob = TxBuilder::new(&Vec::from("BOB"));
+    let carol = TxBuilder::new(&Vec::from("CAROL"));
+
+    let mut chain = KvStoreChain::new(
+        db::RamKvStore::new(),
+        blockchain::get_test_blockchain_config(),
+    )
+    .unwrap();
+    let alice_initial = chain
+        .get_balance(alice.get_address(), TokenId::Ziesha)
+        .unwrap();
+
+    assert!(matches!(
+        chain.draft_block(
+            1,
+            &[alice
+                .create_vesting(
+                    "".into(),
+                    bob.get_address(),
+                    Money::ziesha(1000),
+                    LockTime::Height(3),
+                    Some(LockTime::Height(2)),
+                    Money::ziesha(0),
+                    1,
+                )
+                .0],
+            &miner,
+            false,
+        ),
+        Err(BlockchainError::InvalidVesting)
+    ));
+
+    let (tx, vesting_id) = alice.create_vesting(
+        "".into(),
+        bob.get_address(),
+        Money::ziesha(1000),
+        LockTime::Height(3),
+        Some(LockTime::Height(7)),
+        Money::ziesha(0),
+        1,
+    );
+    let blk = chain
+        .draft_block(1, &[tx], &miner, false)
+        .unwrap()
+        .unwrap()
+        .block;
+    chain.apply_block(&blk).unwrap();
+
+    assert_eq!(
+        chain
+            .get_balance(alice.get_address(), TokenId::Ziesha)
+            .unwrap(),
+        alice_initial - Amount(1000)
+    );
+    assert_eq!(
+        chain
+            .get_balance(bob.get_address(), TokenId::Ziesha)
+            .unwrap(),
+        Amount(0)
+    );
+    assert_eq!(
+        chain.currency_in_circulation().unwrap(),
+        Amount(2000000000000000000)
+    );
+    let vestings = chain.get_vestings(bob.get_address()).unwrap();
+    assert_eq!(vestings.len(), 1);
+    assert_eq!(vestings[0].0, vesting_id);
+    assert_eq!(vestings[0].1.locked(), Amount(1000));
+
+    // Still locked at height 2
+    assert!(matches!(
+        chain.draft_block(
+            2,
+            &[bob.claim_vesting("".into(), vesting_id, Money::ziesha(0), 1)],
+            &miner,
+            false,
+        ),
+        Err(BlockchainError::NothingToClaim)
+    ));
+    assert!(matches!(
+        chain.draft_block(
+            2,
+            &[carol.claim_vesting("".into(), vesting_id, Money::ziesha(0), 1)],
+            &miner,
+            false,
+        ),
+        Err(BlockchainError::NotVestingBeneficiary)
+    ));
+
+    for ts in 2..4 {
+        let blk = chain
+            .draft_block(ts, &[], &miner, false)
+            .unwrap()
+            .unwrap()
+            .block;
+        chain.apply_block(&blk).unwrap();
+    }
+
+    // A quarter is released at height 4
+    let blk = chain
+        .draft_block(
+            4,
+            &[bob.claim_vesting("".into(), vesting_id, Money::ziesha(0), 1)],
+            &miner,
+            false,
+        )
+        .unwrap()
+        .unwrap()
+        .block;
+    chain.apply_block(&blk).unwrap();
+    assert_eq!(
+        chain
+            .get_balance(bob.get_address(), TokenId::Ziesha)
+            .unwrap(),
+        Amount(250)
+    );
+    assert_eq!(
+        chain.get_vesting(vesting_id).unwrap().unwrap().claimed,
+        Amount(250)
+    );
+    assert_eq!(
+        chain.currency_in_circulation().unwrap(),
+        Amount(2000000000000000000)
+    );
+
+    for ts in 5..7 {
+        let blk = chain
+            .draft_block(ts, &[], &miner, false)
+            .unwrap()
+            .unwrap()
+            .block;
+        chain.apply_block(&blk).unwrap();
+    }
+
+    // Everything is released at height 7
+    let blk = chain
+        .draft_block(
+            7,
+            &[bob.claim_vesting("".into(), vesting_id, Money::ziesha(0), 2)],
+            &miner,
+            false,
+        )
+        .unwrap()
+        .unwrap()
+        .block;
+    chain.apply_block(&blk).unwrap();
+    assert_eq!(
+        chain
+            .get_balance(bob.get_address(), TokenId::Ziesha)
+            .unwrap(),
+        Amount(1000)
+    );
+    assert_eq!(chain.get_vesting(vesting_id).unwrap(), None);
+    assert!(chain.get_vestings(bob.get_address()).unwrap().is_empty());
+
+    assert!(matches!(
+        chain.draft_block(
+            8,
+            &[bob.claim_vesting("".into(), vesting_id, Money::ziesha(0), 3)],
+            &miner,
+            false,
+        ),
+        Err(BlockchainError::VestingNotFound)
+    ));
+
+    rollback_till_empty(&mut chain).unwrap();
+}
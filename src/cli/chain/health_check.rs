@@ -3,8 +3,8 @@ use bazuka::blockchain::Blockchain;
 use bazuka::db::KvStore;
 use bazuka::{
     blockchain::KvStoreChain,
-    core::{Amount, TokenId},
-    db::ReadOnlyLevelDbKvStore,
+    core::{Amount, TokenId, VestingAccount},
+    db::{keys, ReadOnlyLevelDbKvStore},
 };
 use colored::Colorize;
 
@@ -38,8 +38,39 @@ pub fn health_check(conf: &BazukaConfig) {
         == chain
             .get_contract_balance(mpn_contract_id, TokenId::Ziesha)
             .unwrap();
-    let currency_in_circulation_check =
-        chain.currency_in_circulation().unwrap() == Amount::from(2000000000000000000);
+    // Fully claimed vestings are removed, and each vesting is indexed under
+    // its beneficiary
+    let vestings = chain
+        .db()
+        .pairs(keys::vesting_prefix().into())
+        .unwrap()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let vesting_indices_count = chain
+        .db()
+        .pairs(keys::vesting_index_prefix().into())
+        .unwrap()
+        .into_iter()
+        .count();
+    let vesting_accounts_check = vestings.len() == vesting_indices_count
+        && vestings.into_iter().all(|(k, v)| {
+            let acc: VestingAccount = v.try_into().unwrap();
+            let vesting_id = k.0[keys::vesting_prefix().len()..].parse().unwrap();
+            acc.vesting.validate()
+                && acc.claimed < acc.vesting.amount.amount
+                && chain
+                    .db()
+                    .get(keys::vesting_beneficiary(
+                        &acc.vesting.beneficiary,
+                        &vesting_id,
+                    ))
+                    .unwrap()
+                    .is_some()
+        });
+    // Slashed funds are burnt and out of circulation
+    let currency_in_circulation_check = chain.currency_in_circulation().unwrap()
+        + chain.get_burned(TokenId::Ziesha).unwrap()
+        == chain.get_token(TokenId::Ziesha).unwrap().unwrap().supply;
     println!(
         "Rollback validity check: {}",
         if rollback_validity_check {
@@ -56,6 +87,14 @@ pub fn health_check(conf: &BazukaConfig) {
             "FAIL".bright_red()
         }
     );
+    println!(
+        "Vesting accounts check: {}",
+        if vesting_accounts_check {
+            "PASS".bright_green()
+        } else {
+            "FAIL".bright_red()
+        }
+    );
     println!(
         "Currency in circulation check: {}",
         if currency_in_circulation_check {
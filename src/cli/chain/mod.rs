@@ -1,7 +1,9 @@
 pub mod db_query;
 pub mod health_check;
 pub mod rollback;
+pub mod snapshot;
 
 pub use db_query::*;
 pub use health_check::*;
 pub use rollback::*;
+pub use snapshot::*;
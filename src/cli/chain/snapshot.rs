@@ -0,0 +1,88 @@
+use crate::cli::BazukaConfig;
+use bazuka::blockchain::{Blockchain, BlockchainConfig, KvStoreChain, SnapshotHeader};
+use bazuka::config::blockchain::{get_blockchain_config, get_dev_blockchain_config};
+use bazuka::db::{LevelDbKvStore, ReadOnlyLevelDbKvStore};
+use bazuka::wallet::WalletCollection;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+/// The genesis block of a dev chain depends on the validator of the wallet
+fn blockchain_config(dev_wallet: Option<WalletCollection>, small_mpn: bool) -> BlockchainConfig {
+    match dev_wallet {
+        Some(mut wallet) => get_dev_blockchain_config(&wallet.validator().tx_builder(), small_mpn),
+        None => get_blockchain_config(),
+    }
+}
+
+fn try_export_snapshot(
+    output: &Path,
+    height: Option<u64>,
+    conf: &BazukaConfig,
+    blockchain_conf: BlockchainConfig,
+) -> Result<SnapshotHeader, Box<dyn Error>> {
+    let rdb = ReadOnlyLevelDbKvStore::read_only(&conf.db, 64)?;
+    let chain = KvStoreChain::new(rdb.snapshot(), blockchain_conf)?;
+    Ok(chain.export_snapshot(height, BufWriter::new(File::create(output)?))?)
+}
+
+pub fn export_snapshot(
+    output: PathBuf,
+    height: Option<u64>,
+    conf: &BazukaConfig,
+    dev_wallet: Option<WalletCollection>,
+    small_mpn: bool,
+) {
+    match try_export_snapshot(
+        &output,
+        height,
+        conf,
+        blockchain_config(dev_wallet, small_mpn),
+    ) {
+        Ok(header) => println!(
+            "Snapshot of height {} exported to {} (Checksum: {})",
+            header.height,
+            output.display(),
+            header.checksum
+        ),
+        Err(err) => println!("Error: {}", err),
+    }
+}
+
+fn try_import_snapshot(
+    input: &Path,
+    keep_rollbacks: Option<u64>,
+    conf: &BazukaConfig,
+    blockchain_conf: BlockchainConfig,
+) -> Result<u64, Box<dyn Error>> {
+    let keep_rollbacks = keep_rollbacks.unwrap_or(blockchain_conf.max_reorg_depth);
+    let chain = KvStoreChain::import_snapshot(
+        LevelDbKvStore::new(&conf.db, 64)?,
+        blockchain_conf,
+        BufReader::new(File::open(input)?),
+        keep_rollbacks,
+    )?;
+    Ok(chain.get_height()?)
+}
+
+pub fn import_snapshot(
+    input: PathBuf,
+    keep_rollbacks: Option<u64>,
+    conf: &BazukaConfig,
+    dev_wallet: Option<WalletCollection>,
+    small_mpn: bool,
+) {
+    match try_import_snapshot(
+        &input,
+        keep_rollbacks,
+        conf,
+        blockchain_config(dev_wallet, small_mpn),
+    ) {
+        Ok(height) => println!(
+            "Snapshot imported! Syncing will resume from height {}.",
+            height
+        ),
+        Err(err) => println!("Error: {}", err),
+    }
+}
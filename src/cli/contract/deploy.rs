@@ -0,0 +1,62 @@
+use std::path::{Path, PathBuf};
+
+use crate::cli::{BazukaConfig, CURRENT_NETWORK};
+use bazuka::client::{BazukaClient, Limit, NodeError};
+use bazuka::common::*;
+use bazuka::contract::ContractPackage;
+use bazuka::core::{Decimal, Money, NonceGroup, TokenId};
+use bazuka::wallet::WalletCollection;
+use tokio::try_join;
+
+pub async fn deploy(
+    memo: Option<String>,
+    package: PathBuf,
+    fee: Decimal,
+    conf: BazukaConfig,
+    mut wallet: WalletCollection,
+    wallet_path: &PathBuf,
+    contracts_path: &Path,
+) -> () {
+    let contract = ContractPackage::load(&package).unwrap();
+    let tx_builder = wallet.current().tx_builder();
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    try_join!(
+        async move {
+            let curr_nonce = client.get_account(tx_builder.get_address()).await?.nonce;
+
+            let new_nonce = wallet
+                .current()
+                .new_nonce(NonceGroup::TransactionAndDelta(tx_builder.get_address()))
+                .unwrap_or(curr_nonce + 1);
+            let (tx, contract_id) = contract.create_tx(
+                &tx_builder,
+                memo.unwrap_or_default(),
+                Money {
+                    amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
+                    token_id: TokenId::Ziesha,
+                },
+                new_nonce,
+            );
+            if let Some(err) = client.transact(tx.clone().into()).await?.error {
+                println!("Error: {}", err);
+            } else {
+                wallet.current().add_tx(tx.into());
+                wallet.save(wallet_path).unwrap();
+                // Proving params are kept for building function calls later
+                contract
+                    .save(&contracts_path.join(contract_id.to_string()))
+                    .unwrap();
+                println!("Sent");
+                println!("Contract-Id: {}", contract_id);
+            }
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use bazuka::contract::ContractPackage;
+use colored::Colorize;
+
+pub fn info(package: PathBuf) {
+    let contract = ContractPackage::load(&package).unwrap().contract;
+    println!(
+        "{} {}",
+        "State model:".bright_yellow(),
+        serde_json::to_string(&contract.state_model).unwrap()
+    );
+    println!(
+        "{} {}",
+        "Initial state:".bright_yellow(),
+        contract.initial_state.state_hash
+    );
+    for (i, f) in contract.deposit_functions.iter().enumerate() {
+        println!(
+            "{} {}",
+            format!("Deposit function #{}:", i).bright_yellow(),
+            serde_json::to_string(f).unwrap()
+        );
+    }
+    for (i, f) in contract.withdraw_functions.iter().enumerate() {
+        println!(
+            "{} {}",
+            format!("Withdraw function #{}:", i).bright_yellow(),
+            serde_json::to_string(f).unwrap()
+        );
+    }
+    for (i, f) in contract.functions.iter().enumerate() {
+        println!(
+            "{} {}",
+            format!("Function #{}:", i).bright_yellow(),
+            serde_json::to_string(f).unwrap()
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod deploy;
+pub mod info;
+
+pub use deploy::*;
+pub use info::*;
@@ -1,10 +1,10 @@
 #[cfg(feature = "node")]
 use {
-    bazuka::blockchain::Blockchain,
+    bazuka::blockchain::{Blockchain, TokenPrice},
     bazuka::client::{messages::SocialProfiles, Limit, NodeRequest},
     bazuka::common::*,
     bazuka::db::KvStore,
-    bazuka::node::{node_create, Firewall},
+    bazuka::node::{node_create, Firewall, NodeOptions},
     hyper::server::conn::AddrStream,
     hyper::service::{make_service_fn, service_fn},
     hyper::{Body, Client, Request, Response, Server, StatusCode},
@@ -15,10 +15,9 @@ use {
 #[cfg(feature = "client")]
 use {
     bazuka::client::{NodeError, PeerAddress},
-    bazuka::config,
-    bazuka::core::{Address, Decimal, GeneralAddress, MpnAddress, TokenId},
+    bazuka::core::{Address, Decimal, GeneralAddress, MpnAddress, TokenId, VestingId},
     bazuka::mpn::MpnWorker,
-    bazuka::wallet::WalletCollection,
+    bazuka::wallet::{WalletCollection, WatchOnlyWallet},
     colored::Colorize,
     serde::{Deserialize, Serialize},
     std::net::SocketAddr,
@@ -28,6 +27,7 @@ use {
 };
 
 pub mod chain;
+pub mod contract;
 pub mod init;
 pub mod wallet;
 pub use init::*;
@@ -68,6 +68,8 @@ pub struct BazukaConfig {
     bootstrap: Vec<PeerAddress>,
     db: PathBuf,
     mpn_workers: Vec<BazukaConfigMpnWorker>,
+    /// Number of recent blocks to keep bodies and rollback data of (Pruned mode)
+    prune: Option<u64>,
 }
 
 #[cfg(feature = "client")]
@@ -85,6 +87,12 @@ impl BazukaConfig {
 #[allow(clippy::large_enum_variant)]
 #[cfg(feature = "client")]
 enum WalletOptions {
+    /// Manage the accounts of the wallet
+    Account(AccountOptions),
+    /// Manage multisig accounts
+    Multisig(MultisigOptions),
+    /// Lock funds for a beneficiary, or claim the released ones
+    Vesting(VestingOptions),
     /// Add a new token to the wallet
     AddToken {
         #[structopt(long)]
@@ -104,6 +112,44 @@ enum WalletOptions {
         decimals: u8,
         #[structopt(long)]
         mintable: bool,
+        /// Hard cap of the supply, when mintable
+        #[structopt(long)]
+        max_supply: Option<Decimal>,
+        /// URI of the token's off-chain metadata
+        #[structopt(long)]
+        metadata_uri: Option<String>,
+        /// Hex-encoded hash of the content behind the metadata URI
+        #[structopt(long)]
+        metadata_hash: Option<String>,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Allow another account to transfer some of your tokens. Overwrites the
+    /// current allowance, approve 0 first when changing a non-zero one.
+    Approve {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        spender: Address,
+        #[structopt(long)]
+        token_id: Option<TokenId>,
+        #[structopt(long)]
+        amount: Decimal,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Transfer tokens of another account, approved for you
+    TransferFrom {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        owner: Address,
+        #[structopt(long)]
+        to: Address,
+        #[structopt(long)]
+        token_id: Option<TokenId>,
+        #[structopt(long)]
+        amount: Decimal,
         #[structopt(long, default_value = "0")]
         fee: Decimal,
     },
@@ -121,6 +167,9 @@ enum WalletOptions {
         amount: Decimal,
         #[structopt(long, default_value = "0")]
         fee: Decimal,
+        /// Index of the account to send from (The account in use by default)
+        #[structopt(long)]
+        account: Option<usize>,
     },
     /// Register your validator
     RegisterValidator {
@@ -131,6 +180,13 @@ enum WalletOptions {
         #[structopt(long, default_value = "0")]
         fee: Decimal,
     },
+    /// Stop being a validator, delegations start unbonding
+    UnregisterValidator {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
     /// Delegate to a validator
     Delegate {
         #[structopt(long)]
@@ -142,8 +198,9 @@ enum WalletOptions {
         #[structopt(long, default_value = "0")]
         fee: Decimal,
     },
-    /// Reclaim funds inside an ended delegatation back to your account
-    ReclaimDelegate {
+    /// Undelegate from a validator, funds are locked until the unbonding
+    /// period is over
+    Undelegate {
         #[structopt(long)]
         memo: Option<String>,
         #[structopt(long)]
@@ -153,6 +210,15 @@ enum WalletOptions {
         #[structopt(long, default_value = "0")]
         fee: Decimal,
     },
+    /// Reclaim funds inside an ended delegatation back to your account
+    ReclaimDelegate {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        from: Address,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
     /// Resets wallet nonces
     Reset {},
     /// Get info and balances of the wallet
@@ -162,6 +228,197 @@ enum WalletOptions {
     },
     /// Resend pending transactions
     ResendPending {},
+    /// Encrypt the wallet file with a passphrase, or end the unlocked session
+    Lock {},
+    /// Unlock the encrypted wallet for a while, so that the passphrase is not
+    /// asked again by every command. The key is only kept in the memory of a
+    /// background process.
+    Unlock {
+        /// Seconds until the wallet gets locked again
+        #[structopt(long, default_value = "300")]
+        duration: u64,
+    },
+    /// Background process serving the session started by `unlock`
+    #[structopt(setting = structopt::clap::AppSettings::Hidden)]
+    SessionAgent {
+        #[structopt(long)]
+        duration: u64,
+    },
+    /// Change the passphrase of an encrypted wallet
+    ChangePassphrase {},
+    /// Create a watch-only wallet, which can prepare transactions without
+    /// holding any private keys
+    Watch {
+        #[structopt(long)]
+        address: Address,
+        #[structopt(long)]
+        mpn_address: MpnAddress,
+    },
+    /// Prepare an unsigned transaction with the watch-only wallet
+    PrepareSend {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        from: GeneralAddress,
+        #[structopt(long)]
+        to: GeneralAddress,
+        #[structopt(long)]
+        token_id: Option<TokenId>,
+        #[structopt(long)]
+        amount: Decimal,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+        #[structopt(long)]
+        nonce: Option<u32>,
+        #[structopt(long)]
+        output: PathBuf,
+    },
+    /// Sign prepared transactions, without connecting to the network
+    Sign {
+        #[structopt(long)]
+        input: PathBuf,
+        #[structopt(long)]
+        output: PathBuf,
+    },
+    /// Broadcast transactions that are signed offline
+    Broadcast {
+        #[structopt(long)]
+        input: PathBuf,
+    },
+}
+
+#[derive(StructOpt)]
+#[cfg(feature = "client")]
+enum AccountOptions {
+    /// Derive a new account from the wallet mnemonic
+    New {
+        #[structopt(long)]
+        name: String,
+    },
+    /// List the accounts of the wallet
+    List {},
+    /// Choose the account used by the wallet commands
+    Use {
+        #[structopt(long)]
+        index: usize,
+    },
+    /// Rename an account
+    Rename {
+        #[structopt(long)]
+        index: usize,
+        #[structopt(long)]
+        name: String,
+    },
+}
+
+#[derive(StructOpt)]
+#[cfg(feature = "client")]
+enum VestingOptions {
+    /// Lock funds for a beneficiary until a block height or a timestamp
+    Create {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        to: Address,
+        #[structopt(long)]
+        token_id: Option<TokenId>,
+        #[structopt(long)]
+        amount: Decimal,
+        #[structopt(long)]
+        unlock_height: Option<u64>,
+        #[structopt(long)]
+        unlock_timestamp: Option<u32>,
+        /// Release the funds linearly until the given height
+        #[structopt(long)]
+        linear_until_height: Option<u64>,
+        /// Release the funds linearly until the given timestamp
+        #[structopt(long)]
+        linear_until_timestamp: Option<u32>,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// List the vestings of which you are the beneficiary
+    List {},
+    /// Claim the released funds of a vesting
+    Claim {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        id: VestingId,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+}
+
+#[derive(StructOpt)]
+#[cfg(feature = "client")]
+enum MultisigOptions {
+    /// Register a M-of-N multisig account
+    Register {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        threshold: u8,
+        #[structopt(long)]
+        signer: Vec<Address>,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Create an envelope for sending funds from a multisig account
+    Propose {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        threshold: u8,
+        #[structopt(long)]
+        signer: Vec<Address>,
+        #[structopt(long)]
+        to: Address,
+        #[structopt(long)]
+        token_id: Option<TokenId>,
+        #[structopt(long)]
+        amount: Decimal,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+        #[structopt(long)]
+        output: PathBuf,
+    },
+    /// Add your signature to an envelope
+    Sign {
+        #[structopt(long)]
+        input: PathBuf,
+        /// Where to write the signed envelope (Overwrites the input by default)
+        #[structopt(long)]
+        output: Option<PathBuf>,
+    },
+    /// Collect the signatures of multiple copies of an envelope
+    Combine {
+        #[structopt(long)]
+        input: Vec<PathBuf>,
+        #[structopt(long)]
+        output: PathBuf,
+    },
+    /// Send the transaction of a fully signed envelope
+    Broadcast {
+        #[structopt(long)]
+        input: PathBuf,
+    },
+}
+
+#[cfg(feature = "node")]
+fn parse_fee_token_price(s: &str) -> Result<(TokenId, TokenPrice), String> {
+    let (token_id, price) = s
+        .split_once('=')
+        .ok_or_else(|| "Expected <token-id>=<num>[/<den>]".to_string())?;
+    let (num, den) = price.split_once('/').unwrap_or((price, "1"));
+    let num = num.parse().map_err(|_| "Invalid price!".to_string())?;
+    let den = den.parse().map_err(|_| "Invalid price!".to_string())?;
+    Ok((
+        token_id
+            .parse()
+            .map_err(|_| "Invalid token-id!".to_string())?,
+        TokenPrice::new(num, den).ok_or_else(|| "Invalid price!".to_string())?,
+    ))
 }
 
 #[derive(StructOpt)]
@@ -180,6 +437,12 @@ enum NodeCliOptions {
         dev: bool,
         #[structopt(long)]
         small_mpn: bool,
+        #[structopt(long)]
+        tx_history: bool,
+        /// Accept tx fees paid in a custom token, given its price in Ziesha as
+        /// a fraction (E.g. --fee-token-price <token-id>=1/2)
+        #[structopt(long, parse(try_from_str = parse_fee_token_price))]
+        fee_token_price: Vec<(TokenId, TokenPrice)>,
     },
     /// Get status of a node
     Status {},
@@ -197,6 +460,58 @@ enum ChainCliOptions {
     DbQuery { prefix: String },
     /// Check health of the blockchain
     HealthCheck {},
+    /// Export/import snapshots of the blockchain
+    Snapshot(SnapshotCliOptions),
+}
+
+#[derive(StructOpt)]
+#[cfg(feature = "client")]
+enum SnapshotCliOptions {
+    /// Export the database at the given height (Latest height by default)
+    Export {
+        #[structopt(long)]
+        output: PathBuf,
+        #[structopt(long)]
+        height: Option<u64>,
+        /// The database belongs to a dev chain
+        #[structopt(long)]
+        dev: bool,
+        #[structopt(long)]
+        small_mpn: bool,
+    },
+    /// Boot an empty database from a snapshot. Snapshots are only checked for
+    /// corruption, so they should come from a trusted source
+    Import {
+        #[structopt(long)]
+        input: PathBuf,
+        /// Number of recent blocks that can still be rolled back
+        #[structopt(long)]
+        keep_rollbacks: Option<u64>,
+        /// The snapshot belongs to a dev chain
+        #[structopt(long)]
+        dev: bool,
+        #[structopt(long)]
+        small_mpn: bool,
+    },
+}
+
+#[derive(StructOpt)]
+#[cfg(feature = "client")]
+enum ContractCliOptions {
+    /// Deploy a contract package built with the contract SDK
+    Deploy {
+        #[structopt(long)]
+        memo: Option<String>,
+        #[structopt(long)]
+        package: PathBuf,
+        #[structopt(long, default_value = "0")]
+        fee: Decimal,
+    },
+    /// Show the state model and verifying keys of a contract package
+    Info {
+        #[structopt(long)]
+        package: PathBuf,
+    },
 }
 
 #[derive(StructOpt)]
@@ -230,11 +545,15 @@ enum CliOptions {
 
     /// Chain subcommand
     Chain(ChainCliOptions),
+
+    /// Contract subcommand
+    Contract(ContractCliOptions),
 }
 
 #[cfg(feature = "node")]
 async fn run_node<K: KvStore, B: Blockchain<K>>(
     blockchain: B,
+    node_opts: NodeOptions,
     bazuka_config: BazukaConfig,
     wallet: WalletCollection,
     social_profiles: SocialProfiles,
@@ -270,7 +589,7 @@ async fn run_node<K: KvStore, B: Blockchain<K>>(
     // Async loop that is responsible for answering external requests and gathering
     // data from external world through a heartbeat loop.
     let node = node_create(
-        config::node::get_node_options(),
+        node_opts,
         &network,
         address,
         bootstrap_nodes,
@@ -367,7 +686,24 @@ pub async fn initialize_cli() {
         .ok()
         .map(|f| serde_yaml::from_reader(f).unwrap());
     let wallet_path = home::home_dir().unwrap().join(Path::new(".bazuka-wallet"));
-    let wallet = WalletCollection::open(wallet_path.clone()).unwrap();
+    let session_path = home::home_dir()
+        .unwrap()
+        .join(Path::new(".bazuka-wallet-session"));
+    // The wallet is only opened (and decrypted) by the commands that need it
+    let wallet = || {
+        WalletCollection::open_with_session(
+            &wallet_path,
+            &session_path,
+            crate::cli::wallet::read_passphrase,
+        )
+        .unwrap()
+    };
+    let watch_only_path = home::home_dir()
+        .unwrap()
+        .join(Path::new(".bazuka-watch-only"));
+    let contracts_path = home::home_dir()
+        .unwrap()
+        .join(Path::new(".bazuka-contracts"));
 
     match opts {
         CliOptions::Chain(chain_opts) => match chain_opts {
@@ -380,6 +716,53 @@ pub async fn initialize_cli() {
             ChainCliOptions::HealthCheck {} => {
                 crate::cli::chain::health_check(&conf.expect(BAZUKA_NOT_INITILIZED));
             }
+            ChainCliOptions::Snapshot(snapshot_opts) => match snapshot_opts {
+                SnapshotCliOptions::Export {
+                    output,
+                    height,
+                    dev,
+                    small_mpn,
+                } => {
+                    crate::cli::chain::export_snapshot(
+                        output,
+                        height,
+                        &conf.expect(BAZUKA_NOT_INITILIZED),
+                        dev.then(|| wallet().expect(BAZUKA_NOT_INITILIZED)),
+                        small_mpn,
+                    );
+                }
+                SnapshotCliOptions::Import {
+                    input,
+                    keep_rollbacks,
+                    dev,
+                    small_mpn,
+                } => {
+                    crate::cli::chain::import_snapshot(
+                        input,
+                        keep_rollbacks,
+                        &conf.expect(BAZUKA_NOT_INITILIZED),
+                        dev.then(|| wallet().expect(BAZUKA_NOT_INITILIZED)),
+                        small_mpn,
+                    );
+                }
+            },
+        },
+        CliOptions::Contract(contract_opts) => match contract_opts {
+            ContractCliOptions::Deploy { memo, package, fee } => {
+                crate::cli::contract::deploy(
+                    memo,
+                    package,
+                    fee,
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    &contracts_path,
+                )
+                .await;
+            }
+            ContractCliOptions::Info { package } => {
+                crate::cli::contract::info(package);
+            }
         },
         #[cfg(feature = "node")]
         CliOptions::Node(node_opts) => match node_opts {
@@ -389,22 +772,26 @@ pub async fn initialize_cli() {
                 dev,
                 ram,
                 small_mpn,
+                tx_history,
+                fee_token_price,
             } => {
                 crate::cli::node::start(
                     discord_handle,
                     client_only,
                     conf.expect(BAZUKA_NOT_INITILIZED),
-                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
                     ram,
                     dev,
                     small_mpn,
+                    tx_history,
+                    fee_token_price.into_iter().collect(),
                 )
                 .await;
             }
             NodeCliOptions::Status {} => {
                 crate::cli::node::status(
                     conf.expect(BAZUKA_NOT_INITILIZED),
-                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
                 )
                 .await;
             }
@@ -433,7 +820,7 @@ pub async fn initialize_cli() {
                 db,
                 conf,
                 &conf_path,
-                wallet,
+                wallet(),
                 &wallet_path,
             )
             .await
@@ -443,10 +830,143 @@ pub async fn initialize_cli() {
             println!("Client feature not turned on!");
         }
         CliOptions::Wallet(wallet_opts) => match wallet_opts {
+            WalletOptions::Multisig(multisig_opts) => match multisig_opts {
+                MultisigOptions::Register {
+                    memo,
+                    threshold,
+                    signer,
+                    fee,
+                } => {
+                    crate::cli::wallet::register_multisig(
+                        memo,
+                        threshold,
+                        signer,
+                        fee,
+                        conf.expect(BAZUKA_NOT_INITILIZED),
+                        wallet().expect(BAZUKA_NOT_INITILIZED),
+                        &wallet_path,
+                    )
+                    .await;
+                }
+                MultisigOptions::Propose {
+                    memo,
+                    threshold,
+                    signer,
+                    to,
+                    token_id,
+                    amount,
+                    fee,
+                    output,
+                } => {
+                    crate::cli::wallet::propose_multisig(
+                        memo,
+                        threshold,
+                        signer,
+                        to,
+                        amount,
+                        fee,
+                        token_id,
+                        output,
+                        conf.expect(BAZUKA_NOT_INITILIZED),
+                        wallet().expect(BAZUKA_NOT_INITILIZED),
+                    )
+                    .await;
+                }
+                MultisigOptions::Sign { input, output } => {
+                    crate::cli::wallet::sign_multisig(
+                        input,
+                        output,
+                        wallet().expect(BAZUKA_NOT_INITILIZED),
+                    );
+                }
+                MultisigOptions::Combine { input, output } => {
+                    crate::cli::wallet::combine_multisig(input, output);
+                }
+                MultisigOptions::Broadcast { input } => {
+                    crate::cli::wallet::broadcast_multisig(
+                        input,
+                        conf.expect(BAZUKA_NOT_INITILIZED),
+                        wallet().expect(BAZUKA_NOT_INITILIZED),
+                    )
+                    .await;
+                }
+            },
+            WalletOptions::Vesting(vesting_opts) => match vesting_opts {
+                VestingOptions::Create {
+                    memo,
+                    to,
+                    token_id,
+                    amount,
+                    unlock_height,
+                    unlock_timestamp,
+                    linear_until_height,
+                    linear_until_timestamp,
+                    fee,
+                } => {
+                    crate::cli::wallet::create_vesting(
+                        memo,
+                        to,
+                        token_id,
+                        amount,
+                        (unlock_height, unlock_timestamp),
+                        (linear_until_height, linear_until_timestamp),
+                        fee,
+                        conf.expect(BAZUKA_NOT_INITILIZED),
+                        wallet().expect(BAZUKA_NOT_INITILIZED),
+                        &wallet_path,
+                    )
+                    .await;
+                }
+                VestingOptions::List {} => {
+                    crate::cli::wallet::list_vestings(
+                        conf.expect(BAZUKA_NOT_INITILIZED),
+                        wallet().expect(BAZUKA_NOT_INITILIZED),
+                    )
+                    .await;
+                }
+                VestingOptions::Claim { memo, id, fee } => {
+                    crate::cli::wallet::claim_vesting(
+                        memo,
+                        id,
+                        fee,
+                        conf.expect(BAZUKA_NOT_INITILIZED),
+                        wallet().expect(BAZUKA_NOT_INITILIZED),
+                        &wallet_path,
+                    )
+                    .await;
+                }
+            },
+            WalletOptions::Account(account_opts) => match account_opts {
+                AccountOptions::New { name } => {
+                    crate::cli::wallet::new_account(
+                        name,
+                        wallet().expect(BAZUKA_NOT_INITILIZED),
+                        &wallet_path,
+                    );
+                }
+                AccountOptions::List {} => {
+                    crate::cli::wallet::list_accounts(wallet().expect(BAZUKA_NOT_INITILIZED));
+                }
+                AccountOptions::Use { index } => {
+                    crate::cli::wallet::use_account(
+                        index,
+                        wallet().expect(BAZUKA_NOT_INITILIZED),
+                        &wallet_path,
+                    );
+                }
+                AccountOptions::Rename { index, name } => {
+                    crate::cli::wallet::rename_account(
+                        index,
+                        name,
+                        wallet().expect(BAZUKA_NOT_INITILIZED),
+                        &wallet_path,
+                    );
+                }
+            },
             WalletOptions::AddToken { id } => {
                 crate::cli::wallet::add_token(
                     id,
-                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
                     &wallet_path,
                 );
             }
@@ -457,6 +977,9 @@ pub async fn initialize_cli() {
                 supply,
                 decimals,
                 mintable,
+                max_supply,
+                metadata_uri,
+                metadata_hash,
                 fee,
             } => {
                 crate::cli::wallet::new_token(
@@ -466,9 +989,52 @@ pub async fn initialize_cli() {
                     supply,
                     decimals,
                     mintable,
+                    max_supply,
+                    metadata_uri,
+                    metadata_hash,
+                    fee,
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                )
+                .await;
+            }
+            WalletOptions::Approve {
+                memo,
+                spender,
+                token_id,
+                amount,
+                fee,
+            } => {
+                crate::cli::wallet::approve(
+                    memo,
+                    spender,
+                    token_id,
+                    amount,
+                    fee,
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                )
+                .await;
+            }
+            WalletOptions::TransferFrom {
+                memo,
+                owner,
+                to,
+                token_id,
+                amount,
+                fee,
+            } => {
+                crate::cli::wallet::transfer_from(
+                    memo,
+                    owner,
+                    to,
+                    token_id,
+                    amount,
                     fee,
                     conf.expect(BAZUKA_NOT_INITILIZED),
-                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
                     &wallet_path,
                 )
                 .await;
@@ -480,6 +1046,7 @@ pub async fn initialize_cli() {
                 amount,
                 fee,
                 token_id,
+                account,
             } => {
                 crate::cli::wallet::send(
                     memo,
@@ -488,14 +1055,15 @@ pub async fn initialize_cli() {
                     amount,
                     fee,
                     token_id,
+                    account,
                     conf.expect(BAZUKA_NOT_INITILIZED),
-                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
                     &wallet_path,
                 )
                 .await;
             }
             WalletOptions::Reset {} => {
-                crate::cli::wallet::reset(wallet.expect(BAZUKA_NOT_INITILIZED), &wallet_path);
+                crate::cli::wallet::reset(wallet().expect(BAZUKA_NOT_INITILIZED), &wallet_path);
             }
             WalletOptions::RegisterValidator {
                 memo,
@@ -507,13 +1075,48 @@ pub async fn initialize_cli() {
                     commision,
                     fee,
                     conf.expect(BAZUKA_NOT_INITILIZED),
-                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
                     &wallet_path,
                 )
                 .await;
             }
-            WalletOptions::ReclaimDelegate { .. } => {
-                unimplemented!();
+            WalletOptions::UnregisterValidator { memo, fee } => {
+                crate::cli::wallet::unregister_validator(
+                    memo,
+                    fee,
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                )
+                .await;
+            }
+            WalletOptions::Undelegate {
+                memo,
+                from,
+                amount,
+                fee,
+            } => {
+                crate::cli::wallet::undelegate(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    memo,
+                    amount,
+                    from,
+                    fee,
+                )
+                .await;
+            }
+            WalletOptions::ReclaimDelegate { memo, from, fee } => {
+                crate::cli::wallet::reclaim_delegate(
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    memo,
+                    from,
+                    fee,
+                )
+                .await;
             }
             WalletOptions::Delegate {
                 memo,
@@ -523,7 +1126,7 @@ pub async fn initialize_cli() {
             } => {
                 crate::cli::wallet::delegate(
                     conf.expect(BAZUKA_NOT_INITILIZED),
-                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
                     &wallet_path,
                     memo,
                     amount,
@@ -535,7 +1138,7 @@ pub async fn initialize_cli() {
             WalletOptions::ResendPending {} => {
                 crate::cli::wallet::resend_pending(
                     conf.expect(BAZUKA_NOT_INITILIZED),
-                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
                     &wallet_path,
                 )
                 .await;
@@ -543,11 +1146,65 @@ pub async fn initialize_cli() {
             WalletOptions::Info { validator } => {
                 crate::cli::wallet::info(
                     conf.expect(BAZUKA_NOT_INITILIZED),
-                    wallet.expect(BAZUKA_NOT_INITILIZED),
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
                     validator,
                 )
                 .await;
             }
+            WalletOptions::Lock {} => {
+                crate::cli::wallet::lock(&wallet_path, &session_path);
+            }
+            WalletOptions::Unlock { duration } => {
+                crate::cli::wallet::unlock(&wallet_path, duration, &session_path);
+            }
+            WalletOptions::SessionAgent { duration } => {
+                crate::cli::wallet::session_agent(&wallet_path, duration, &session_path);
+            }
+            WalletOptions::ChangePassphrase {} => {
+                crate::cli::wallet::change_passphrase(
+                    wallet().expect(BAZUKA_NOT_INITILIZED),
+                    &wallet_path,
+                    &session_path,
+                );
+            }
+            WalletOptions::Watch {
+                address,
+                mpn_address,
+            } => {
+                crate::cli::wallet::watch(address, mpn_address, &watch_only_path);
+            }
+            WalletOptions::PrepareSend {
+                memo,
+                from,
+                to,
+                token_id,
+                amount,
+                fee,
+                nonce,
+                output,
+            } => {
+                crate::cli::wallet::prepare_send(
+                    memo,
+                    from,
+                    to,
+                    amount,
+                    fee,
+                    token_id,
+                    nonce,
+                    output,
+                    conf.expect(BAZUKA_NOT_INITILIZED),
+                    WatchOnlyWallet::open(&watch_only_path)
+                        .unwrap()
+                        .expect("Watch-only wallet not found!"),
+                )
+                .await;
+            }
+            WalletOptions::Sign { input, output } => {
+                crate::cli::wallet::sign(input, output, wallet().expect(BAZUKA_NOT_INITILIZED));
+            }
+            WalletOptions::Broadcast { input } => {
+                crate::cli::wallet::broadcast(input, conf.expect(BAZUKA_NOT_INITILIZED)).await;
+            }
         },
     }
 }
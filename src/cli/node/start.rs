@@ -1,10 +1,17 @@
 use crate::cli::{run_node, BazukaConfig, CURRENT_NETWORK};
 
 use bazuka::{
-    blockchain::KvStoreChain, client::messages::SocialProfiles, config, db::LevelDbKvStore,
-    db::RamKvStore, wallet::WalletCollection,
+    blockchain::{FeeTokenPrices, KvStoreChain, TokenPrice},
+    client::messages::SocialProfiles,
+    config,
+    core::TokenId,
+    db::LevelDbKvStore,
+    db::RamKvStore,
+    wallet::WalletCollection,
 };
+use std::collections::HashMap;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn start(
     discord_handle: Option<String>,
     client_only: bool,
@@ -13,17 +20,28 @@ pub async fn start(
     ram: bool,
     dev: bool,
     small_mpn: bool,
+    tx_history: bool,
+    fee_token_prices: HashMap<TokenId, TokenPrice>,
 ) {
-    let blockchain_conf = if dev {
+    let mut blockchain_conf = if dev {
         let validator_wallet = wallet.validator().tx_builder();
         config::blockchain::get_dev_blockchain_config(&validator_wallet, small_mpn)
     } else {
         config::blockchain::get_blockchain_config()
     };
+    blockchain_conf.tx_history = tx_history;
+    // A pruned node should still be able to roll back as deep as a reorg can go
+    blockchain_conf.pruning = conf
+        .prune
+        .map(|window| std::cmp::max(window, blockchain_conf.max_reorg_depth));
+
+    let mut node_opts = config::node::get_node_options();
+    node_opts.fee_token_prices = FeeTokenPrices::new(fee_token_prices);
 
     if ram {
         run_node(
             KvStoreChain::new(RamKvStore::new(), blockchain_conf).unwrap(),
+            node_opts,
             conf.clone(),
             wallet.clone(),
             SocialProfiles {
@@ -37,6 +55,7 @@ pub async fn start(
     } else {
         run_node(
             KvStoreChain::new(LevelDbKvStore::new(&conf.db, 64).unwrap(), blockchain_conf).unwrap(),
+            node_opts,
             conf.clone(),
             wallet.clone(),
             SocialProfiles {
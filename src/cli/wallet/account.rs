@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use bazuka::wallet::WalletCollection;
+use colored::Colorize;
+
+pub fn new_account(name: String, mut wallet: WalletCollection, wallet_path: &PathBuf) {
+    let index = wallet.new_account(name);
+    wallet.save(wallet_path).unwrap();
+    println!("Account #{} created!", index);
+}
+
+pub fn list_accounts(mut wallet: WalletCollection) {
+    let current_account = wallet.current_account();
+    for (index, name) in wallet.accounts() {
+        let tx_builder = wallet.user(index).tx_builder();
+        println!(
+            "{} {}{}",
+            format!("#{}", index).bright_yellow(),
+            name,
+            if index == current_account {
+                " (In use)".bright_green().to_string()
+            } else {
+                "".into()
+            }
+        );
+        println!("\t{}", tx_builder.get_address());
+        println!("\t{}", tx_builder.get_mpn_address());
+    }
+}
+
+pub fn use_account(index: usize, mut wallet: WalletCollection, wallet_path: &PathBuf) {
+    wallet.use_account(index).unwrap();
+    wallet.save(wallet_path).unwrap();
+    println!("Using account #{}!", index);
+}
+
+pub fn rename_account(
+    index: usize,
+    name: String,
+    mut wallet: WalletCollection,
+    wallet_path: &PathBuf,
+) {
+    wallet.rename_account(index, name).unwrap();
+    wallet.save(wallet_path).unwrap();
+    println!("Account #{} renamed!", index);
+}
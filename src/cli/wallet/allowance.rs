@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+use super::submit_tx;
+use crate::cli::BazukaConfig;
+use bazuka::core::{Address, Decimal, Money, RegularSendEntry, TokenId};
+use bazuka::wallet::WalletCollection;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn approve(
+    memo: Option<String>,
+    spender: Address,
+    token_id: Option<TokenId>,
+    amount: Decimal,
+    fee: Decimal,
+    conf: BazukaConfig,
+    wallet: WalletCollection,
+    wallet_path: &PathBuf,
+) {
+    let tkn = token_id.unwrap_or(TokenId::Ziesha);
+    submit_tx(
+        tkn,
+        fee,
+        conf,
+        wallet,
+        wallet_path,
+        |tx_builder, tkn_decimals, fee, nonce| {
+            tx_builder.approve(
+                memo.unwrap_or_default(),
+                spender,
+                Money {
+                    amount: amount.to_amount(tkn_decimals),
+                    token_id: tkn,
+                },
+                fee,
+                nonce,
+            )
+        },
+    )
+    .await;
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn transfer_from(
+    memo: Option<String>,
+    owner: Address,
+    to: Address,
+    token_id: Option<TokenId>,
+    amount: Decimal,
+    fee: Decimal,
+    conf: BazukaConfig,
+    wallet: WalletCollection,
+    wallet_path: &PathBuf,
+) {
+    let tkn = token_id.unwrap_or(TokenId::Ziesha);
+    submit_tx(
+        tkn,
+        fee,
+        conf,
+        wallet,
+        wallet_path,
+        |tx_builder, tkn_decimals, fee, nonce| {
+            tx_builder.transfer_from(
+                memo.unwrap_or_default(),
+                owner,
+                vec![RegularSendEntry {
+                    dst: to,
+                    amount: Money {
+                        amount: amount.to_amount(tkn_decimals),
+                        token_id: tkn,
+                    },
+                }],
+                fee,
+                nonce,
+            )
+        },
+    )
+    .await;
+}
@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+use crate::cli::{BazukaConfig, CURRENT_NETWORK};
+use bazuka::wallet::read_transactions;
+use bazuka::{
+    client::{BazukaClient, Limit, NodeError},
+    common::*,
+    core::{GeneralTransaction, Signer},
+    crypto::SignatureScheme,
+};
+use tokio::try_join;
+
+/// Sends transactions that were signed offline
+pub async fn broadcast(input: PathBuf, conf: BazukaConfig) {
+    let txs = read_transactions::<GeneralTransaction, _>(&input).unwrap();
+    if let Some(tx) = txs.iter().find(|tx| !tx.verify_signature()) {
+        panic!("Transaction is not properly signed: {:#?}", tx);
+    }
+
+    // The client key is only used for talking with the node
+    let (_, client_key) = Signer::generate_keys(&rand::random::<[u8; 32]>());
+    let (req_loop, client) = BazukaClient::connect(
+        client_key,
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+
+    try_join!(
+        async move {
+            for tx in txs {
+                if let Some(err) = client.transact(tx).await?.error {
+                    println!("Error: {}", err);
+                } else {
+                    println!("Sent");
+                }
+            }
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
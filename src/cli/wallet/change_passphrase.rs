@@ -0,0 +1,13 @@
+use std::path::Path;
+
+use bazuka::wallet::WalletCollection;
+
+use super::read_new_passphrase;
+
+pub fn change_passphrase(mut wallet: WalletCollection, wallet_path: &Path, session_path: &Path) {
+    let passphrase = read_new_passphrase().unwrap();
+    wallet.change_passphrase(&passphrase).unwrap();
+    wallet.save(wallet_path).unwrap();
+    WalletCollection::end_session(session_path).unwrap();
+    println!("Wallet passphrase changed!");
+}
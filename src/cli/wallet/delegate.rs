@@ -18,7 +18,7 @@ pub async fn delegate(
     to: Address,
     fee: Decimal,
 ) -> () {
-    let tx_builder = wallet.user(0).tx_builder();
+    let tx_builder = wallet.current().tx_builder();
     if tx_builder.get_address() == to {
         println!(
             "{} {}\n{}",
@@ -39,7 +39,7 @@ pub async fn delegate(
             let curr_nonce = client.get_account(tx_builder.get_address()).await?.nonce;
 
             let new_nonce = wallet
-                .user(0)
+                .current()
                 .new_nonce(NonceGroup::TransactionAndDelta(tx_builder.get_address()))
                 .unwrap_or(curr_nonce + 1);
             let tx = tx_builder.delegate(
@@ -57,7 +57,106 @@ pub async fn delegate(
             if let Some(err) = client.transact(tx.clone().into()).await?.error {
                 println!("Error: {}", err);
             } else {
-                wallet.user(0).add_tx(tx.clone().into());
+                wallet.current().add_tx(tx.clone().into());
+                wallet.save(wallet_path).unwrap();
+                println!("Sent");
+            }
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
+
+pub async fn undelegate(
+    conf: BazukaConfig,
+    mut wallet: WalletCollection,
+    wallet_path: &PathBuf,
+    memo: Option<String>,
+    amount: Decimal,
+    from: Address,
+    fee: Decimal,
+) {
+    let tx_builder = wallet.current().tx_builder();
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    try_join!(
+        async move {
+            let curr_nonce = client.get_account(tx_builder.get_address()).await?.nonce;
+
+            let new_nonce = wallet
+                .current()
+                .new_nonce(NonceGroup::TransactionAndDelta(tx_builder.get_address()))
+                .unwrap_or(curr_nonce + 1);
+            let tx = tx_builder.delegate(
+                memo.unwrap_or_default(),
+                from,
+                amount.to_amount(bazuka::config::UNIT_ZEROS),
+                true,
+                Money {
+                    amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
+                    token_id: TokenId::Ziesha,
+                },
+                new_nonce,
+            );
+
+            if let Some(err) = client.transact(tx.clone().into()).await?.error {
+                println!("Error: {}", err);
+            } else {
+                wallet.current().add_tx(tx.clone().into());
+                wallet.save(wallet_path).unwrap();
+                println!(
+                    "Sent, funds can be reclaimed after the unbonding period through `bazuka wallet reclaim-delegate`"
+                );
+            }
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
+
+pub async fn reclaim_delegate(
+    conf: BazukaConfig,
+    mut wallet: WalletCollection,
+    wallet_path: &PathBuf,
+    memo: Option<String>,
+    from: Address,
+    fee: Decimal,
+) {
+    let tx_builder = wallet.current().tx_builder();
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    try_join!(
+        async move {
+            let curr_nonce = client.get_account(tx_builder.get_address()).await?.nonce;
+
+            let new_nonce = wallet
+                .current()
+                .new_nonce(NonceGroup::TransactionAndDelta(tx_builder.get_address()))
+                .unwrap_or(curr_nonce + 1);
+            let tx = tx_builder.reclaim_delegate(
+                memo.unwrap_or_default(),
+                from,
+                Money {
+                    amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
+                    token_id: TokenId::Ziesha,
+                },
+                new_nonce,
+            );
+
+            if let Some(err) = client.transact(tx.clone().into()).await?.error {
+                println!("Error: {}", err);
+            } else {
+                wallet.current().add_tx(tx.clone().into());
                 wallet.save(wallet_path).unwrap();
                 println!("Sent");
             }
@@ -3,7 +3,7 @@ use tokio::try_join;
 use crate::cli::{BazukaConfig, CURRENT_NETWORK};
 use bazuka::client::{Limit, NodeError};
 use bazuka::common::*;
-use bazuka::core::{MpnAddress, NonceGroup};
+use bazuka::core::{Address, Amount, MpnAddress, NonceGroup};
 use bazuka::wallet::WalletCollection;
 use bazuka::{client::BazukaClient, core::TokenId};
 use colored::Colorize;
@@ -11,7 +11,13 @@ use std::collections::HashMap;
 
 pub async fn info(conf: BazukaConfig, mut wallet: WalletCollection, validator: bool) -> () {
     let val_tx_builder = wallet.validator().tx_builder();
-    let tx_builder = wallet.user(0).tx_builder();
+    let tx_builder = wallet.current().tx_builder();
+    let current_account = wallet.current_account();
+    let accounts = wallet
+        .accounts()
+        .into_iter()
+        .map(|(index, name)| (index, name, wallet.user(index).clone()))
+        .collect::<Vec<_>>();
 
     let (req_loop, client) = BazukaClient::connect(
         tx_builder.get_priv_key(),
@@ -84,151 +90,224 @@ pub async fn info(conf: BazukaConfig, mut wallet: WalletCollection, validator: b
                     }
                 }
             } else {
-                let acc = client.get_account(tx_builder.get_address()).await?;
-                let mut token_balances = HashMap::new();
                 let mut tokens = HashMap::new();
-                for tkn in wallet.user(0).get_tokens().iter() {
-                    if let Some(inf) = client.get_token(*tkn).await?.token {
-                        token_balances.insert(
-                            *tkn,
-                            client.get_balance(tx_builder.get_address(), *tkn).await?,
-                        );
-                        tokens.insert(*tkn, inf);
+                let mut total_balances = HashMap::<TokenId, Amount>::new();
+                for (index, name, account_wallet) in accounts.iter() {
+                    let index = *index;
+                    let account_tx_builder = account_wallet.tx_builder();
+                    let acc = client.get_account(account_tx_builder.get_address()).await?;
+                    let mut token_balances = HashMap::new();
+                    for tkn in account_wallet.get_tokens().iter() {
+                        if let Some(inf) = client.get_token(*tkn).await?.token {
+                            let balance = client
+                                .get_balance(account_tx_builder.get_address(), *tkn)
+                                .await?;
+                            *total_balances.entry(*tkn).or_insert(Amount(0)) += balance.balance;
+                            token_balances.insert(*tkn, balance);
+                            tokens.insert(*tkn, inf);
+                        }
                     }
-                }
 
-                let curr_nonce = wallet
-                    .user(0)
-                    .new_nonce(NonceGroup::TransactionAndDelta(tx_builder.get_address()))
-                    .map(|n| n - 1);
-                let curr_mpn_deposit_nonce = wallet
-                    .user(0)
-                    .new_nonce(NonceGroup::MpnDeposit(tx_builder.get_address()))
-                    .map(|n| n - 1);
+                    let curr_nonce = account_wallet
+                        .new_nonce(NonceGroup::TransactionAndDelta(
+                            account_tx_builder.get_address(),
+                        ))
+                        .map(|n| n - 1);
+                    let curr_mpn_deposit_nonce = account_wallet
+                        .new_nonce(NonceGroup::MpnDeposit(account_tx_builder.get_address()))
+                        .map(|n| n - 1);
 
-                println!();
-                println!("{}", "Main-chain\n---------".bright_green());
-                println!(
-                    "{}\t{}",
-                    "Address:".bright_yellow(),
-                    tx_builder.get_address()
-                );
-                for id in wallet.user(0).get_tokens().iter() {
-                    if let Some(inf) = token_balances.get(id) {
-                        println!(
-                            "{}\t{}{}",
-                            format!("<{}>:", inf.name).bright_yellow(),
-                            inf.balance
-                                .display_by_decimals(tokens.get(id).unwrap().decimals),
-                            if *id == TokenId::Ziesha {
-                                bazuka::config::SYMBOL.to_string()
+                    println!();
+                    println!(
+                        "{}",
+                        format!(
+                            "Account #{}: {}{}\n=========",
+                            index,
+                            name,
+                            if index == current_account {
+                                " (In use)"
                             } else {
-                                format!(" {} (Token-Id: {})", inf.symbol, id)
+                                ""
                             }
-                        );
+                        )
+                        .bright_blue()
+                    );
+                    println!();
+                    println!("{}", "Main-chain\n---------".bright_green());
+                    println!(
+                        "{}\t{}",
+                        "Address:".bright_yellow(),
+                        account_tx_builder.get_address()
+                    );
+                    for id in account_wallet.get_tokens().iter() {
+                        if let Some(inf) = token_balances.get(id) {
+                            println!(
+                                "{}\t{}{}",
+                                format!("<{}>:", inf.name).bright_yellow(),
+                                inf.balance
+                                    .display_by_decimals(tokens.get(id).unwrap().decimals),
+                                if *id == TokenId::Ziesha {
+                                    bazuka::config::SYMBOL.to_string()
+                                } else {
+                                    format!(" {} (Token-Id: {})", inf.symbol, id)
+                                }
+                            );
+                        }
                     }
-                }
-                if let Some(nonce) = curr_nonce {
-                    if nonce > acc.nonce {
-                        println!("(Pending transactions: {})", nonce - acc.nonce);
+                    if let Some(nonce) = curr_nonce {
+                        if nonce > acc.nonce {
+                            println!("(Pending transactions: {})", nonce - acc.nonce);
+                        }
                     }
-                }
-                if let Some(nonce) = curr_mpn_deposit_nonce {
-                    if nonce > acc.mpn_deposit_nonce {
-                        println!("(Pending deposits: {})", nonce - acc.mpn_deposit_nonce);
+                    if let Some(nonce) = curr_mpn_deposit_nonce {
+                        if nonce > acc.mpn_deposit_nonce {
+                            println!("(Pending deposits: {})", nonce - acc.mpn_deposit_nonce);
+                        }
                     }
-                }
 
-                let delegations = client
-                    .get_delegations(tx_builder.get_address(), 100)
-                    .await?;
+                    let delegations = client
+                        .get_delegations(account_tx_builder.get_address(), 100)
+                        .await?;
 
-                if !delegations.delegatees.is_empty() {
-                    println!();
-                    println!("{}", "Delegatees\n---------".bright_green());
-                    for (addr, amount) in delegations.delegatees.iter() {
+                    if !delegations.delegatees.is_empty() {
+                        println!();
+                        println!("{}", "Delegatees\n---------".bright_green());
+                        for (addr, amount) in delegations.delegatees.iter() {
+                            println!(
+                                "You -> {} ({}{})",
+                                addr,
+                                amount.display_by_decimals(bazuka::config::UNIT_ZEROS),
+                                bazuka::config::SYMBOL
+                            );
+                        }
+                    }
+
+                    let rewards = client
+                        .get_staking_rewards(account_tx_builder.get_address())
+                        .await?
+                        .delegator_rewards;
+
+                    if !rewards.is_empty() {
+                        let mut earned = HashMap::<Address, Amount>::new();
+                        for reward in rewards.iter() {
+                            *earned.entry(reward.validator.clone()).or_insert(Amount(0)) +=
+                                reward.amount;
+                        }
+                        println!();
+                        println!("{}", "Staking rewards\n---------".bright_green());
+                        for (addr, amount) in earned.iter() {
+                            println!(
+                                "{} -> You ({}{})",
+                                addr,
+                                amount.display_by_decimals(bazuka::config::UNIT_ZEROS),
+                                bazuka::config::SYMBOL
+                            );
+                        }
                         println!(
-                            "You -> {} ({}{})",
-                            addr,
-                            amount.display_by_decimals(bazuka::config::UNIT_ZEROS),
+                            "{}\t{}{}",
+                            "Total earned:".bright_yellow(),
+                            earned
+                                .values()
+                                .fold(Amount(0), |a, b| a + *b)
+                                .display_by_decimals(bazuka::config::UNIT_ZEROS),
                             bazuka::config::SYMBOL
                         );
                     }
-                }
 
-                println!();
+                    println!();
 
-                let mpn_address = MpnAddress {
-                    pub_key: tx_builder.get_zk_address(),
-                };
+                    let mpn_address = MpnAddress {
+                        pub_key: account_tx_builder.get_zk_address(),
+                    };
 
-                for (i, addr) in [mpn_address].into_iter().enumerate() {
-                    println!(
-                        "{}",
-                        format!("MPN Account #{}\n---------", i).bright_green()
-                    );
-                    let resp = client.get_mpn_account(addr.clone()).await?.account;
-                    let curr_mpn_tx_nonce = wallet
-                        .user(0)
-                        .new_nonce(NonceGroup::MpnTransaction(addr.clone()));
-                    let curr_mpn_withdraw_nonce =
-                        wallet.user(0).new_nonce(NonceGroup::MpnWithdraw(addr));
-                    if !resp.address.is_on_curve() {
+                    for (i, addr) in [mpn_address].into_iter().enumerate() {
                         println!(
-                            "{}\t{}",
-                            "Address:".bright_yellow(),
-                            MpnAddress {
-                                pub_key: tx_builder.get_zk_address(),
-                            }
+                            "{}",
+                            format!("MPN Account #{}\n---------", i).bright_green()
                         );
-                        println!("Waiting to be activated... (Send some funds to it!)")
-                    } else {
-                        let acc_pk = bazuka::crypto::jubjub::PublicKey(resp.address.compress());
-                        if acc_pk != tx_builder.get_zk_address() {
+                        let resp = client.get_mpn_account(addr.clone()).await?.account;
+                        let curr_mpn_tx_nonce =
+                            account_wallet.new_nonce(NonceGroup::MpnTransaction(addr.clone()));
+                        let curr_mpn_withdraw_nonce =
+                            account_wallet.new_nonce(NonceGroup::MpnWithdraw(addr));
+                        if !resp.address.is_on_curve() {
                             println!(
-                                "{} {}",
-                                "Error:".bright_red(),
-                                "Slot acquired by someone else!"
+                                "{}\t{}",
+                                "Address:".bright_yellow(),
+                                MpnAddress {
+                                    pub_key: account_tx_builder.get_zk_address(),
+                                }
                             );
-                            continue;
-                        }
-                        println!(
-                            "{}\t{}",
-                            "Address:".bright_yellow(),
-                            MpnAddress { pub_key: acc_pk }
-                        );
-                        for (_, money) in resp.tokens.iter() {
-                            let resp = client
-                                .get_token(money.token_id)
-                                .await
-                                .map(|resp| resp)
-                                .unwrap();
-                            if let Some(inf) = token_balances.get(&money.token_id) {
+                            println!("Waiting to be activated... (Send some funds to it!)")
+                        } else {
+                            let acc_pk = bazuka::crypto::jubjub::PublicKey(resp.address.compress());
+                            if acc_pk != account_tx_builder.get_zk_address() {
                                 println!(
-                                    "{}\t{}{}",
-                                    format!("<{}>:", inf.name).bright_yellow(),
-                                    resp.token
-                                        .as_ref()
-                                        .map(|t| money.amount.display_by_decimals(t.decimals))
-                                        .unwrap_or("N/A".to_string()),
-                                    if money.token_id == TokenId::Ziesha {
-                                        bazuka::config::SYMBOL.to_string()
-                                    } else {
-                                        format!(" {}", inf.symbol)
-                                    }
+                                    "{} {}",
+                                    "Error:".bright_red(),
+                                    "Slot acquired by someone else!"
                                 );
+                                continue;
+                            }
+                            println!(
+                                "{}\t{}",
+                                "Address:".bright_yellow(),
+                                MpnAddress { pub_key: acc_pk }
+                            );
+                            for (_, money) in resp.tokens.iter() {
+                                let resp = client
+                                    .get_token(money.token_id)
+                                    .await
+                                    .map(|resp| resp)
+                                    .unwrap();
+                                if let Some(inf) = token_balances.get(&money.token_id) {
+                                    println!(
+                                        "{}\t{}{}",
+                                        format!("<{}>:", inf.name).bright_yellow(),
+                                        resp.token
+                                            .as_ref()
+                                            .map(|t| money.amount.display_by_decimals(t.decimals))
+                                            .unwrap_or("N/A".to_string()),
+                                        if money.token_id == TokenId::Ziesha {
+                                            bazuka::config::SYMBOL.to_string()
+                                        } else {
+                                            format!(" {}", inf.symbol)
+                                        }
+                                    );
+                                }
                             }
                         }
-                    }
-                    if let Some(nonce) = curr_mpn_tx_nonce {
-                        if nonce > resp.tx_nonce + 1 {
-                            println!("(Pending transactions: {})", nonce - resp.tx_nonce - 1);
+                        if let Some(nonce) = curr_mpn_tx_nonce {
+                            if nonce > resp.tx_nonce + 1 {
+                                println!("(Pending transactions: {})", nonce - resp.tx_nonce - 1);
+                            }
                         }
-                    }
-                    if let Some(nonce) = curr_mpn_withdraw_nonce {
-                        if nonce > resp.withdraw_nonce + 1 {
-                            println!("(Pending withdrawals: {})", nonce - resp.withdraw_nonce - 1);
+                        if let Some(nonce) = curr_mpn_withdraw_nonce {
+                            if nonce > resp.withdraw_nonce + 1 {
+                                println!(
+                                    "(Pending withdrawals: {})",
+                                    nonce - resp.withdraw_nonce - 1
+                                );
+                            }
                         }
+                        println!();
+                    }
+                }
+
+                if accounts.len() > 1 {
+                    println!("{}", "Total (Main-chain)\n=========".bright_blue());
+                    for (id, balance) in total_balances.iter() {
+                        let inf = tokens.get(id).unwrap();
+                        println!(
+                            "{}\t{}{}",
+                            format!("<{}>:", inf.name).bright_yellow(),
+                            balance.display_by_decimals(inf.decimals),
+                            if *id == TokenId::Ziesha {
+                                bazuka::config::SYMBOL.to_string()
+                            } else {
+                                format!(" {} (Token-Id: {})", inf.symbol, id)
+                            }
+                        );
                     }
                     println!();
                 }
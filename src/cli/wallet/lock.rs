@@ -0,0 +1,19 @@
+use std::path::Path;
+
+use bazuka::wallet::{WalletCollection, WalletError};
+
+use super::read_new_passphrase;
+
+pub fn lock(wallet_path: &Path, session_path: &Path) {
+    WalletCollection::end_session(session_path).unwrap();
+    match WalletCollection::open(wallet_path) {
+        Err(WalletError::Locked) => println!("Wallet locked!"),
+        wallet => {
+            let mut wallet = wallet.unwrap().expect(crate::cli::BAZUKA_NOT_INITILIZED);
+            let passphrase = read_new_passphrase().unwrap();
+            wallet.lock(&passphrase).unwrap();
+            wallet.save(wallet_path).unwrap();
+            println!("Wallet encrypted!");
+        }
+    }
+}
@@ -1,17 +1,102 @@
+pub mod account;
 pub mod add_token;
+pub mod allowance;
+pub mod broadcast;
+pub mod change_passphrase;
 pub mod delegate;
 pub mod info;
+pub mod lock;
+pub mod multisig;
 pub mod new_token;
+pub mod passphrase;
+pub mod prepare_send;
 pub mod register_validator;
 pub mod resend_pending;
 pub mod reset;
 pub mod send;
+pub mod sign;
+pub mod unlock;
+pub mod unregister_validator;
+pub mod vesting;
+pub mod watch;
 
+pub use account::*;
 pub use add_token::*;
+pub use allowance::*;
+pub use broadcast::*;
+pub use change_passphrase::*;
 pub use delegate::*;
 pub use info::*;
+pub use lock::*;
+pub use multisig::*;
 pub use new_token::*;
+pub use passphrase::*;
+pub use prepare_send::*;
 pub use register_validator::*;
 pub use resend_pending::*;
 pub use reset::*;
 pub use send::*;
+pub use sign::*;
+pub use unlock::*;
+pub use unregister_validator::*;
+pub use vesting::*;
+pub use watch::*;
+
+use std::path::PathBuf;
+use tokio::try_join;
+
+use crate::cli::{BazukaConfig, CURRENT_NETWORK};
+use bazuka::client::{BazukaClient, Limit, NodeError};
+use bazuka::common::*;
+use bazuka::core::{Decimal, Money, NonceGroup, TokenId, TransactionAndDelta};
+use bazuka::wallet::{TxBuilder, WalletCollection};
+
+/// Builds a transaction of the active account, given the decimals of
+/// `token_id`, the fee and the next nonce, and submits it. The transaction is
+/// kept in the wallet once it is accepted by the node.
+async fn submit_tx<F: FnOnce(&TxBuilder, u8, Money, u32) -> TransactionAndDelta>(
+    token_id: TokenId,
+    fee: Decimal,
+    conf: BazukaConfig,
+    mut wallet: WalletCollection,
+    wallet_path: &PathBuf,
+    build: F,
+) {
+    let tx_builder = wallet.current().tx_builder();
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    try_join!(
+        async move {
+            let tkn_decimals = client
+                .get_token(token_id)
+                .await?
+                .token
+                .expect("Token not found!")
+                .decimals;
+            let curr_nonce = client.get_account(tx_builder.get_address()).await?.nonce;
+            let new_nonce = wallet
+                .current()
+                .new_nonce(NonceGroup::TransactionAndDelta(tx_builder.get_address()))
+                .unwrap_or(curr_nonce + 1);
+            let fee = Money {
+                amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
+                token_id: TokenId::Ziesha,
+            };
+            let tx = build(&tx_builder, tkn_decimals, fee, new_nonce);
+            if let Some(err) = client.transact(tx.clone().into()).await?.error {
+                println!("Error: {}", err);
+            } else {
+                wallet.current().add_tx(tx.into());
+                wallet.save(wallet_path).unwrap();
+                println!("Sent");
+            }
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
@@ -0,0 +1,177 @@
+use std::path::PathBuf;
+use tokio::try_join;
+
+use crate::cli::{BazukaConfig, CURRENT_NETWORK};
+use bazuka::client::{BazukaClient, Limit, NodeError};
+use bazuka::common::*;
+use bazuka::core::{
+    Address, Decimal, Money, MultisigPolicy, NonceGroup, RegularSendEntry, TokenId, TransactionData,
+};
+use bazuka::wallet::{MultisigEnvelope, WalletCollection};
+
+fn policy(threshold: u8, signers: Vec<Address>) -> MultisigPolicy {
+    let policy = MultisigPolicy { threshold, signers };
+    if !policy.validate() {
+        panic!("Invalid multisig policy!");
+    }
+    policy
+}
+
+pub async fn register_multisig(
+    memo: Option<String>,
+    threshold: u8,
+    signers: Vec<Address>,
+    fee: Decimal,
+    conf: BazukaConfig,
+    mut wallet: WalletCollection,
+    wallet_path: &PathBuf,
+) {
+    let policy = policy(threshold, signers);
+    let tx_builder = wallet.current().tx_builder();
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    try_join!(
+        async move {
+            let curr_nonce = client.get_account(tx_builder.get_address()).await?.nonce;
+            let new_nonce = wallet
+                .current()
+                .new_nonce(NonceGroup::TransactionAndDelta(tx_builder.get_address()))
+                .unwrap_or(curr_nonce + 1);
+            let address = policy.address();
+            let tx = tx_builder.register_multisig(
+                memo.unwrap_or_default(),
+                policy,
+                Money {
+                    amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
+                    token_id: TokenId::Ziesha,
+                },
+                new_nonce,
+            );
+            if let Some(err) = client.transact(tx.clone().into()).await?.error {
+                println!("Error: {}", err);
+            } else {
+                wallet.current().add_tx(tx.into());
+                wallet.save(wallet_path).unwrap();
+                println!("Multisig address: {}", address);
+            }
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
+
+/// Creates an envelope for sending funds from a multisig account, which is
+/// then passed to the co-signers
+#[allow(clippy::too_many_arguments)]
+pub async fn propose_multisig(
+    memo: Option<String>,
+    threshold: u8,
+    signers: Vec<Address>,
+    to: Address,
+    amount: Decimal,
+    fee: Decimal,
+    token_id: Option<TokenId>,
+    output: PathBuf,
+    conf: BazukaConfig,
+    mut wallet: WalletCollection,
+) {
+    let policy = policy(threshold, signers);
+    let tx_builder = wallet.current().tx_builder();
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    let tkn = token_id.unwrap_or(TokenId::Ziesha);
+    try_join!(
+        async move {
+            let tkn_decimals = client
+                .get_token(tkn)
+                .await?
+                .token
+                .expect("Token not found!")
+                .decimals;
+            let nonce = client.get_account(policy.address()).await?.nonce + 1;
+            let envelope = MultisigEnvelope::new(
+                policy,
+                memo.unwrap_or_default(),
+                TransactionData::RegularSend {
+                    entries: vec![RegularSendEntry {
+                        dst: to,
+                        amount: Money {
+                            amount: amount.to_amount(tkn_decimals),
+                            token_id: tkn,
+                        },
+                    }],
+                },
+                Money {
+                    amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
+                    token_id: TokenId::Ziesha,
+                },
+                nonce,
+            );
+            envelope.save(&output).unwrap();
+            println!("Envelope written to {}", output.display());
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
+
+pub fn sign_multisig(input: PathBuf, output: Option<PathBuf>, mut wallet: WalletCollection) {
+    let mut envelope = MultisigEnvelope::open(&input).unwrap();
+    println!("{:#?}", envelope.tx.tx);
+    envelope.sign(&wallet.current().tx_builder()).unwrap();
+    envelope.save(output.unwrap_or(input)).unwrap();
+    println!(
+        "Signed! ({}/{} signatures)",
+        envelope.sigs.len(),
+        envelope.policy.threshold
+    );
+}
+
+pub fn combine_multisig(inputs: Vec<PathBuf>, output: PathBuf) {
+    let mut envelopes = inputs
+        .iter()
+        .map(|path| MultisigEnvelope::open(path).unwrap());
+    let mut envelope = envelopes.next().expect("No envelopes given!");
+    for other in envelopes {
+        envelope.merge(other).unwrap();
+    }
+    envelope.save(&output).unwrap();
+    println!(
+        "Combined! ({}/{} signatures)",
+        envelope.sigs.len(),
+        envelope.policy.threshold
+    );
+}
+
+pub async fn broadcast_multisig(input: PathBuf, conf: BazukaConfig, mut wallet: WalletCollection) {
+    let tx = MultisigEnvelope::open(&input).unwrap().finalize().unwrap();
+    let tx_builder = wallet.current().tx_builder();
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    try_join!(
+        async move {
+            if let Some(err) = client.transact(tx.into()).await?.error {
+                println!("Error: {}", err);
+            } else {
+                println!("Sent");
+            }
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use crate::cli::{BazukaConfig, CURRENT_NETWORK};
 use bazuka::client::{BazukaClient, Limit, NodeError};
 use bazuka::common::*;
-use bazuka::core::{Decimal, Money, NonceGroup, TokenId};
+use bazuka::core::{Decimal, Money, NonceGroup, TokenId, TokenMetadata};
 use bazuka::wallet::WalletCollection;
 use tokio::try_join;
 
@@ -14,12 +14,24 @@ pub async fn new_token(
     supply: Decimal,
     decimals: u8,
     mintable: bool,
+    max_supply: Option<Decimal>,
+    metadata_uri: Option<String>,
+    metadata_hash: Option<String>,
     fee: Decimal,
     conf: BazukaConfig,
     mut wallet: WalletCollection,
     wallet_path: &PathBuf,
 ) -> () {
-    let tx_builder = wallet.user(0).tx_builder();
+    let metadata = metadata_uri.map(|uri| TokenMetadata {
+        uri,
+        hash: metadata_hash.map(|h| {
+            hex::decode(h)
+                .ok()
+                .and_then(|h| h.try_into().ok())
+                .expect("Metadata hash should be 32 bytes in hex!")
+        }),
+    });
+    let tx_builder = wallet.current().tx_builder();
     let (req_loop, client) = BazukaClient::connect(
         tx_builder.get_priv_key(),
         conf.random_node(),
@@ -31,7 +43,7 @@ pub async fn new_token(
             let curr_nonce = client.get_account(tx_builder.get_address()).await?.nonce;
 
             let new_nonce = wallet
-                .user(0)
+                .current()
                 .new_nonce(NonceGroup::TransactionAndDelta(tx_builder.get_address()))
                 .unwrap_or(curr_nonce + 1);
             let (pay, token_id) = tx_builder.create_token(
@@ -41,6 +53,8 @@ pub async fn new_token(
                 supply.to_amount(decimals),
                 decimals,
                 mintable.then(|| tx_builder.get_address()),
+                max_supply.map(|s| s.to_amount(decimals)),
+                metadata,
                 Money {
                     amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
                     token_id: TokenId::Ziesha,
@@ -50,8 +64,8 @@ pub async fn new_token(
             if let Some(err) = client.transact(pay.clone().into()).await?.error {
                 println!("Error: {}", err);
             } else {
-                wallet.user(0).add_token(token_id);
-                wallet.user(0).add_tx(pay.clone().into());
+                wallet.current().add_token(token_id);
+                wallet.current().add_tx(pay.clone().into());
                 wallet.save(wallet_path).unwrap();
                 println!("Sent");
                 println!("Token-Id: {}", token_id);
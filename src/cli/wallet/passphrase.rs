@@ -0,0 +1,19 @@
+use bazuka::wallet::WalletError;
+
+/// Environment variable for providing the wallet passphrase non-interactively
+const PASSPHRASE_ENV: &str = "BAZUKA_WALLET_PASSPHRASE";
+
+pub fn read_passphrase() -> Result<String, WalletError> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    Ok(rpassword::prompt_password("Wallet passphrase: ")?)
+}
+
+pub fn read_new_passphrase() -> Result<String, WalletError> {
+    let passphrase = rpassword::prompt_password("New passphrase: ")?;
+    if rpassword::prompt_password("Repeat passphrase: ")? != passphrase {
+        return Err(WalletError::PassphraseMismatch);
+    }
+    Ok(passphrase)
+}
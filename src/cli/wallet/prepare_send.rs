@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+
+use crate::cli::{BazukaConfig, CURRENT_NETWORK};
+use bazuka::wallet::{write_transactions, UnsignedTransaction, WatchOnlyWallet};
+use bazuka::{
+    client::{BazukaClient, Limit, NodeError},
+    common::*,
+    config,
+    core::{Decimal, GeneralAddress, Money, Signer, TokenId},
+    crypto::SignatureScheme,
+};
+use tokio::try_join;
+
+/// Builds an unsigned transaction with a watch-only wallet, to be signed
+/// offline through `bazuka wallet sign`
+#[allow(clippy::too_many_arguments)]
+pub async fn prepare_send(
+    memo: Option<String>,
+    from: GeneralAddress,
+    to: GeneralAddress,
+    amount: Decimal,
+    fee: Decimal,
+    token_id: Option<TokenId>,
+    nonce: Option<u32>,
+    output: PathBuf,
+    conf: BazukaConfig,
+    wallet: WatchOnlyWallet,
+) {
+    let mpn_contract_id = config::blockchain::get_blockchain_config()
+        .mpn_config
+        .mpn_contract_id;
+
+    // The client key is only used for talking with the node
+    let (_, client_key) = Signer::generate_keys(&rand::random::<[u8; 32]>());
+    let (req_loop, client) = BazukaClient::connect(
+        client_key,
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    let tkn = token_id.unwrap_or(TokenId::Ziesha);
+
+    try_join!(
+        async move {
+            let tkn_decimals = client
+                .get_token(tkn)
+                .await?
+                .token
+                .expect("Token not found!")
+                .decimals;
+            let amount = Money {
+                amount: amount.to_amount(tkn_decimals),
+                token_id: tkn,
+            };
+            let fee = Money {
+                amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
+                token_id: TokenId::Ziesha,
+            };
+            let tx: UnsignedTransaction = match (from, to) {
+                (GeneralAddress::ChainAddress(from), GeneralAddress::ChainAddress(to)) => {
+                    if wallet.address != from {
+                        panic!("Source address is not watched by this wallet!");
+                    }
+                    let nonce = match nonce {
+                        Some(nonce) => nonce,
+                        None => client.get_account(from).await?.nonce + 1,
+                    };
+                    wallet
+                        .create_transaction(memo.unwrap_or_default(), to, amount, fee, nonce)
+                        .into()
+                }
+                (GeneralAddress::ChainAddress(from), GeneralAddress::MpnAddress(to)) => {
+                    if wallet.address != from {
+                        panic!("Source address is not watched by this wallet!");
+                    }
+                    let nonce = match nonce {
+                        Some(nonce) => nonce,
+                        None => client.get_account(from).await?.mpn_deposit_nonce + 1,
+                    };
+                    wallet
+                        .deposit_mpn(
+                            memo.unwrap_or_default(),
+                            mpn_contract_id,
+                            to,
+                            nonce,
+                            amount,
+                            fee,
+                        )
+                        .into()
+                }
+                (GeneralAddress::MpnAddress(from), GeneralAddress::MpnAddress(to)) => {
+                    if wallet.mpn_address != from {
+                        panic!("Source address is not watched by this wallet!");
+                    }
+                    if memo.is_some() {
+                        panic!("Cannot assign a memo to a MPN-to-MPN transaction!");
+                    }
+                    let nonce = match nonce {
+                        Some(nonce) => nonce,
+                        None => client.get_mpn_account(from).await?.account.tx_nonce + 1,
+                    };
+                    wallet.create_mpn_transaction(to, amount, fee, nonce).into()
+                }
+                (GeneralAddress::MpnAddress(_), GeneralAddress::ChainAddress(_)) => {
+                    panic!("MPN withdrawals cannot be signed offline!");
+                }
+            };
+            write_transactions(&output, &[tx]).unwrap();
+            println!("Unsigned transaction written to {}", output.display());
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
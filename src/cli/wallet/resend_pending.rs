@@ -12,7 +12,7 @@ async fn resend_all_wallet_txs(
     conf: BazukaConfig,
     wallet: &mut WalletCollection,
 ) -> Result<(), NodeError> {
-    let tx_builder = wallet.user(0).tx_builder();
+    let tx_builder = wallet.current().tx_builder();
     let (req_loop, client) = BazukaClient::connect(
         tx_builder.get_priv_key(),
         conf.random_node(),
@@ -21,7 +21,7 @@ async fn resend_all_wallet_txs(
     );
     try_join!(
         async move {
-            for (_, txs) in wallet.user(0).txs.iter() {
+            for (_, txs) in wallet.current().txs.iter() {
                 for tx in txs {
                     client.transact(tx.clone()).await?;
                 }
@@ -17,11 +17,16 @@ pub async fn send(
     amount: Decimal,
     fee: Decimal,
     token_id: Option<TokenId>,
+    account: Option<usize>,
     conf: BazukaConfig,
     mut wallet: WalletCollection,
     wallet_path: &PathBuf,
 ) {
-    let tx_builder = wallet.user(0).tx_builder();
+    let account = account.unwrap_or(wallet.current_account());
+    if !wallet.accounts().iter().any(|(index, _)| *index == account) {
+        panic!("Account #{} does not exist in your wallet!", account);
+    }
+    let tx_builder = wallet.user(account).tx_builder();
     let mpn_contract_id = config::blockchain::get_blockchain_config()
         .mpn_config
         .mpn_contract_id;
@@ -33,7 +38,7 @@ pub async fn send(
         Some(Limit::default().time(2 * SECOND)),
     );
     let tkn = if let Some(token_id) = token_id {
-        if wallet.user(0).get_tokens().contains(&token_id) {
+        if wallet.user(account).get_tokens().contains(&token_id) {
             panic!("Token does not exist in your wallet!");
         } else {
             token_id
@@ -60,7 +65,7 @@ pub async fn send(
                             let curr_nonce =
                                 client.get_account(tx_builder.get_address()).await?.nonce;
                             let new_nonce = wallet
-                                .user(0)
+                                .user(account)
                                 .new_nonce(NonceGroup::TransactionAndDelta(
                                     tx_builder.get_address(),
                                 ))
@@ -82,7 +87,7 @@ pub async fn send(
                             if let Some(err) = client.transact(tx.clone().into()).await?.error {
                                 println!("Error: {}", err);
                             } else {
-                                wallet.user(0).add_tx(tx.clone().into());
+                                wallet.user(account).add_tx(tx.clone().into());
                                 wallet.save(wallet_path).unwrap();
                                 println!("Sent");
                             }
@@ -93,7 +98,7 @@ pub async fn send(
                                 .await?
                                 .mpn_deposit_nonce;
                             let new_nonce = wallet
-                                .user(0)
+                                .user(account)
                                 .new_nonce(NonceGroup::MpnDeposit(tx_builder.get_address()))
                                 .unwrap_or(curr_nonce + 1);
                             let pay = tx_builder.deposit_mpn(
@@ -110,7 +115,7 @@ pub async fn send(
                                     token_id: TokenId::Ziesha,
                                 },
                             );
-                            wallet.user(0).add_tx(pay.clone().into());
+                            wallet.user(account).add_tx(pay.clone().into());
                             wallet.save(wallet_path).unwrap();
                             println!("{:#?}", client.transact(pay.clone().into()).await?);
                         }
@@ -125,7 +130,7 @@ pub async fn send(
                         GeneralAddress::ChainAddress(to) => {
                             let acc = client.get_mpn_account(from.clone()).await?.account;
                             let new_nonce = wallet
-                                .user(0)
+                                .user(account)
                                 .new_nonce(NonceGroup::MpnWithdraw(tx_builder.get_mpn_address()))
                                 .unwrap_or(acc.withdraw_nonce + 1);
                             let pay = tx_builder.withdraw_mpn(
@@ -142,7 +147,7 @@ pub async fn send(
                                 },
                                 to.to_string().parse().unwrap(), // TODO: WTH :D
                             );
-                            wallet.user(0).add_tx(pay.clone().into());
+                            wallet.user(account).add_tx(pay.clone().into());
                             wallet.save(wallet_path).unwrap();
                             println!("{:#?}", client.transact(pay.clone().into()).await?);
                         }
@@ -153,7 +158,7 @@ pub async fn send(
                             }
                             let acc = client.get_mpn_account(from).await?.account;
                             let new_nonce = wallet
-                                .user(0)
+                                .user(account)
                                 .new_nonce(NonceGroup::MpnTransaction(tx_builder.get_mpn_address()))
                                 .unwrap_or(acc.tx_nonce + 1);
                             let tx = tx_builder.create_mpn_transaction(
@@ -168,7 +173,7 @@ pub async fn send(
                                 },
                                 new_nonce,
                             );
-                            wallet.user(0).add_tx(tx.clone().into());
+                            wallet.user(account).add_tx(tx.clone().into());
                             wallet.save(wallet_path).unwrap();
                             println!("{:#?}", client.transact(tx.clone().into()).await?);
                         }
@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use bazuka::core::GeneralTransaction;
+use bazuka::wallet::{
+    read_transactions, write_transactions, UnsignedTransaction, WalletCollection,
+};
+
+/// Signs transactions prepared by a watch-only wallet, with the active
+/// account. Needs no network access, so it can be run on an air-gapped
+/// machine.
+pub fn sign(input: PathBuf, output: PathBuf, mut wallet: WalletCollection) {
+    let tx_builder = wallet.current().tx_builder();
+    let txs = read_transactions::<UnsignedTransaction, _>(&input).unwrap();
+    let signed = txs
+        .into_iter()
+        .map(|tx| {
+            println!("{:#?}", tx);
+            tx.sign(&tx_builder)
+        })
+        .collect::<Result<Vec<GeneralTransaction>, _>>()
+        .unwrap();
+    write_transactions(&output, &signed).unwrap();
+    println!(
+        "{} transaction(s) signed and written to {}",
+        signed.len(),
+        output.display()
+    );
+}
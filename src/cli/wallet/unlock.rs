@@ -0,0 +1,79 @@
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use bazuka::wallet::{WalletCollection, WalletError};
+use zeroize::Zeroizing;
+
+use super::read_passphrase;
+
+fn try_unlock(wallet_path: &Path, duration: u64, session_path: &Path) -> Result<(), WalletError> {
+    if cfg!(not(unix)) {
+        return Err(WalletError::SessionsUnsupported);
+    }
+    let passphrase = Zeroizing::new(read_passphrase()?);
+    let wallet =
+        WalletCollection::open_with_passphrase(wallet_path, || Ok(passphrase.to_string()))?
+            .expect(crate::cli::BAZUKA_NOT_INITILIZED);
+    if !wallet.is_encrypted() {
+        return Err(WalletError::NotEncrypted);
+    }
+    drop(wallet);
+
+    // The session is served by a background process, which receives the
+    // passphrase through a pipe
+    let mut agent = Command::new(std::env::current_exe()?);
+    agent
+        .args([
+            "wallet",
+            "session-agent",
+            "--duration",
+            &duration.to_string(),
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    // Keeps running after the terminal is closed
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut agent, 0);
+    let mut agent = agent.spawn()?;
+    agent
+        .stdin
+        .take()
+        .expect("Agent stdin is piped!")
+        .write_all(passphrase.as_bytes())?;
+
+    for _ in 0..100 {
+        if WalletCollection::open_with_session(wallet_path, session_path, || {
+            Err(WalletError::Locked)
+        })
+        .is_ok()
+        {
+            return Ok(());
+        }
+        if agent.try_wait()?.is_some() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    Err(WalletError::SessionNotStarted)
+}
+
+pub fn unlock(wallet_path: &Path, duration: u64, session_path: &Path) {
+    match try_unlock(wallet_path, duration, session_path) {
+        Ok(()) => println!("Wallet unlocked for {} seconds!", duration),
+        Err(err) => println!("Error: {}", err),
+    }
+}
+
+/// Serves the session started by `unlock`, the passphrase is read from stdin
+pub fn session_agent(wallet_path: &Path, duration: u64, session_path: &Path) {
+    let mut passphrase = Zeroizing::new(String::new());
+    std::io::stdin().read_to_string(&mut passphrase).unwrap();
+    let wallet = WalletCollection::open_with_passphrase(wallet_path, || Ok(passphrase.to_string()))
+        .unwrap()
+        .expect(crate::cli::BAZUKA_NOT_INITILIZED);
+    drop(passphrase);
+    wallet.serve_session(session_path, duration).unwrap();
+}
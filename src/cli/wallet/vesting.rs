@@ -0,0 +1,182 @@
+use std::path::PathBuf;
+use tokio::try_join;
+
+use crate::cli::{BazukaConfig, CURRENT_NETWORK};
+use bazuka::client::{BazukaClient, Limit, NodeError};
+use bazuka::common::*;
+use bazuka::core::{Address, Decimal, LockTime, Money, NonceGroup, TokenId, VestingId};
+use bazuka::wallet::WalletCollection;
+use colored::Colorize;
+
+fn lock_time(height: Option<u64>, timestamp: Option<u32>) -> Option<LockTime> {
+    match (height, timestamp) {
+        (Some(height), None) => Some(LockTime::Height(height)),
+        (None, Some(timestamp)) => Some(LockTime::Timestamp(timestamp)),
+        (None, None) => None,
+        (Some(_), Some(_)) => panic!("Specify either a height or a timestamp, not both!"),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn create_vesting(
+    memo: Option<String>,
+    to: Address,
+    token_id: Option<TokenId>,
+    amount: Decimal,
+    unlock: (Option<u64>, Option<u32>),
+    linear_until: (Option<u64>, Option<u32>),
+    fee: Decimal,
+    conf: BazukaConfig,
+    mut wallet: WalletCollection,
+    wallet_path: &PathBuf,
+) {
+    let unlock = lock_time(unlock.0, unlock.1).expect("Unlock height or timestamp not given!");
+    let linear_until = lock_time(linear_until.0, linear_until.1);
+    let tkn = token_id.unwrap_or(TokenId::Ziesha);
+    let tx_builder = wallet.current().tx_builder();
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    try_join!(
+        async move {
+            let tkn_decimals = client
+                .get_token(tkn)
+                .await?
+                .token
+                .expect("Token not found!")
+                .decimals;
+            let curr_nonce = client.get_account(tx_builder.get_address()).await?.nonce;
+            let new_nonce = wallet
+                .current()
+                .new_nonce(NonceGroup::TransactionAndDelta(tx_builder.get_address()))
+                .unwrap_or(curr_nonce + 1);
+            let (tx, vesting_id) = tx_builder.create_vesting(
+                memo.unwrap_or_default(),
+                to,
+                Money {
+                    amount: amount.to_amount(tkn_decimals),
+                    token_id: tkn,
+                },
+                unlock,
+                linear_until,
+                Money {
+                    amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
+                    token_id: TokenId::Ziesha,
+                },
+                new_nonce,
+            );
+            if let Some(err) = client.transact(tx.clone().into()).await?.error {
+                println!("Error: {}", err);
+            } else {
+                wallet.current().add_tx(tx.into());
+                wallet.save(wallet_path).unwrap();
+                println!("Vesting-Id: {}", vesting_id);
+            }
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
+
+pub async fn list_vestings(conf: BazukaConfig, mut wallet: WalletCollection) {
+    let tx_builder = wallet.current().tx_builder();
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    try_join!(
+        async move {
+            let vestings = client
+                .get_vestings(tx_builder.get_address())
+                .await?
+                .vestings;
+            if vestings.is_empty() {
+                println!("No vestings!");
+            }
+            for (id, acc) in vestings.iter() {
+                let decimals = client
+                    .get_token(acc.vesting.amount.token_id)
+                    .await?
+                    .token
+                    .map(|t| t.decimals)
+                    .unwrap_or_default();
+                println!();
+                println!("{}\t{}", "Vesting-Id:".bright_yellow(), id);
+                println!(
+                    "{}\t{} (Token-Id: {})",
+                    "Locked:".bright_yellow(),
+                    acc.locked().display_by_decimals(decimals),
+                    acc.vesting.amount.token_id
+                );
+                println!(
+                    "{}\t{}",
+                    "Claimed:".bright_yellow(),
+                    acc.claimed.display_by_decimals(decimals)
+                );
+                match acc.vesting.linear_until {
+                    Some(until) => println!(
+                        "{}\tLinearly from {} until {}",
+                        "Release:".bright_yellow(),
+                        acc.vesting.unlock,
+                        until
+                    ),
+                    None => println!("{}\tAt {}", "Release:".bright_yellow(), acc.vesting.unlock),
+                }
+            }
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
+
+pub async fn claim_vesting(
+    memo: Option<String>,
+    vesting_id: VestingId,
+    fee: Decimal,
+    conf: BazukaConfig,
+    mut wallet: WalletCollection,
+    wallet_path: &PathBuf,
+) {
+    let tx_builder = wallet.current().tx_builder();
+    let (req_loop, client) = BazukaClient::connect(
+        tx_builder.get_priv_key(),
+        conf.random_node(),
+        CURRENT_NETWORK.into(),
+        Some(Limit::default().time(2 * SECOND)),
+    );
+    try_join!(
+        async move {
+            let curr_nonce = client.get_account(tx_builder.get_address()).await?.nonce;
+            let new_nonce = wallet
+                .current()
+                .new_nonce(NonceGroup::TransactionAndDelta(tx_builder.get_address()))
+                .unwrap_or(curr_nonce + 1);
+            let tx = tx_builder.claim_vesting(
+                memo.unwrap_or_default(),
+                vesting_id,
+                Money {
+                    amount: fee.to_amount(bazuka::config::UNIT_ZEROS),
+                    token_id: TokenId::Ziesha,
+                },
+                new_nonce,
+            );
+            if let Some(err) = client.transact(tx.clone().into()).await?.error {
+                println!("Error: {}", err);
+            } else {
+                wallet.current().add_tx(tx.into());
+                wallet.save(wallet_path).unwrap();
+                println!("Sent");
+            }
+            Ok::<(), NodeError>(())
+        },
+        req_loop
+    )
+    .unwrap();
+}
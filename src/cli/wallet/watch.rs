@@ -0,0 +1,11 @@
+use std::path::PathBuf;
+
+use bazuka::core::{Address, MpnAddress};
+use bazuka::wallet::WatchOnlyWallet;
+
+pub fn watch(address: Address, mpn_address: MpnAddress, watch_only_path: &PathBuf) {
+    WatchOnlyWallet::new(address, mpn_address)
+        .save(watch_only_path)
+        .unwrap();
+    println!("Watch-only wallet created!");
+}
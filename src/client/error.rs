@@ -60,10 +60,14 @@ pub enum NodeError {
     HandshakeClientMismatch,
     #[error("remote server error: {0}")]
     RemoteServerError(String),
+    #[error("rpc error {0}: {1}")]
+    RpcError(i64, String),
     #[error("block timestamp is way higher than current network timestamp")]
     BlockTimestampInFuture,
     #[error("your validator is not exposed on the internet")]
     ValidatorNotExposed,
     #[error("request sender's ip address is unknown")]
     SenderIpUnknown,
+    #[error("light-client error: {0}")]
+    LightClientError(#[from] super::light::LightClientError),
 }
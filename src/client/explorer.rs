@@ -1,7 +1,7 @@
 use crate::core::{
-    Address, Amount, Block, ContractDeposit, ContractUpdate, ContractWithdraw, GeneralTransaction,
-    Header, Money, MpnDeposit, MpnWithdraw, ProofOfStake, Token, TokenUpdate, Transaction,
-    TransactionData,
+    Block, ContractDeposit, ContractUpdate, ContractWithdraw, GeneralTransaction, Header, LockTime,
+    Money, MpnDeposit, MpnWithdraw, ProofOfStake, Token, TokenMetadata, TokenUpdate, Transaction,
+    TransactionData, Vesting,
 };
 use crate::crypto::jubjub::*;
 use crate::zk::{
@@ -55,6 +55,8 @@ pub struct ExplorerToken {
     pub symbol: String,
     pub supply: u64,
     pub minter: Option<String>,
+    pub max_supply: Option<u64>,
+    pub metadata: Option<ExplorerTokenMetadata>,
 }
 
 impl From<&Token> for ExplorerToken {
@@ -64,6 +66,23 @@ impl From<&Token> for ExplorerToken {
             symbol: obj.symbol.clone(),
             supply: obj.supply.into(),
             minter: obj.minter.as_ref().map(|a| a.to_string()),
+            max_supply: obj.max_supply.map(|s| s.into()),
+            metadata: obj.metadata.as_ref().map(|m| m.into()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ExplorerTokenMetadata {
+    pub uri: String,
+    pub hash: Option<String>,
+}
+
+impl From<&TokenMetadata> for ExplorerTokenMetadata {
+    fn from(obj: &TokenMetadata) -> Self {
+        Self {
+            uri: obj.uri.clone(),
+            hash: obj.hash.map(hex::encode),
         }
     }
 }
@@ -253,6 +272,8 @@ impl From<&ZkProof> for ExplorerZkProof {
 pub enum ExplorerTokenUpdate {
     Mint { amount: u64 },
     ChangeMinter { minter: String },
+    Burn { amount: u64 },
+    RenounceMinter,
 }
 
 impl From<&TokenUpdate> for ExplorerTokenUpdate {
@@ -264,6 +285,10 @@ impl From<&TokenUpdate> for ExplorerTokenUpdate {
             TokenUpdate::ChangeMinter { minter } => Self::ChangeMinter {
                 minter: minter.to_string(),
             },
+            TokenUpdate::Burn { amount } => Self::Burn {
+                amount: (*amount).into(),
+            },
+            TokenUpdate::RenounceMinter => Self::RenounceMinter,
         }
     }
 }
@@ -331,6 +356,25 @@ impl From<&ContractUpdate> for ExplorerContractUpdate {
     }
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ExplorerVesting {
+    pub beneficiary: String,
+    pub amount: ExplorerMoney,
+    pub unlock: LockTime,
+    pub linear_until: Option<LockTime>,
+}
+
+impl From<&Vesting> for ExplorerVesting {
+    fn from(obj: &Vesting) -> Self {
+        Self {
+            beneficiary: obj.beneficiary.to_string(),
+            amount: obj.amount.into(),
+            unlock: obj.unlock,
+            linear_until: obj.linear_until,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(tag = "type")]
 pub enum ExplorerTransactionData {
@@ -360,6 +404,33 @@ pub enum ExplorerTransactionData {
         token_id: String,
         update: ExplorerTokenUpdate,
     },
+    RegisterMultisig {
+        threshold: u8,
+        signers: Vec<String>,
+    },
+    CreateVesting {
+        vesting: ExplorerVesting,
+    },
+    ClaimVesting {
+        vesting_id: String,
+    },
+    ReclaimDelegate {
+        from: String,
+    },
+    SlashValidator {
+        first: ExplorerHeader,
+        second: ExplorerHeader,
+    },
+    UnregisterStaker,
+    Approve {
+        spender: String,
+        token_id: String,
+        amount: u64,
+    },
+    TransferFrom {
+        owner: String,
+        entries: Vec<(String, ExplorerMoney)>,
+    },
 }
 
 impl From<&TransactionData> for ExplorerTransactionData {
@@ -404,6 +475,40 @@ impl From<&TransactionData> for ExplorerTransactionData {
                 token_id: token_id.to_string(),
                 update: update.into(),
             },
+            TransactionData::RegisterMultisig { policy } => Self::RegisterMultisig {
+                threshold: policy.threshold,
+                signers: policy.signers.iter().map(|s| s.to_string()).collect(),
+            },
+            TransactionData::CreateVesting { vesting } => Self::CreateVesting {
+                vesting: vesting.into(),
+            },
+            TransactionData::ClaimVesting { vesting_id } => Self::ClaimVesting {
+                vesting_id: vesting_id.to_string(),
+            },
+            TransactionData::ReclaimDelegate { from } => Self::ReclaimDelegate {
+                from: from.to_string(),
+            },
+            TransactionData::SlashValidator { first, second } => Self::SlashValidator {
+                first: first.as_ref().into(),
+                second: second.as_ref().into(),
+            },
+            TransactionData::UnregisterStaker => Self::UnregisterStaker,
+            TransactionData::Approve {
+                spender,
+                token_id,
+                amount,
+            } => Self::Approve {
+                spender: spender.to_string(),
+                token_id: token_id.to_string(),
+                amount: (*amount).into(),
+            },
+            TransactionData::TransferFrom { owner, entries } => Self::TransferFrom {
+                owner: owner.to_string(),
+                entries: entries
+                    .iter()
+                    .map(|e| (e.dst.to_string(), e.amount.into()))
+                    .collect(),
+            },
         }
     }
 }
@@ -448,17 +553,11 @@ impl From<&Block> for ExplorerBlock {
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ExplorerStaker {
-    pub_key: String,
-    stake: u64,
-}
-
-impl From<&(Address, Amount)> for ExplorerStaker {
-    fn from(obj: &(Address, Amount)) -> Self {
-        Self {
-            pub_key: obj.0.to_string(),
-            stake: obj.1.into(),
-        }
-    }
+    pub pub_key: String,
+    pub stake: u64,
+    pub commision: u8,
+    /// Estimated yearly return of delegating to this staker
+    pub apy: f64,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
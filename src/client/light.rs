@@ -0,0 +1,490 @@
+use super::messages::TransactionProof;
+use super::{BazukaClient, NodeError};
+use crate::blockchain::{Blockchain, BlockchainConfig, BlockchainError};
+use crate::core::{hash::Hash, EpochStaker, Hasher, Header, ValidatorProof, Vrf};
+use crate::crypto::merkle::MerkleTree;
+use crate::crypto::VerifiableRandomFunction;
+use crate::db::KvStore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LightClientError {
+    #[error("header number is not consecutive")]
+    InvalidBlockNumber,
+    #[error("header is not pointing to its parent")]
+    InvalidParentHash,
+    #[error("header timestamp is before its parent")]
+    InvalidTimestamp,
+    #[error("header is not signed by its validator")]
+    InvalidSignature,
+    #[error("header validator is not proven")]
+    UnelectedValidator,
+    #[error("stakers of epoch {0} are not known")]
+    UnknownEpochStakers(u32),
+    #[error("fork is deeper than the maximum reorg depth")]
+    ReorgTooDeep,
+    #[error("fork is not heavier than the current chain")]
+    WeakerFork,
+}
+
+/// A header that is trusted, and the stakers of its epoch, used for verifying
+/// the validator proofs of the headers after it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LightCheckpoint {
+    pub header: Header,
+    pub stakers: Vec<EpochStaker>,
+}
+
+impl LightCheckpoint {
+    /// Builds a checkpoint from the tip of a chain that is trusted
+    pub fn from_chain<K: KvStore, B: Blockchain<K>>(chain: &B) -> Result<Self, BlockchainError> {
+        let header = chain.get_tip()?;
+        let (epoch, _) = chain.epoch_slot(header.proof_of_stake.timestamp);
+        Ok(Self {
+            stakers: chain.get_epoch_stakers(epoch)?,
+            header,
+        })
+    }
+}
+
+/// Follows the chain by keeping only the headers. Block bodies are never
+/// downloaded, transactions are confirmed through their merkle proofs.
+///
+/// Validators are elected based on the stakes fixed at the beginning of each
+/// epoch. Only the stakers of the checkpoint epoch are trusted, the stakers of
+/// the later epochs are taken from the node which the headers are synced from,
+/// since headers do not commit to the state. Re-anchor on a new trusted
+/// checkpoint in order to not depend on the node.
+pub struct LightClient {
+    config: BlockchainConfig,
+    epochs: HashMap<u32, Vec<EpochStaker>>,
+    headers: Vec<Header>,
+}
+
+impl LightClient {
+    pub fn new(config: BlockchainConfig, checkpoint: LightCheckpoint) -> Self {
+        let mut client = Self {
+            config,
+            epochs: HashMap::new(),
+            headers: vec![checkpoint.header],
+        };
+        let (epoch, _) = client.epoch_slot(client.tip().proof_of_stake.timestamp);
+        client.epochs.insert(epoch, checkpoint.stakers);
+        client
+    }
+
+    pub fn tip(&self) -> &Header {
+        self.headers.last().unwrap()
+    }
+
+    pub fn get_header(&self, number: u64) -> Option<&Header> {
+        let first = self.headers.first().unwrap().number;
+        if number < first {
+            return None;
+        }
+        self.headers.get((number - first) as usize)
+    }
+
+    /// Sets the stakers which the headers of the given epoch are verified
+    /// against
+    pub fn add_epoch_stakers(&mut self, epoch: u32, stakers: Vec<EpochStaker>) {
+        self.epochs.insert(epoch, stakers);
+    }
+
+    fn epoch_slot(&self, timestamp: u32) -> (u32, u32) {
+        let slot_number =
+            timestamp.saturating_sub(self.config.chain_start_timestamp) / self.config.slot_duration;
+        (
+            slot_number / self.config.slot_per_epoch,
+            slot_number % self.config.slot_per_epoch,
+        )
+    }
+
+    /// Stake of the validator of the header, if it has been elected
+    fn elected_stake(&self, header: &Header) -> Result<Option<u64>, LightClientError> {
+        let pos = &header.proof_of_stake;
+        let (epoch, slot) = self.epoch_slot(pos.timestamp);
+        let stakers = self
+            .epochs
+            .get(&epoch)
+            .ok_or(LightClientError::UnknownEpochStakers(epoch))?;
+        let sum_stakes = stakers.iter().map(|s| u64::from(s.stake)).sum::<u64>();
+        if let Some(staker) = stakers.iter().find(|s| s.address == pos.validator) {
+            let chance = (u64::from(staker.stake) as f64 / sum_stakes as f64) as f32;
+            if let ValidatorProof::Proof {
+                vrf_output,
+                vrf_proof,
+            } = &pos.proof
+            {
+                if Into::<f32>::into(vrf_output.clone()) <= chance
+                    && Vrf::verify(
+                        &staker.vrf_pub_key,
+                        format!("{}-{}", epoch, slot).as_bytes(),
+                        vrf_output,
+                        vrf_proof,
+                    )
+                {
+                    return Ok(Some(staker.stake.into()));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Same fork-choice rule as the full nodes: the fork with more accumulated
+    /// stake weight wins, the longer one wins when both are equally heavy.
+    fn fork_weight(&self, headers: &[Header]) -> Result<u128, LightClientError> {
+        let mut weight = 0u128;
+        for h in headers.iter() {
+            weight += self.elected_stake(h)?.unwrap_or_default() as u128;
+        }
+        Ok(weight)
+    }
+
+    /// Appends headers after the header their first one is pointing to, after
+    /// checking they are properly chained and produced by elected validators.
+    /// Headers replace the current ones after the fork point when they are
+    /// heavier, as long as the fork is not deeper than the maximum reorg depth.
+    pub fn extend(&mut self, headers: &[Header]) -> Result<(), LightClientError> {
+        let first = match headers.first() {
+            Some(h) => h.number,
+            None => {
+                return Ok(());
+            }
+        };
+        let checkpoint = self.headers.first().unwrap().number;
+        let height = self.tip().number + 1;
+        if first > height {
+            return Err(LightClientError::InvalidBlockNumber);
+        }
+        if first <= checkpoint || height - first > self.config.max_reorg_depth {
+            return Err(LightClientError::ReorgTooDeep);
+        }
+
+        let mut last_header = self.get_header(first - 1).unwrap().clone();
+        for h in headers.iter() {
+            if h.number != last_header.number + 1 {
+                return Err(LightClientError::InvalidBlockNumber);
+            }
+            if h.parent_hash != last_header.hash() {
+                return Err(LightClientError::InvalidParentHash);
+            }
+            if h.proof_of_stake.timestamp < last_header.proof_of_stake.timestamp {
+                return Err(LightClientError::InvalidTimestamp);
+            }
+            if !h.verify_signature() {
+                return Err(LightClientError::InvalidSignature);
+            }
+            if self.config.check_validator && self.elected_stake(h)?.is_none() {
+                return Err(LightClientError::UnelectedValidator);
+            }
+            last_header = h.clone();
+        }
+
+        // Skip the headers that are already synced
+        let known = headers
+            .iter()
+            .take_while(|h| self.get_header(h.number) == Some(h))
+            .count();
+        let headers = &headers[known..];
+        if headers.is_empty() {
+            return Ok(());
+        }
+        let fork_index = (headers[0].number - checkpoint) as usize;
+        if fork_index < self.headers.len() {
+            let local_headers = &self.headers[fork_index..];
+            let local_weight = self.fork_weight(local_headers)?;
+            let fork_weight = self.fork_weight(headers)?;
+            if fork_weight < local_weight
+                || (fork_weight == local_weight && headers.len() <= local_headers.len())
+            {
+                return Err(LightClientError::WeakerFork);
+            }
+            self.headers.truncate(fork_index);
+        }
+        self.headers.extend(headers.iter().cloned());
+        Ok(())
+    }
+
+    /// Extends the headers, fetching the stakers of the epochs they reach
+    async fn extend_from(
+        &mut self,
+        client: &BazukaClient,
+        headers: &[Header],
+    ) -> Result<(), NodeError> {
+        loop {
+            match self.extend(headers) {
+                Err(LightClientError::UnknownEpochStakers(epoch)) => {
+                    let stakers = client.get_epoch_stakers(epoch).await?.stakers;
+                    self.add_epoch_stakers(epoch, stakers);
+                }
+                res => {
+                    return Ok(res?);
+                }
+            }
+        }
+    }
+
+    /// Fetches and verifies new headers from the node until reaching its tip.
+    /// Follows the reorgs of the node that are not deeper than the maximum
+    /// reorg depth. Returns the new height.
+    pub async fn sync(&mut self, client: &BazukaClient, batch_size: u64) -> Result<u64, NodeError> {
+        loop {
+            let height = self.tip().number + 1;
+            let headers = client.get_headers(height, batch_size).await?.headers;
+            if headers.is_empty() {
+                return Ok(height);
+            }
+            if headers[0].parent_hash != self.tip().hash() {
+                // The node has switched to another fork, find where it starts
+                let checkpoint = self.headers.first().unwrap().number;
+                let since = std::cmp::max(
+                    height.saturating_sub(self.config.max_reorg_depth),
+                    checkpoint + 1,
+                );
+                let fork = client
+                    .get_headers(since, height - since + batch_size)
+                    .await?
+                    .headers;
+                // Stakers of the epochs after the fork point may be different
+                let (fork_epoch, _) =
+                    self.epoch_slot(self.get_header(since - 1).unwrap().proof_of_stake.timestamp);
+                self.epochs.retain(|epoch, _| *epoch <= fork_epoch);
+                self.extend_from(client, &fork).await?;
+            } else {
+                self.extend_from(client, &headers).await?;
+            }
+        }
+    }
+
+    /// Checks that the transaction is included in a synced block. Returns the
+    /// number of confirmations of the transaction.
+    pub fn verify_transaction(&self, proof: &TransactionProof) -> Option<u64> {
+        let header = self.get_header(proof.height)?;
+        MerkleTree::<Hasher>::verify(proof.tx.hash(), &proof.proof, header.block_root)
+            .then(|| self.tip().number - proof.height)
+    }
+
+    /// Fetches the inclusion proof of a transaction and verifies it. Returns
+    /// the number of confirmations, or `None` if the transaction is not
+    /// confirmed on the synced headers. Proofs are built from the block
+    /// bodies, so nodes with pruning enabled fail with `BlockPruned` for the
+    /// transactions older than their pruning window.
+    pub async fn confirm_transaction(
+        &self,
+        client: &BazukaClient,
+        tx_hash: <Hasher as Hash>::Output,
+    ) -> Result<Option<u64>, NodeError> {
+        Ok(client
+            .get_transaction_proof(tx_hash)
+            .await?
+            .proof
+            .and_then(|proof| self.verify_transaction(&proof)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::KvStoreChain;
+    use crate::config::blockchain::get_test_blockchain_config;
+    use crate::core::{Amount, Block, Money};
+    use crate::db::RamKvStore;
+    use crate::wallet::TxBuilder;
+
+    /// Drafts a block whose validator is not elected, so that it does not
+    /// weigh in the fork-choice
+    fn draft_unproven(
+        chain: &KvStoreChain<RamKvStore>,
+        timestamp: &mut u32,
+        miner: &TxBuilder,
+    ) -> Block {
+        loop {
+            *timestamp += 1;
+            let blk = chain
+                .draft_block(*timestamp, &[], miner, true)
+                .unwrap()
+                .unwrap()
+                .block;
+            if matches!(blk.header.proof_of_stake.proof, ValidatorProof::Unproven) {
+                return blk;
+            }
+        }
+    }
+
+    #[test]
+    fn test_light_client() {
+        let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+        let alice = TxBuilder::new(&Vec::from("ABC"));
+        let mut chain = KvStoreChain::new(RamKvStore::new(), get_test_blockchain_config()).unwrap();
+        let mut light = LightClient::new(
+            get_test_blockchain_config(),
+            LightCheckpoint::from_chain(&chain).unwrap(),
+        );
+
+        let mut txs = Vec::new();
+        for i in 1..=3 {
+            let tx = alice.create_transaction(
+                "".into(),
+                miner.get_address(),
+                Money::ziesha(100),
+                Money::ziesha(0),
+                i,
+            );
+            let blk = chain
+                .draft_block(i, &[tx.clone()], &miner, true)
+                .unwrap()
+                .unwrap()
+                .block;
+            chain.extend(i as u64, &[blk]).unwrap();
+            txs.push(tx.tx);
+        }
+
+        let headers = chain.get_headers(1, 3).unwrap();
+        assert!(matches!(
+            light.extend(&headers[1..]),
+            Err(LightClientError::InvalidBlockNumber)
+        ));
+        let mut wrong_parent = headers.clone();
+        wrong_parent[1].parent_hash = Default::default();
+        assert!(matches!(
+            light.extend(&wrong_parent),
+            Err(LightClientError::InvalidParentHash)
+        ));
+        assert_eq!(light.tip().number, 0);
+
+        light.extend(&headers).unwrap();
+        assert_eq!(light.tip(), &chain.get_tip().unwrap());
+
+        let proven = chain.prove_transaction(txs[0].hash()).unwrap().unwrap();
+        let mut tx_proof = TransactionProof {
+            height: proven.height,
+            tx: proven.tx,
+            proof: proven.proof,
+        };
+        assert_eq!(light.verify_transaction(&tx_proof), Some(2));
+
+        // Proof is not valid against other blocks
+        tx_proof.height = 2;
+        assert_eq!(light.verify_transaction(&tx_proof), None);
+        tx_proof.height = 10;
+        assert_eq!(light.verify_transaction(&tx_proof), None);
+        tx_proof.height = 1;
+        tx_proof.tx = txs[1].clone();
+        assert_eq!(light.verify_transaction(&tx_proof), None);
+
+        assert!(chain
+            .prove_transaction(Default::default())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_light_client_elections() {
+        let validators =
+            ["VALIDATOR", "VALIDATOR2", "VALIDATOR3"].map(|v| TxBuilder::new(&Vec::from(v)));
+        let alice = TxBuilder::new(&Vec::from("ABC"));
+        let mut conf = get_test_blockchain_config();
+        conf.check_validator = true;
+        let mut chain = KvStoreChain::new(RamKvStore::new(), conf.clone()).unwrap();
+        let mut light = LightClient::new(conf, LightCheckpoint::from_chain(&chain).unwrap());
+
+        // Stakes of the next epoch are changed by a delegation
+        let mut mempool = vec![alice.delegate(
+            "".into(),
+            validators[0].get_address(),
+            Amount(5000),
+            false,
+            Money::ziesha(0),
+            1,
+        )];
+        let mut timestamp = 0;
+        while chain.epoch_slot(chain.get_tip().unwrap().proof_of_stake.timestamp) < (1, 3) {
+            timestamp += chain.config().slot_duration;
+            for validator in validators.iter() {
+                if let Some(draft) = chain
+                    .draft_block(timestamp, &mempool, validator, true)
+                    .unwrap()
+                {
+                    let height = chain.get_height().unwrap();
+                    chain.extend(height, &[draft.block]).unwrap();
+                    mempool.clear();
+                    break;
+                }
+            }
+        }
+        let headers = chain
+            .get_headers(1, chain.get_height().unwrap() - 1)
+            .unwrap();
+        assert_ne!(
+            chain.get_epoch_stakers(0).unwrap(),
+            chain.get_epoch_stakers(1).unwrap()
+        );
+
+        // Headers signed by validators that are not elected are rejected
+        let mut forged = headers[0].clone();
+        forged.proof_of_stake.validator = alice.get_address();
+        alice.sign_header(&mut forged);
+        assert!(matches!(
+            light.extend(&[forged]),
+            Err(LightClientError::UnelectedValidator)
+        ));
+
+        assert!(matches!(
+            light.extend(&headers),
+            Err(LightClientError::UnknownEpochStakers(1))
+        ));
+        assert_eq!(light.tip().number, 0);
+        light.add_epoch_stakers(1, chain.get_epoch_stakers(1).unwrap());
+        light.extend(&headers).unwrap();
+        assert_eq!(light.tip(), &chain.get_tip().unwrap());
+    }
+
+    #[test]
+    fn test_light_client_reorg() {
+        let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+        let miner_b = TxBuilder::new(&Vec::from("VALIDATOR2"));
+        let mut conf = get_test_blockchain_config();
+        conf.max_reorg_depth = 3;
+        let mut chain_a = KvStoreChain::new(RamKvStore::new(), conf.clone()).unwrap();
+        let mut chain_b = KvStoreChain::new(RamKvStore::new(), conf.clone()).unwrap();
+        let mut light = LightClient::new(conf, LightCheckpoint::from_chain(&chain_a).unwrap());
+
+        let mut timestamp = 0;
+        let common = draft_unproven(&chain_a, &mut timestamp, &miner);
+        chain_a.extend(1, &[common.clone()]).unwrap();
+        chain_b.extend(1, &[common]).unwrap();
+        let mut timestamp_b = timestamp;
+        for _ in 0..2 {
+            let blk = draft_unproven(&chain_a, &mut timestamp, &miner);
+            chain_a.extend(blk.header.number, &[blk]).unwrap();
+        }
+        for _ in 0..3 {
+            let blk = draft_unproven(&chain_b, &mut timestamp_b, &miner_b);
+            chain_b.extend(blk.header.number, &[blk]).unwrap();
+        }
+        let headers_a = chain_a.get_headers(1, 3).unwrap();
+        let headers_b = chain_b.get_headers(1, 4).unwrap();
+        light.extend(&headers_a).unwrap();
+
+        // Forks are chosen the same way as the full nodes
+        assert!(!chain_a.will_extend(2, &headers_b[1..3]).unwrap());
+        assert!(matches!(
+            light.extend(&headers_b[1..3]),
+            Err(LightClientError::WeakerFork)
+        ));
+        assert_eq!(light.tip(), headers_a.last().unwrap());
+
+        assert!(chain_a.will_extend(2, &headers_b[1..]).unwrap());
+        light.extend(&headers_b).unwrap();
+        assert_eq!(light.tip(), headers_b.last().unwrap());
+        assert_eq!(light.get_header(2), Some(&headers_b[1]));
+
+        assert!(matches!(
+            light.extend(&headers_a),
+            Err(LightClientError::ReorgTooDeep)
+        ));
+    }
+}
@@ -1,7 +1,8 @@
-use crate::blockchain::{TimestampCommit, TransactionStats, ZkBlockchainPatch};
+use crate::blockchain::{TimestampCommit, TransactionReceipt, TransactionStats, ZkBlockchainPatch};
 use crate::core::{
-    Address, Amount, Block, ContractId, GeneralAddress, GeneralTransaction, Header, Money,
-    MpnAddress, Signature, Token, TransactionAndDelta, ValidatorProof,
+    hash::Hash, Address, Amount, Block, ContractAccount, ContractId, DelegatorReward, EpochStaker,
+    GeneralAddress, GeneralTransaction, Hasher, Header, Money, MpnAddress, Signature, Token,
+    Transaction, TransactionAndDelta, ValidatorProof, ValidatorReward, VestingAccount,
 };
 use crate::mpn::MpnWork;
 use crate::zk;
@@ -9,7 +10,10 @@ use std::collections::HashMap;
 use thiserror::Error;
 
 use super::{
-    explorer::{ExplorerBlock, ExplorerGeneralTransaction, ExplorerMpnAccount, ExplorerStaker},
+    explorer::{
+        ExplorerBlock, ExplorerGeneralTransaction, ExplorerMpnAccount, ExplorerStaker,
+        ExplorerTransaction,
+    },
     Peer, PeerAddress,
 };
 use serde::{Deserialize, Serialize};
@@ -27,6 +31,7 @@ pub struct GetStatsResponse {
     pub social_profiles: SocialProfiles,
     pub address: String,
     pub height: u64,
+    pub pruned_height: u64,
     pub nodes: usize,
     pub next_reward: Amount,
     pub timestamp: u32,
@@ -61,6 +66,27 @@ pub struct GetDelegationsResponse {
     pub delegatees: HashMap<String, Amount>,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetVestingsRequest {
+    pub address: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetVestingsResponse {
+    pub vestings: HashMap<String, VestingAccount>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetStakingRewardsRequest {
+    pub address: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetStakingRewardsResponse {
+    pub validator_rewards: Vec<ValidatorReward>,
+    pub delegator_rewards: Vec<DelegatorReward>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct GetMpnAccountRequest {
     pub address: String,
@@ -141,6 +167,8 @@ pub struct GetExplorerStakersRequest {}
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct GetExplorerStakersResponse {
     pub current: Vec<ExplorerStaker>,
+    /// Estimated yearly return of staked Ziesha, before commisions
+    pub apy: f64,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -342,6 +370,49 @@ pub struct GetTokenInfoResponse {
     pub token: Option<Token>,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetTransactionProofRequest {
+    pub tx_hash: <Hasher as Hash>::Output,
+}
+
+/// Proof of inclusion of a transaction in the block at `height`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TransactionProof {
+    pub height: u64,
+    pub tx: Transaction,
+    pub proof: Vec<<Hasher as Hash>::Output>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetTransactionProofResponse {
+    pub proof: Option<TransactionProof>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetEpochStakersRequest {
+    pub epoch: u32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetEpochStakersResponse {
+    pub stakers: Vec<EpochStaker>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetContractStateRequest {
+    pub contract_id: String,
+    pub locator: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetContractStateResponse {
+    pub value: zk::ZkScalar,
+    pub proof: zk::ZkStateProof,
+    pub state_model: zk::ZkStateModel,
+    pub account: ContractAccount,
+    pub height: u64,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ValidatorClaim {
     pub timestamp: u32,
@@ -362,6 +433,7 @@ impl ValidatorClaim {
                 let bytes = bincode::serialize(&unsigned).unwrap();
                 crate::core::Signer::verify(&self.address, &bytes, sig)
             }
+            Signature::MultiSigned(_) => false,
         }
     }
 }
@@ -429,3 +501,165 @@ pub struct CheckTransactionRequest {
 pub struct CheckTransactionResponse {
     pub error: Option<String>,
 }
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetHistoryRequest {
+    pub address: String,
+    pub page: usize,
+    pub page_size: usize,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct HistoryEntry {
+    pub height: u64,
+    pub index: u32,
+    pub tx_hash: String,
+    pub receipt: TransactionReceipt,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetHistoryResponse {
+    /// Blocks older than this were applied before the history was enabled
+    pub indexed_since: u64,
+    pub history: Vec<HistoryEntry>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetTransactionRequest {
+    pub tx_hash: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum TransactionStatus {
+    Confirmed {
+        height: u64,
+        index: u32,
+        block_hash: String,
+        timestamp: u32,
+        confirmations: u64,
+    },
+    Pending {
+        stats: TransactionStats,
+    },
+    Rejected {
+        stats: TransactionStats,
+    },
+    NotFound,
+}
+
+/// `tx` is `None` when the transaction is not found, or when it's confirmed
+/// in a block whose body is pruned by the node. The status of a confirmed
+/// transaction is still returned in the latter case.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetTransactionResponse {
+    pub status: TransactionStatus,
+    pub tx: Option<ExplorerTransaction>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventTopic {
+    Block,
+    Transaction,
+    ValidatorClaim,
+    MpnWorkPool,
+}
+
+impl std::str::FromStr for EventTopic {
+    type Err = InputError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "block" => Ok(Self::Block),
+            "tx" => Ok(Self::Transaction),
+            "claim" => Ok(Self::ValidatorClaim),
+            "mpn" => Ok(Self::MpnWorkPool),
+            _ => Err(InputError::Invalid),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum NodeEvent {
+    /// A new block was added on top of the chain
+    NewBlock(ExplorerBlock),
+    /// Blocks at and after the given height are no longer on the chain
+    Rollback {
+        height: u64,
+    },
+    /// A transaction was admitted into the mempool
+    NewTransaction(ExplorerGeneralTransaction),
+    ValidatorClaim(ValidatorClaim),
+    /// MPN work pool was prepared, solved or dropped (`works == 0`)
+    MpnWorkPool {
+        works: usize,
+        remaining: usize,
+    },
+}
+
+impl NodeEvent {
+    pub fn topic(&self) -> EventTopic {
+        match self {
+            NodeEvent::NewBlock(_) | NodeEvent::Rollback { .. } => EventTopic::Block,
+            NodeEvent::NewTransaction(_) => EventTopic::Transaction,
+            NodeEvent::ValidatorClaim(_) => EventTopic::ValidatorClaim,
+            NodeEvent::MpnWorkPool { .. } => EventTopic::MpnWorkPool,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct IndexedNodeEvent {
+    pub id: u64,
+    pub event: NodeEvent,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetEventsRequest {
+    /// Only events with ids greater than or equal to this are returned
+    pub since: u64,
+    /// Seconds to wait for new events when there are none
+    pub timeout: Option<u32>,
+    /// Comma separated list of topics (block, tx, claim, mpn)
+    pub topics: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct GetEventsResponse {
+    pub events: Vec<IndexedNodeEvent>,
+    /// Value of `since` for the next request
+    pub next: u64,
+}
+
+pub const RPC_PARSE_ERROR: i64 = -32700;
+pub const RPC_INVALID_REQUEST: i64 = -32600;
+pub const RPC_METHOD_NOT_FOUND: i64 = -32601;
+pub const RPC_INVALID_PARAMS: i64 = -32602;
+pub const RPC_INTERNAL_ERROR: i64 = -32603;
+pub const RPC_BLOCKCHAIN_ERROR: i64 = -32000;
+pub const RPC_FORBIDDEN: i64 = -32001;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RpcRequest {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(default)]
+    pub params: serde_json::Value,
+    /// Requests without an id are notifications and get no response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RpcResponse {
+    pub jsonrpc: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+    pub id: serde_json::Value,
+}
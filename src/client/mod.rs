@@ -1,7 +1,9 @@
-use crate::core::{Address, GeneralTransaction, MpnAddress, Signer, TokenId};
+use crate::core::{
+    hash::Hash, Address, ContractId, GeneralTransaction, Hasher, MpnAddress, Signer, TokenId,
+};
 use crate::crypto::ed25519;
 use crate::crypto::SignatureScheme;
-use crate::zk::ZkProof;
+use crate::zk::{ZkDataLocator, ZkProof};
 use hyper::body::{Bytes, HttpBody};
 use hyper::header::HeaderValue;
 use hyper::{Body, Method, Request, Response, StatusCode};
@@ -16,7 +18,9 @@ use tokio::time::timeout;
 
 mod error;
 pub mod explorer;
+pub mod light;
 pub mod messages;
+pub mod proof;
 pub mod utils;
 pub use error::NodeError;
 use messages::*;
@@ -54,6 +58,8 @@ pub struct Peer {
     pub pub_key: ed25519::PublicKey,
     pub height: u64,
     pub outdated_states: usize,
+    /// Bodies of the blocks below this height are not available on the peer
+    pub pruned_height: u64,
 }
 
 pub struct NodeRequest {
@@ -314,6 +320,32 @@ impl BazukaClient {
             .await
     }
 
+    pub async fn get_transaction_proof(
+        &self,
+        tx_hash: <Hasher as Hash>::Output,
+    ) -> Result<GetTransactionProofResponse, NodeError> {
+        self.sender
+            .bincode_get::<GetTransactionProofRequest, GetTransactionProofResponse>(
+                format!("http://{}/bincode/transaction/proof", self.peer),
+                GetTransactionProofRequest { tx_hash },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+
+    pub async fn get_epoch_stakers(
+        &self,
+        epoch: u32,
+    ) -> Result<GetEpochStakersResponse, NodeError> {
+        self.sender
+            .bincode_get::<GetEpochStakersRequest, GetEpochStakersResponse>(
+                format!("http://{}/bincode/stakers/epoch", self.peer),
+                GetEpochStakersRequest { epoch },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+
     pub async fn get_blocks(&self, since: u64, count: u64) -> Result<GetBlocksResponse, NodeError> {
         self.sender
             .bincode_get::<GetBlocksRequest, GetBlocksResponse>(
@@ -363,6 +395,33 @@ impl BazukaClient {
             .await
     }
 
+    pub async fn get_vestings(&self, address: Address) -> Result<GetVestingsResponse, NodeError> {
+        self.sender
+            .json_get::<GetVestingsRequest, GetVestingsResponse>(
+                format!("http://{}/vestings", self.peer),
+                GetVestingsRequest {
+                    address: address.to_string(),
+                },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+
+    pub async fn get_staking_rewards(
+        &self,
+        address: Address,
+    ) -> Result<GetStakingRewardsResponse, NodeError> {
+        self.sender
+            .json_get::<GetStakingRewardsRequest, GetStakingRewardsResponse>(
+                format!("http://{}/staking/rewards", self.peer),
+                GetStakingRewardsRequest {
+                    address: address.to_string(),
+                },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+
     pub async fn get_balance(
         &self,
         address: Address,
@@ -392,6 +451,98 @@ impl BazukaClient {
             .await
     }
 
+    pub async fn get_contract_state(
+        &self,
+        contract_id: ContractId,
+        locator: ZkDataLocator,
+    ) -> Result<GetContractStateResponse, NodeError> {
+        self.sender
+            .json_get::<GetContractStateRequest, GetContractStateResponse>(
+                format!("http://{}/contract/state", self.peer),
+                GetContractStateRequest {
+                    contract_id: contract_id.to_string(),
+                    locator: locator.to_string(),
+                },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+
+    pub async fn get_history(
+        &self,
+        address: Address,
+        page: usize,
+        page_size: usize,
+    ) -> Result<GetHistoryResponse, NodeError> {
+        self.sender
+            .json_get::<GetHistoryRequest, GetHistoryResponse>(
+                format!("http://{}/history", self.peer),
+                GetHistoryRequest {
+                    address: address.to_string(),
+                    page,
+                    page_size,
+                },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+
+    pub async fn get_transaction(
+        &self,
+        tx_hash: String,
+    ) -> Result<GetTransactionResponse, NodeError> {
+        self.sender
+            .json_get::<GetTransactionRequest, GetTransactionResponse>(
+                format!("http://{}/transaction", self.peer),
+                GetTransactionRequest { tx_hash },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+    pub async fn rpc<Params: serde::Serialize, Res: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Params,
+    ) -> Result<Res, NodeError> {
+        let resp = self
+            .sender
+            .json_post::<RpcRequest, RpcResponse>(
+                format!("http://{}/rpc", self.peer),
+                RpcRequest {
+                    jsonrpc: "2.0".into(),
+                    method: method.into(),
+                    params: serde_json::to_value(params)?,
+                    id: Some(0.into()),
+                },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await?;
+        if let Some(err) = resp.error {
+            return Err(NodeError::RpcError(err.code, err.message));
+        }
+        Ok(serde_json::from_value(
+            resp.result.unwrap_or(serde_json::Value::Null),
+        )?)
+    }
+    pub async fn events(
+        &self,
+        since: u64,
+        timeout: Option<u32>,
+        topics: Option<String>,
+    ) -> Result<GetEventsResponse, NodeError> {
+        self.sender
+            .json_get::<GetEventsRequest, GetEventsResponse>(
+                format!("http://{}/events", self.peer),
+                GetEventsRequest {
+                    since,
+                    timeout,
+                    topics,
+                },
+                self.limit.clone().unwrap_or_default(),
+            )
+            .await
+    }
+
     pub async fn get_mpn_account(
         &self,
         mpn_address: MpnAddress,
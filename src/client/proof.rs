@@ -0,0 +1,201 @@
+use super::messages::GetContractStateResponse;
+use crate::core::ZkHasher as CoreZkHasher;
+use crate::zk::{ZkCompressedState, ZkDataLocator, ZkHasher, ZkScalar, ZkStateModel, ZkStateProof};
+
+/// Checks that `value` is the data at `locator` of a contract state with the
+/// given model and compressed-state, without needing the full-state. The
+/// result is only as trustworthy as the compressed-state it is checked against.
+pub fn verify_state_proof(
+    state_model: &ZkStateModel,
+    compressed_state: &ZkCompressedState,
+    locator: &ZkDataLocator,
+    value: ZkScalar,
+    proof: &ZkStateProof,
+) -> bool {
+    let mut parent_types = Vec::new();
+    let mut curr_type = state_model.clone();
+    for loc in locator.0.iter() {
+        parent_types.push(curr_type.clone());
+        curr_type = match curr_type.locate(&ZkDataLocator(vec![*loc])) {
+            Ok(t) => t,
+            Err(_) => {
+                return false;
+            }
+        };
+    }
+
+    let mut siblings = proof.0.iter().cloned();
+    let mut value = value;
+    for (parent_type, loc) in parent_types.iter().zip(locator.0.iter()).rev() {
+        match parent_type {
+            ZkStateModel::List { log4_size, .. } => {
+                let mut curr_ind = *loc;
+                for _ in 0..*log4_size {
+                    let mut dats = Vec::new();
+                    for i in 0..4 {
+                        dats.push(if i == curr_ind % 4 {
+                            value
+                        } else if let Some(sibling) = siblings.next() {
+                            sibling
+                        } else {
+                            return false;
+                        });
+                    }
+                    value = CoreZkHasher::hash(&dats);
+                    curr_ind /= 4;
+                }
+            }
+            ZkStateModel::Struct { field_types } => {
+                let mut dats = Vec::new();
+                for field_index in 0..field_types.len() as u64 {
+                    dats.push(if field_index == *loc {
+                        value
+                    } else if let Some(sibling) = siblings.next() {
+                        sibling
+                    } else {
+                        return false;
+                    });
+                }
+                value = CoreZkHasher::hash(&dats);
+            }
+            ZkStateModel::Scalar => {
+                return false;
+            }
+        }
+    }
+
+    siblings.next().is_none() && value == compressed_state.state_hash
+}
+
+impl GetContractStateResponse {
+    /// Checks the returned value against the compressed-state of the contract
+    /// account returned along with it. Headers do not commit to the contract
+    /// states, so this is a consistency check of the node's answer and not a
+    /// light-client proof: a dishonest node may return a fake account too.
+    pub fn is_consistent(&self, locator: &ZkDataLocator) -> bool {
+        verify_state_proof(
+            &self.state_model,
+            &self.account.compressed_state,
+            locator,
+            self.value,
+            &self.proof,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::{Blockchain, BlockchainError, KvStoreChain};
+    use crate::config::blockchain::get_test_blockchain_config;
+    use crate::core::{ContractId, Money};
+    use crate::db::RamKvStore;
+    use crate::wallet::TxBuilder;
+    use crate::zk::{ZkContract, ZkDataPairs};
+
+    #[test]
+    fn test_verify_state_proof() {
+        let miner = TxBuilder::new(&Vec::from("VALIDATOR"));
+        let alice = TxBuilder::new(&Vec::from("ABC"));
+        let mut chain = KvStoreChain::new(RamKvStore::new(), get_test_blockchain_config()).unwrap();
+
+        let state_model = ZkStateModel::Struct {
+            field_types: vec![
+                ZkStateModel::Scalar,
+                ZkStateModel::List {
+                    log4_size: 2,
+                    item_type: Box::new(ZkStateModel::Struct {
+                        field_types: vec![ZkStateModel::Scalar, ZkStateModel::Scalar],
+                    }),
+                },
+            ],
+        };
+        let data = ZkDataPairs(
+            [
+                (ZkDataLocator(vec![0]), ZkScalar::from(10)),
+                (ZkDataLocator(vec![1, 6, 1]), ZkScalar::from(20)),
+                (ZkDataLocator(vec![1, 9, 0]), ZkScalar::from(30)),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let tx = alice.create_contract(
+            "".into(),
+            ZkContract {
+                state_model: state_model.clone(),
+                initial_state: state_model.compress::<CoreZkHasher>(&data).unwrap(),
+                deposit_functions: Vec::new(),
+                withdraw_functions: Vec::new(),
+                functions: Vec::new(),
+            },
+            data,
+            Money::ziesha(0),
+            1,
+        );
+        let contract_id = ContractId::new(&tx.tx);
+        let draft = chain.draft_block(1, &[tx], &miner, true).unwrap().unwrap();
+        chain.extend(1, std::slice::from_ref(&draft.block)).unwrap();
+
+        // Full-state is not available yet
+        assert!(matches!(
+            chain.prove_state(contract_id, ZkDataLocator(vec![0])),
+            Err(BlockchainError::StatesUnavailable)
+        ));
+        chain.update_states(&draft.patch).unwrap();
+
+        let compressed_state = chain
+            .get_contract_account(contract_id)
+            .unwrap()
+            .compressed_state;
+        for (locator, expected) in [
+            (vec![0], Some(10)),
+            (vec![1, 6, 1], Some(20)),
+            (vec![1, 9, 0], Some(30)),
+            (vec![1, 3, 1], Some(0)),
+            (vec![1, 6], None),
+            (vec![1], None),
+            (vec![], None),
+        ] {
+            let locator = ZkDataLocator(locator);
+            let (value, proof) = chain.prove_state(contract_id, locator.clone()).unwrap();
+            if let Some(expected) = expected {
+                assert_eq!(value, ZkScalar::from(expected));
+            }
+            assert!(verify_state_proof(
+                &state_model,
+                &compressed_state,
+                &locator,
+                value,
+                &proof
+            ));
+            assert!(!verify_state_proof(
+                &state_model,
+                &compressed_state,
+                &locator,
+                value + ZkScalar::from(1),
+                &proof
+            ));
+        }
+
+        // Proofs are bound to their locators
+        let (value, proof) = chain
+            .prove_state(contract_id, ZkDataLocator(vec![1, 6, 1]))
+            .unwrap();
+        assert!(!verify_state_proof(
+            &state_model,
+            &compressed_state,
+            &ZkDataLocator(vec![1, 6, 0]),
+            value,
+            &proof
+        ));
+        let mut short_proof = proof.clone();
+        short_proof.0.pop();
+        assert!(!verify_state_proof(
+            &state_model,
+            &compressed_state,
+            &ZkDataLocator(vec![1, 6, 1]),
+            value,
+            &short_proof
+        ));
+    }
+}
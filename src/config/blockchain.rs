@@ -148,6 +148,8 @@ fn get_ziesha_token_creation_tx() -> Transaction {
                 supply: Amount(2_000_000_000_u64 * UNIT),
                 decimals: UNIT_ZEROS,
                 minter: None,
+                max_supply: None,
+                metadata: None,
             },
         },
         nonce: 1,
@@ -209,6 +211,7 @@ pub fn get_blockchain_config() -> BlockchainConfig {
                 timestamp: CHAIN_START_TIMESTAMP,
                 validator: Default::default(),
                 proof: ValidatorProof::Unproven,
+                sig: None,
             },
         },
         body: vec![
@@ -278,6 +281,13 @@ pub fn get_blockchain_config() -> BlockchainConfig {
         chain_start_timestamp: CHAIN_START_TIMESTAMP,
         check_validator: true,
         max_validator_commision: 26, // 26 / 255 ~= 10%
+        max_commision_change: 3,     // 3 / 255 ~= 1%
+        commision_change_delay: 6,   // ~1 hour
+        unbonding_epochs: 144,       // ~1 day
+        slashing_ratio: 13,          // 13 / 255 ~= 5%
+        tx_history: false,
+        max_reorg_depth: 64, // Blocks deeper than 64 are considered final
+        pruning: None,
     }
 }
 
@@ -392,6 +402,7 @@ pub fn get_test_blockchain_config() -> BlockchainConfig {
     conf.chain_start_timestamp = 0;
     conf.check_validator = false;
     conf.slot_duration = 5;
+    conf.tx_history = true;
 
     conf.genesis.block.body[1] = get_test_mpn_contract().tx;
     conf.genesis.block.body.drain(2..);
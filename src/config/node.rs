@@ -26,8 +26,11 @@ pub fn get_node_options() -> NodeOptions {
         state_unavailable_ban_time: 30,
         candidate_remove_threshold: 3600,
         mempool_max_fetch: 1000,
+        mempool_max_size: 10000,
+        max_events: 1000,
         max_block_time_difference: 120,
         automatic_block_generation: true,
+        fee_token_prices: Default::default(),
     }
 }
 
@@ -56,7 +59,10 @@ pub fn get_simulator_options() -> NodeOptions {
         state_unavailable_ban_time: 10,
         candidate_remove_threshold: 600,
         mempool_max_fetch: 1000,
+        mempool_max_size: 10000,
+        max_events: 1000,
         max_block_time_difference: 120,
         automatic_block_generation: false,
+        fee_token_prices: Default::default(),
     }
 }
@@ -0,0 +1,275 @@
+use crate::core::{ContractId, Money, TransactionAndDelta, ZkHasher};
+use crate::wallet::TxBuilder;
+use crate::zk;
+use bellman::groth16;
+use bellman::{Circuit, SynthesisError};
+use bls12_381::{Bls12, Scalar as BellmanFr};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("io error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("bincode error: {0}")]
+    BincodeError(#[from] bincode::Error),
+    #[error("synthesis error: {0}")]
+    SynthesisError(#[from] SynthesisError),
+    #[error("state-manager error: {0}")]
+    StateManagerError(#[from] zk::StateManagerError),
+    #[error("invalid state model")]
+    InvalidStateModel,
+    #[error("function not found")]
+    FunctionNotFound,
+}
+
+/// Circuits that can be deployed as contract functions. Public inputs of the
+/// circuit should be the previous height, the previous state, the aux-data and
+/// the next state, in the same order.
+pub trait ContractCircuit: Circuit<BellmanFr> + Clone {
+    /// Circuit with no witness assignments, used for generating the parameters
+    fn empty() -> Self;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractFunction {
+    Deposit(u32),
+    Withdraw(u32),
+    Call(u32),
+}
+
+impl ContractFunction {
+    fn file_name(&self) -> String {
+        match self {
+            ContractFunction::Deposit(id) => format!("deposit-{}.params", id),
+            ContractFunction::Withdraw(id) => format!("withdraw-{}.params", id),
+            ContractFunction::Call(id) => format!("function-{}.params", id),
+        }
+    }
+}
+
+/// Runs a local (Non-multiparty!) trusted-setup for the given circuit
+pub fn generate_params<C: ContractCircuit, R: RngCore>(
+    rng: &mut R,
+) -> Result<groth16::Parameters<Bls12>, ContractError> {
+    Ok(groth16::generate_random_parameters::<Bls12, _, _>(
+        C::empty(),
+        rng,
+    )?)
+}
+
+pub struct ContractBuilder {
+    state_model: zk::ZkStateModel,
+    initial_state: zk::ZkDataPairs,
+    deposit_functions: Vec<(zk::ZkMultiInputVerifierKey, groth16::Parameters<Bls12>)>,
+    withdraw_functions: Vec<(zk::ZkMultiInputVerifierKey, groth16::Parameters<Bls12>)>,
+    functions: Vec<(zk::ZkSingleInputVerifierKey, groth16::Parameters<Bls12>)>,
+}
+
+impl ContractBuilder {
+    pub fn new(state_model: zk::ZkStateModel) -> Self {
+        Self {
+            state_model,
+            initial_state: Default::default(),
+            deposit_functions: Vec::new(),
+            withdraw_functions: Vec::new(),
+            functions: Vec::new(),
+        }
+    }
+
+    pub fn initial_state(mut self, initial_state: zk::ZkDataPairs) -> Self {
+        self.initial_state = initial_state;
+        self
+    }
+
+    pub fn deposit_function<C: ContractCircuit, R: RngCore>(
+        mut self,
+        log4_payment_capacity: u8,
+        rng: &mut R,
+    ) -> Result<Self, ContractError> {
+        let params = generate_params::<C, R>(rng)?;
+        self.deposit_functions.push((
+            zk::ZkMultiInputVerifierKey {
+                verifier_key: zk::ZkVerifierKey::Groth16(Box::new(params.vk.clone().into())),
+                log4_payment_capacity,
+            },
+            params,
+        ));
+        Ok(self)
+    }
+
+    pub fn withdraw_function<C: ContractCircuit, R: RngCore>(
+        mut self,
+        log4_payment_capacity: u8,
+        rng: &mut R,
+    ) -> Result<Self, ContractError> {
+        let params = generate_params::<C, R>(rng)?;
+        self.withdraw_functions.push((
+            zk::ZkMultiInputVerifierKey {
+                verifier_key: zk::ZkVerifierKey::Groth16(Box::new(params.vk.clone().into())),
+                log4_payment_capacity,
+            },
+            params,
+        ));
+        Ok(self)
+    }
+
+    pub fn function<C: ContractCircuit, R: RngCore>(
+        mut self,
+        rng: &mut R,
+    ) -> Result<Self, ContractError> {
+        let params = generate_params::<C, R>(rng)?;
+        self.functions.push((
+            zk::ZkSingleInputVerifierKey {
+                verifier_key: zk::ZkVerifierKey::Groth16(Box::new(params.vk.clone().into())),
+            },
+            params,
+        ));
+        Ok(self)
+    }
+
+    pub fn build(self) -> Result<ContractPackage, ContractError> {
+        if !self.state_model.is_valid::<ZkHasher>() {
+            return Err(ContractError::InvalidStateModel);
+        }
+        let initial_state = self.state_model.compress::<ZkHasher>(&self.initial_state)?;
+        let (deposit_functions, deposit_params): (Vec<_>, Vec<_>) =
+            self.deposit_functions.into_iter().unzip();
+        let (withdraw_functions, withdraw_params): (Vec<_>, Vec<_>) =
+            self.withdraw_functions.into_iter().unzip();
+        let (functions, function_params): (Vec<_>, Vec<_>) = self.functions.into_iter().unzip();
+        Ok(ContractPackage {
+            contract: zk::ZkContract {
+                state_model: self.state_model,
+                initial_state,
+                deposit_functions,
+                withdraw_functions,
+                functions,
+            },
+            initial_state: self.initial_state,
+            deposit_params,
+            withdraw_params,
+            function_params,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContractManifest {
+    contract: zk::ZkContract,
+    initial_state: zk::ZkDataPairs,
+}
+
+/// A deployable contract, along with the proving parameters of its functions
+pub struct ContractPackage {
+    pub contract: zk::ZkContract,
+    pub initial_state: zk::ZkDataPairs,
+    deposit_params: Vec<groth16::Parameters<Bls12>>,
+    withdraw_params: Vec<groth16::Parameters<Bls12>>,
+    function_params: Vec<groth16::Parameters<Bls12>>,
+}
+
+impl ContractPackage {
+    const MANIFEST: &'static str = "contract.dat";
+
+    fn params(
+        &self,
+        function: ContractFunction,
+    ) -> Result<&groth16::Parameters<Bls12>, ContractError> {
+        match function {
+            ContractFunction::Deposit(id) => self.deposit_params.get(id as usize),
+            ContractFunction::Withdraw(id) => self.withdraw_params.get(id as usize),
+            ContractFunction::Call(id) => self.function_params.get(id as usize),
+        }
+        .ok_or(ContractError::FunctionNotFound)
+    }
+
+    fn functions(&self) -> Vec<ContractFunction> {
+        (0..self.deposit_params.len() as u32)
+            .map(ContractFunction::Deposit)
+            .chain((0..self.withdraw_params.len() as u32).map(ContractFunction::Withdraw))
+            .chain((0..self.function_params.len() as u32).map(ContractFunction::Call))
+            .collect()
+    }
+
+    /// Writes the contract and the proving parameters of its functions into
+    /// the given directory
+    pub fn save(&self, dir: &Path) -> Result<(), ContractError> {
+        std::fs::create_dir_all(dir)?;
+        bincode::serialize_into(
+            BufWriter::new(File::create(dir.join(Self::MANIFEST))?),
+            &ContractManifest {
+                contract: self.contract.clone(),
+                initial_state: self.initial_state.clone(),
+            },
+        )?;
+        for function in self.functions() {
+            self.params(function)?.write(BufWriter::new(File::create(
+                dir.join(function.file_name()),
+            )?))?;
+        }
+        Ok(())
+    }
+
+    pub fn load(dir: &Path) -> Result<Self, ContractError> {
+        let manifest: ContractManifest =
+            bincode::deserialize_from(BufReader::new(File::open(dir.join(Self::MANIFEST))?))?;
+        let read_params = |function: ContractFunction| -> Result<_, ContractError> {
+            let path: PathBuf = dir.join(function.file_name());
+            Ok(groth16::Parameters::read(
+                BufReader::new(File::open(path)?),
+                false,
+            )?)
+        };
+        let contract = manifest.contract;
+        Ok(Self {
+            deposit_params: (0..contract.deposit_functions.len() as u32)
+                .map(|id| read_params(ContractFunction::Deposit(id)))
+                .collect::<Result<_, _>>()?,
+            withdraw_params: (0..contract.withdraw_functions.len() as u32)
+                .map(|id| read_params(ContractFunction::Withdraw(id)))
+                .collect::<Result<_, _>>()?,
+            function_params: (0..contract.functions.len() as u32)
+                .map(|id| read_params(ContractFunction::Call(id)))
+                .collect::<Result<_, _>>()?,
+            contract,
+            initial_state: manifest.initial_state,
+        })
+    }
+
+    pub fn create_tx(
+        &self,
+        tx_builder: &TxBuilder,
+        memo: String,
+        fee: Money,
+        nonce: u32,
+    ) -> (TransactionAndDelta, ContractId) {
+        let tx = tx_builder.create_contract(
+            memo,
+            self.contract.clone(),
+            self.initial_state.clone(),
+            fee,
+            nonce,
+        );
+        let contract_id = ContractId::new(&tx.tx);
+        (tx, contract_id)
+    }
+
+    /// Proves a state transition of the contract through the given function
+    pub fn prove<C: Circuit<BellmanFr>, R: RngCore>(
+        &self,
+        function: ContractFunction,
+        circuit: C,
+        rng: &mut R,
+    ) -> Result<zk::ZkProof, ContractError> {
+        let proof = groth16::create_random_proof(circuit, self.params(function)?, rng)?;
+        Ok(zk::ZkProof::Groth16(Box::new(proof.into())))
+    }
+}
+
+#[cfg(test)]
+mod test;
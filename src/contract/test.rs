@@ -0,0 +1,121 @@
+use super::*;
+use crate::zk::ZkScalar;
+use bellman::ConstraintSystem;
+use rand::rngs::OsRng;
+
+/// Adds the aux-data to a single-scalar state
+#[derive(Clone)]
+struct AddCircuit {
+    inputs: Option<(u64, ZkScalar, ZkScalar, ZkScalar)>,
+}
+
+impl ContractCircuit for AddCircuit {
+    fn empty() -> Self {
+        Self { inputs: None }
+    }
+}
+
+impl Circuit<BellmanFr> for AddCircuit {
+    fn synthesize<CS: ConstraintSystem<BellmanFr>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let inputs = self.inputs;
+        let _height = cs.alloc_input(
+            || "height",
+            || {
+                inputs
+                    .map(|i| BellmanFr::from(i.0))
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+        let prev_state = cs.alloc_input(
+            || "prev_state",
+            || {
+                inputs
+                    .map(|i| i.1.into())
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+        let aux_data = cs.alloc_input(
+            || "aux_data",
+            || {
+                inputs
+                    .map(|i| i.2.into())
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+        let next_state = cs.alloc_input(
+            || "next_state",
+            || {
+                inputs
+                    .map(|i| i.3.into())
+                    .ok_or(SynthesisError::AssignmentMissing)
+            },
+        )?;
+        cs.enforce(
+            || "next_state = prev_state + aux_data",
+            |lc| lc + prev_state + aux_data,
+            |lc| lc + CS::one(),
+            |lc| lc + next_state,
+        );
+        Ok(())
+    }
+}
+
+#[test]
+fn test_contract_package() {
+    let package = ContractBuilder::new(zk::ZkStateModel::Scalar)
+        .function::<AddCircuit, _>(&mut OsRng)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(package.contract.functions.len(), 1);
+    assert_eq!(
+        package.contract.initial_state,
+        zk::ZkCompressedState::empty::<ZkHasher>(zk::ZkStateModel::Scalar)
+    );
+
+    let dir = std::env::temp_dir().join(format!("bazuka-contract-{}", rand::random::<u64>()));
+    package.save(&dir).unwrap();
+    let package = ContractPackage::load(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let prev_state = ZkScalar::from(5);
+    let aux_data = ZkScalar::from(7);
+    let next_state = ZkScalar::from(12);
+    let proof = package
+        .prove(
+            ContractFunction::Call(0),
+            AddCircuit {
+                inputs: Some((1, prev_state, aux_data, next_state)),
+            },
+            &mut OsRng,
+        )
+        .unwrap();
+    let vk = &package.contract.functions[0].verifier_key;
+    assert!(zk::check_proof(
+        vk, 1, prev_state, aux_data, next_state, &proof
+    ));
+    assert!(!zk::check_proof(
+        vk,
+        1,
+        prev_state,
+        aux_data,
+        ZkScalar::from(13),
+        &proof
+    ));
+    assert!(matches!(
+        package.prove(
+            ContractFunction::Deposit(0),
+            AddCircuit::empty(),
+            &mut OsRng
+        ),
+        Err(ContractError::FunctionNotFound)
+    ));
+
+    let abc = TxBuilder::new(&Vec::from("ABC"));
+    let (tx, contract_id) = package.create_tx(&abc, "".into(), Money::ziesha(0), 1);
+    assert_eq!(contract_id, ContractId::new(&tx.tx));
+    assert!(tx.tx.verify_signature());
+}
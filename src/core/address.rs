@@ -54,15 +54,70 @@ where
 pub enum Signature<S: SignatureScheme> {
     Unsigned,
     Signed(S::Sig),
+    // Signatures of the co-signers of a multisig account
+    MultiSigned(Vec<(S::Pub, S::Sig)>),
 }
 
 #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
 pub struct Staker<V: VerifiableRandomFunction> {
     pub vrf_pub_key: V::Pub,
     pub commision: u8,
+    /// Announced commision changes, in the order they take effect
+    pub pending_commisions: Vec<CommisionChange>,
+}
+
+impl<V: VerifiableRandomFunction> Staker<V> {
+    /// Commision charged by the validator in the given epoch
+    pub fn commision_at(&self, epoch: u32) -> u8 {
+        self.pending_commisions
+            .iter()
+            .rev()
+            .find(|change| epoch >= change.effective_epoch)
+            .map(|change| change.commision)
+            .unwrap_or(self.commision)
+    }
+}
+
+/// Stake and VRF key of a validator, as fixed for the elections of an epoch
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct EpochStaker<S: SignatureScheme, V: VerifiableRandomFunction> {
+    pub address: S::Pub,
+    pub vrf_pub_key: V::Pub,
+    pub stake: Amount,
+}
+
+/// Commision announced by a validator, charged from `effective_epoch` on
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct CommisionChange {
+    pub commision: u8,
+    pub effective_epoch: u32,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
 pub struct Delegate {
     pub amount: Amount,
 }
+
+/// Undelegated funds, waiting to be reclaimed by the delegator
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Unbonding {
+    pub amount: Amount,
+    pub unlock_epoch: u32,
+}
+
+/// Ziesha rewarded for the blocks produced by a validator in an epoch
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ValidatorReward {
+    pub epoch: u32,
+    pub reward: Amount,
+    /// Part of the reward kept by the validator
+    pub commision: Amount,
+}
+
+/// Ziesha paid to a delegator by a validator in an epoch
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct DelegatorReward<S: SignatureScheme> {
+    pub epoch: u32,
+    pub validator: S::Pub,
+    pub amount: Amount,
+}
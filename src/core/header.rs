@@ -28,6 +28,8 @@ pub struct ProofOfStake<S: SignatureScheme, V: VerifiableRandomFunction> {
     pub timestamp: u32,
     /// vrf proof for this validator
     pub proof: ValidatorProof<V>,
+    /// signature of the validator over the header
+    pub sig: Option<S::Sig>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, Hash)]
@@ -46,4 +48,18 @@ impl<H: Hash, S: SignatureScheme, V: VerifiableRandomFunction> Header<H, S, V> {
     pub fn hash(&self) -> H::Output {
         H::hash(&bincode::serialize(&self).expect("convert header to bincode format"))
     }
+
+    /// Bytes signed by the validator, i.e the header without its signature
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        let mut unsigned = self.clone();
+        unsigned.proof_of_stake.sig = None;
+        bincode::serialize(&unsigned).expect("convert header to bincode format")
+    }
+
+    pub fn verify_signature(&self) -> bool {
+        match &self.proof_of_stake.sig {
+            Some(sig) => S::verify(&self.proof_of_stake.validator, &self.signing_bytes(), sig),
+            None => false,
+        }
+    }
 }
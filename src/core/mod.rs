@@ -25,7 +25,12 @@ pub type ZkSigner = crypto::jubjub::JubJub<ZkHasher>;
 pub type Address = <Signer as crypto::SignatureScheme>::Pub;
 pub type ParseAddressError = <Signer as crypto::SignatureScheme>::PubParseError;
 pub type Staker = address::Staker<Vrf>;
+pub type CommisionChange = address::CommisionChange;
+pub type EpochStaker = address::EpochStaker<Signer, Vrf>;
 pub type Delegate = address::Delegate;
+pub type Unbonding = address::Unbonding;
+pub type ValidatorReward = address::ValidatorReward;
+pub type DelegatorReward = address::DelegatorReward<Signer>;
 pub type Signature = address::Signature<Signer>;
 pub type Transaction = transaction::Transaction<Hasher, Signer, Vrf>;
 pub type TransactionData = transaction::TransactionData<Hasher, Signer, Vrf>;
@@ -46,6 +51,13 @@ pub type TokenId = transaction::TokenId;
 pub type ParseTokenIdError = transaction::ParseTokenIdError;
 pub type TokenUpdate = transaction::TokenUpdate<Signer>;
 pub type Token = transaction::Token<Signer>;
+pub type TokenMetadata = transaction::TokenMetadata;
+pub type MultisigPolicy = transaction::MultisigPolicy<Signer>;
+pub type LockTime = transaction::LockTime;
+pub type Vesting = transaction::Vesting<Signer>;
+pub type VestingAccount = transaction::VestingAccount<Signer>;
+pub type VestingId = transaction::VestingId<Hasher>;
+pub type ParseVestingIdError = transaction::ParseVestingIdError;
 
 pub type ProofOfStake = header::ProofOfStake<Signer, Vrf>;
 pub type ContractId = transaction::ContractId<Hasher>;
@@ -137,6 +149,22 @@ impl GeneralTransaction {
             GeneralTransaction::MpnWithdraw(mpn_withdraw) => mpn_withdraw.zk_nonce,
         }
     }
+    pub fn fee(&self) -> Money {
+        match self {
+            GeneralTransaction::TransactionAndDelta(tx_delta) => tx_delta.tx.fee,
+            GeneralTransaction::MpnDeposit(mpn_deposit) => mpn_deposit.payment.fee,
+            GeneralTransaction::MpnTransaction(mpn_tx) => mpn_tx.fee,
+            GeneralTransaction::MpnWithdraw(mpn_withdraw) => mpn_withdraw.payment.fee,
+        }
+    }
+    /// Serialized size of the transaction. Transactions that can't be
+    /// serialized are considered as large as possible.
+    pub fn size(&self) -> usize {
+        bincode::serialized_size(self)
+            .ok()
+            .and_then(|sz| usize::try_from(sz).ok())
+            .unwrap_or(usize::MAX)
+    }
     pub fn sender(&self) -> GeneralAddress {
         match self {
             GeneralTransaction::TransactionAndDelta(tx_delta) => {
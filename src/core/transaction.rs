@@ -1,5 +1,6 @@
 use super::address::Signature;
 use super::hash::Hash;
+use super::header::Header;
 use super::Amount;
 use crate::crypto::DeriveMpnAccountIndex;
 use crate::crypto::VerifiableRandomFunction;
@@ -266,9 +267,28 @@ pub struct RegularSendEntry<S: SignatureScheme> {
 pub struct Token<S: SignatureScheme> {
     pub name: String,
     pub symbol: String,
-    pub supply: Amount, // 1u64 in case of a NFT
+    pub supply: Amount, // Total minted, burns included (1u64 in case of a NFT)
     pub decimals: u8,
     pub minter: Option<S::Pub>,
+    pub max_supply: Option<Amount>, // Hard cap, enforced when minting
+    pub metadata: Option<TokenMetadata>,
+}
+
+/// Off-chain description of a token (Logo, website, etc.)
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct TokenMetadata {
+    pub uri: String,
+    /// Hash of the content behind `uri`, so that it can't be changed silently
+    pub hash: Option<[u8; 32]>,
+}
+
+impl TokenMetadata {
+    pub fn validate(&self) -> bool {
+        const MAX_URI_LEN: usize = 256;
+        !self.uri.is_empty()
+            && self.uri.len() <= MAX_URI_LEN
+            && self.uri.chars().all(|c| c.is_ascii_graphic())
+    }
 }
 
 impl<S: SignatureScheme> Token<S> {
@@ -291,10 +311,183 @@ impl<S: SignatureScheme> Token<S> {
     }
 }
 
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct MultisigPolicy<S: SignatureScheme> {
+    pub threshold: u8,
+    pub signers: Vec<S::Pub>,
+}
+
+impl<S: SignatureScheme> MultisigPolicy<S> {
+    const MAX_SIGNERS: usize = 16;
+
+    pub fn validate(&self) -> bool {
+        let distinct = self
+            .signers
+            .iter()
+            .enumerate()
+            .all(|(i, pk)| !self.signers[..i].contains(pk));
+        self.threshold > 0
+            && self.threshold as usize <= self.signers.len()
+            && self.signers.len() <= Self::MAX_SIGNERS
+            && distinct
+    }
+
+    /// Address of the multisig account, nobody knows its private-key
+    pub fn address(&self) -> S::Pub {
+        let mut seed = b"multisig".to_vec();
+        seed.extend(bincode::serialize(self).unwrap());
+        S::derive_pub(&seed)
+    }
+
+    /// Whether at least `threshold` distinct signers of the policy are given
+    pub fn is_satisfied_by<'a, I: Iterator<Item = &'a S::Pub>>(&self, signers: I) -> bool
+    where
+        S::Pub: 'a,
+    {
+        let mut approved: Vec<&S::Pub> = Vec::new();
+        for pk in signers {
+            if !self.signers.contains(pk) || approved.contains(&pk) {
+                return false;
+            }
+            approved.push(pk);
+        }
+        approved.len() >= self.threshold as usize
+    }
+}
+
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    PartialEq,
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    std::hash::Hash,
+    Default,
+)]
+pub struct VestingId<H: Hash>(H::Output);
+
+#[derive(Error, Debug)]
+pub enum ParseVestingIdError {
+    #[error("vesting-id invalid")]
+    Invalid,
+}
+
+impl<H: Hash> VestingId<H> {
+    pub fn new<S: SignatureScheme, V: VerifiableRandomFunction>(tx: &Transaction<H, S, V>) -> Self {
+        Self(tx.hash())
+    }
+}
+
+impl<H: Hash> std::fmt::Display for VestingId<H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl<H: Hash> FromStr for VestingId<H> {
+    type Err = ParseVestingIdError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(|_| ParseVestingIdError::Invalid)?;
+        let hash_output = H::Output::try_from(bytes).map_err(|_| ParseVestingIdError::Invalid)?;
+        Ok(Self(hash_output))
+    }
+}
+
+/// A point in the chain's history, either a block height or a timestamp
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum LockTime {
+    Height(u64),
+    Timestamp(u32),
+}
+
+impl LockTime {
+    /// Position of the given height/timestamp in the unit of this lock-time
+    fn current(&self, height: u64, timestamp: u32) -> u64 {
+        match self {
+            LockTime::Height(_) => height,
+            LockTime::Timestamp(_) => timestamp as u64,
+        }
+    }
+    fn value(&self) -> u64 {
+        match self {
+            LockTime::Height(h) => *h,
+            LockTime::Timestamp(t) => *t as u64,
+        }
+    }
+}
+
+impl std::fmt::Display for LockTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LockTime::Height(h) => write!(f, "height {}", h),
+            LockTime::Timestamp(t) => write!(f, "timestamp {}", t),
+        }
+    }
+}
+
+/// Funds locked for a beneficiary. Nothing is released before `unlock`. If
+/// `linear_until` is given, the funds are released linearly between `unlock`
+/// and `linear_until`, otherwise they are all released at `unlock`.
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct Vesting<S: SignatureScheme> {
+    pub beneficiary: S::Pub,
+    pub amount: Money,
+    pub unlock: LockTime,
+    pub linear_until: Option<LockTime>,
+}
+
+impl<S: SignatureScheme> Vesting<S> {
+    pub fn validate(&self) -> bool {
+        let schedule_valid = match (self.unlock, self.linear_until) {
+            (_, None) => true,
+            (LockTime::Height(start), Some(LockTime::Height(end))) => end > start,
+            (LockTime::Timestamp(start), Some(LockTime::Timestamp(end))) => end > start,
+            _ => false,
+        };
+        self.amount.amount > Amount(0) && self.amount.token_id != TokenId::Null && schedule_valid
+    }
+
+    /// Total amount released by the given height/timestamp
+    pub fn released(&self, height: u64, timestamp: u32) -> Amount {
+        let now = self.unlock.current(height, timestamp);
+        let start = self.unlock.value();
+        if now < start {
+            return Amount(0);
+        }
+        match self.linear_until {
+            Some(end) if now < end.value() => {
+                let total = u64::from(self.amount.amount) as u128;
+                let released = total * (now - start) as u128 / (end.value() - start) as u128;
+                Amount(released as u64)
+            }
+            _ => self.amount.amount,
+        }
+    }
+}
+
+/// State of a vesting, stored on the chain until it is fully claimed
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct VestingAccount<S: SignatureScheme> {
+    pub vesting: Vesting<S>,
+    pub claimed: Amount,
+}
+
+impl<S: SignatureScheme> VestingAccount<S> {
+    pub fn locked(&self) -> Amount {
+        self.vesting.amount.amount - self.claimed
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug, Clone)]
 pub enum TokenUpdate<S: SignatureScheme> {
     Mint { amount: Amount },
     ChangeMinter { minter: S::Pub },
+    // Destroy some of the sender's own balance, allowed for any holder
+    Burn { amount: Amount },
+    // Remove the minter, freezing the supply forever
+    RenounceMinter,
 }
 
 // A transaction could be as simple as sending some funds, or as complicated as
@@ -330,6 +523,42 @@ pub enum TransactionData<H: Hash, S: SignatureScheme, V: VerifiableRandomFunctio
         token_id: TokenId,
         update: TokenUpdate<S>,
     },
+    // Register a M-of-N policy under the address derived from the policy
+    RegisterMultisig {
+        policy: MultisigPolicy<S>,
+    },
+    // Lock funds for a beneficiary, released by time or block height
+    CreateVesting {
+        vesting: Vesting<S>,
+    },
+    // Claim the released part of a vesting, only by its beneficiary
+    ClaimVesting {
+        vesting_id: VestingId<H>,
+    },
+    // Get the undelegated funds back, once their unbonding period is over
+    ReclaimDelegate {
+        from: S::Pub,
+    },
+    // Evidence of a validator signing two different headers for the same slot
+    SlashValidator {
+        first: Box<Header<H, S, V>>,
+        second: Box<Header<H, S, V>>,
+    },
+    // Stop being a validator, all of the delegations start unbonding
+    UnregisterStaker,
+    // Let the spender transfer up to `amount` of the sender's tokens. The
+    // previous allowance is overwritten, so a non-zero allowance should first
+    // be set to zero, otherwise the spender may use both the old and the new one.
+    Approve {
+        spender: S::Pub,
+        token_id: TokenId,
+        amount: Amount,
+    },
+    // Transfer pre-approved funds of the owner, by the spender
+    TransferFrom {
+        owner: S::Pub,
+        entries: Vec<RegularSendEntry<S>>,
+    },
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -366,6 +595,15 @@ impl<H: Hash, S: SignatureScheme, V: VerifiableRandomFunction> Transaction<H, S,
                     let bytes = bincode::serialize(&unsigned).unwrap();
                     S::verify(pk, &bytes, sig)
                 }
+                // Only the signatures are checked here, the signers are
+                // checked against the multisig policy of the account when
+                // the transaction is applied.
+                Signature::MultiSigned(sigs) => {
+                    let mut unsigned = self.clone();
+                    unsigned.sig = Signature::Unsigned;
+                    let bytes = bincode::serialize(&unsigned).unwrap();
+                    !sigs.is_empty() && sigs.iter().all(|(pk, sig)| S::verify(pk, &bytes, sig))
+                }
             },
         }
     }
@@ -51,6 +51,12 @@ impl Default for Signature {
     }
 }
 
+impl std::hash::Hash for Signature {
+    fn hash<Hs: std::hash::Hasher>(&self, state: &mut Hs) {
+        state.write(&self.0.to_bytes());
+    }
+}
+
 impl std::fmt::Display for Signature {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", hex::encode(self.0.to_bytes()))
@@ -76,6 +82,17 @@ impl<H: Hash> SignatureScheme for Ed25519<H> {
     fn verify(pk: &PublicKey, message: &[u8], sig: &Signature) -> bool {
         pk.0.verify(message, &sig.0).is_ok()
     }
+    fn derive_pub(seed: &[u8]) -> PublicKey {
+        // Try-and-increment until the hash is a valid curve point
+        for i in 0u32.. {
+            let mut preimage = seed.to_vec();
+            preimage.extend(i.to_le_bytes());
+            if let Ok(pk) = ed25519_dalek::PublicKey::from_bytes(H::hash(&preimage).as_ref()) {
+                return PublicKey(pk);
+            }
+        }
+        unreachable!()
+    }
 }
 
 impl std::fmt::Display for PublicKey {
@@ -133,4 +150,13 @@ mod tests {
         assert!(Ed25519::<crate::core::Hasher>::verify(&pk, msg, &sig));
         assert!(!Ed25519::<crate::core::Hasher>::verify(&pk, fake_msg, &sig));
     }
+
+    #[test]
+    fn test_ed25519_derive_pub() {
+        let pk1 = Ed25519::<crate::core::Hasher>::derive_pub(b"ABC");
+        let pk2 = Ed25519::<crate::core::Hasher>::derive_pub(b"ABC");
+        let pk3 = Ed25519::<crate::core::Hasher>::derive_pub(b"CBA");
+        assert_eq!(pk1, pk2);
+        assert_ne!(pk1, pk3);
+    }
 }
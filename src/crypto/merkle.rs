@@ -90,6 +90,14 @@ impl<H: Hash> MerkleTree<H> {
         proof
     }
 
+    /// Checks an inclusion proof generated by `prove` against a merkle root
+    pub fn verify(leaf: H::Output, proof: &[H::Output], root: H::Output) -> bool {
+        proof
+            .iter()
+            .fold(leaf, |curr, sibling| merge_hash::<H>(&curr, sibling))
+            == root
+    }
+
     pub fn new(leaves: Vec<H::Output>) -> MerkleTree<H> {
         if leaves.is_empty() {
             return MerkleTree::<H> {
@@ -124,6 +132,16 @@ mod tests {
                 curr = merge_hash::<Sha3Hasher>(&curr, &entry);
             }
             assert_eq!(curr, root);
+            assert!(MerkleTree::<Sha3Hasher>::verify(
+                Sha3Hasher::hash(&[i as u8]),
+                &tree.prove(i),
+                root
+            ));
+            assert!(!MerkleTree::<Sha3Hasher>::verify(
+                Sha3Hasher::hash(&[i as u8 + 1]),
+                &tree.prove(i),
+                root
+            ));
         }
     }
 
@@ -26,10 +26,20 @@ pub trait SignatureScheme: Clone + Serialize + Default {
         + From<Self::Priv>
         + Default; // Default is null pub-key (Treasury address)
     type Priv: Clone;
-    type Sig: Clone + Debug + PartialEq + Eq + Serialize + DeserializeOwned + Display + Default;
+    type Sig: Clone
+        + Debug
+        + PartialEq
+        + Eq
+        + std::hash::Hash
+        + Serialize
+        + DeserializeOwned
+        + Display
+        + Default;
     fn generate_keys(seed: &[u8]) -> (Self::Pub, Self::Priv);
     fn sign(sk: &Self::Priv, msg: &[u8]) -> Self::Sig;
     fn verify(pk: &Self::Pub, msg: &[u8], sig: &Self::Sig) -> bool;
+    /// Deterministically derives a public-key with no known private-key
+    fn derive_pub(seed: &[u8]) -> Self::Pub;
 }
 
 pub trait DeriveMpnAccountIndex {
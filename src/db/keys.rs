@@ -1,5 +1,5 @@
 use super::*;
-use crate::core::{Address, ContractId, TokenId};
+use crate::core::{Address, ContractId, TokenId, VestingId};
 use crate::zk::ZkDataLocator;
 use thiserror::Error;
 
@@ -27,6 +27,10 @@ pub fn merkle(index: u64) -> StringKey {
     format!("MRK-{:010}", index).into()
 }
 
+pub fn pruned_height() -> StringKey {
+    "PRN".into()
+}
+
 pub fn compressed_state_at(contract_id: &ContractId, at: u64) -> StringKey {
     format!("CSA-{:010}-{}", at, contract_id).into()
 }
@@ -173,10 +177,125 @@ impl DelegateeRankDbKey {
     }
 }
 
+pub struct AddressHistoryDbKey {
+    pub address: Address,
+    pub height: u64,
+    pub index: u32,
+    pub tx_hash: <Hasher as Hash>::Output,
+}
+impl From<AddressHistoryDbKey> for StringKey {
+    fn from(key: AddressHistoryDbKey) -> Self {
+        format!(
+            "{}-{:016x}-{:08x}-{}",
+            AddressHistoryDbKey::prefix(&key.address),
+            (u64::MAX - key.height),
+            (u32::MAX - key.index),
+            hex::encode(key.tx_hash)
+        )
+        .into()
+    }
+}
+impl TryFrom<StringKey> for AddressHistoryDbKey {
+    type Error = ParseDbKeyError;
+    fn try_from(key: StringKey) -> Result<Self, ParseDbKeyError> {
+        let splitted = key.0.split('-').collect::<Vec<_>>();
+        if splitted.len() != 5 {
+            return Err(ParseDbKeyError::Invalid);
+        }
+        let address: Address = splitted[1].parse().map_err(|_| ParseDbKeyError::Invalid)?;
+        let height = u64::MAX
+            - u64::from_str_radix(splitted[2], 16).map_err(|_| ParseDbKeyError::Invalid)?;
+        let index = u32::MAX
+            - u32::from_str_radix(splitted[3], 16).map_err(|_| ParseDbKeyError::Invalid)?;
+        let tx_hash = <Hasher as Hash>::Output::try_from(
+            hex::decode(splitted[4]).map_err(|_| ParseDbKeyError::Invalid)?,
+        )
+        .map_err(|_| ParseDbKeyError::Invalid)?;
+        Ok(AddressHistoryDbKey {
+            address,
+            height,
+            index,
+            tx_hash,
+        })
+    }
+}
+impl AddressHistoryDbKey {
+    pub fn prefix(address: &Address) -> String {
+        format!("AHI-{}", address)
+    }
+}
+
+pub fn tx_location(tx_hash: &<Hasher as Hash>::Output) -> StringKey {
+    format!("TXL-{}", hex::encode(tx_hash)).into()
+}
+
+pub fn tx_history_since() -> StringKey {
+    "THS".into()
+}
+
+pub fn tx_receipt(tx_hash: &<Hasher as Hash>::Output) -> StringKey {
+    format!("TXR-{}", hex::encode(tx_hash)).into()
+}
+
 pub fn delegate(delegator: &Address, delegatee: &Address) -> StringKey {
     format!("DEL-{}-{}", delegator, delegatee).into()
 }
 
+/// Prefix of the unbondings of all delegatees
+pub fn unbondings_prefix() -> String {
+    "UBD-".into()
+}
+
+pub fn unbonding_prefix(delegatee: &Address) -> String {
+    format!("{}{}-", unbondings_prefix(), delegatee)
+}
+
+pub fn unbonding(delegator: &Address, delegatee: &Address) -> StringKey {
+    format!("{}{}", unbonding_prefix(delegatee), delegator).into()
+}
+
+pub fn epoch_stakers(epoch: u32) -> StringKey {
+    format!("EPS-{:010}", epoch).into()
+}
+
+pub fn slashed(validator: &Address, epoch: u32, slot: u32) -> StringKey {
+    format!("SLS-{}-{}-{}", validator, epoch, slot).into()
+}
+
+pub fn validator_reward_prefix(validator: &Address) -> String {
+    format!("VRW-{}-", validator)
+}
+
+pub fn validator_reward(validator: &Address, epoch: u32) -> StringKey {
+    format!("{}{:010}", validator_reward_prefix(validator), epoch).into()
+}
+
+pub fn delegator_reward_prefix(delegator: &Address) -> String {
+    format!("DRW-{}-", delegator)
+}
+
+pub fn delegator_reward(delegator: &Address, epoch: u32, validator: &Address) -> StringKey {
+    format!(
+        "{}{:010}-{}",
+        delegator_reward_prefix(delegator),
+        epoch,
+        validator
+    )
+    .into()
+}
+
+pub fn burned(token_id: TokenId) -> StringKey {
+    format!("BRN-{}", token_id).into()
+}
+
+pub fn minted(token_id: TokenId) -> StringKey {
+    format!("MNT-{}", token_id).into()
+}
+
+pub fn allowance(owner: &Address, spender: &Address, token_id: TokenId) -> StringKey {
+    format!("ALW-{}-{}-{}", owner, spender, token_id).into()
+}
+
 pub fn account_balance(address: &Address, token_id: TokenId) -> StringKey {
     format!("ACB-{}-{}", address, token_id).into()
 }
@@ -197,10 +316,39 @@ pub fn token(token_id: &TokenId) -> StringKey {
     format!("TKN-{}", token_id).into()
 }
 
+pub fn multisig_policy(address: &Address) -> StringKey {
+    format!("MSP-{}", address).into()
+}
+
+pub fn vesting_prefix() -> String {
+    "VST-".into()
+}
+
+pub fn vesting(vesting_id: &VestingId) -> StringKey {
+    format!("{}{}", vesting_prefix(), vesting_id).into()
+}
+
+/// Prefix of the beneficiary indices of all vestings
+pub fn vesting_index_prefix() -> String {
+    "VSB-".into()
+}
+
+pub fn vesting_beneficiary_prefix(beneficiary: &Address) -> String {
+    format!("{}{}-", vesting_index_prefix(), beneficiary)
+}
+
+pub fn vesting_beneficiary(beneficiary: &Address, vesting_id: &VestingId) -> StringKey {
+    format!("{}{}", vesting_beneficiary_prefix(beneficiary), vesting_id).into()
+}
+
 pub fn contract_updates() -> StringKey {
     "CUP".into()
 }
 
+pub fn block_contract_updates(index: u64) -> StringKey {
+    format!("BCU-{:010}", index).into()
+}
+
 pub fn local_prefix(contract_id: &ContractId) -> String {
     format!("S-{}", contract_id)
 }
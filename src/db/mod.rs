@@ -1,8 +1,9 @@
 pub mod keys;
 
-use crate::blockchain::{ZkBlockchainPatch, ZkCompressedStateChange};
+use crate::blockchain::{TransactionReceipt, ZkBlockchainPatch, ZkCompressedStateChange};
 use crate::core::{
-    hash::Hash, Amount, Block, ContractAccount, ContractId, Delegate, Hasher, Header, Staker, Token,
+    hash::Hash, Amount, Block, ContractAccount, ContractId, Delegate, DelegatorReward, EpochStaker,
+    Hasher, Header, MultisigPolicy, Staker, Token, Unbonding, ValidatorReward, VestingAccount,
 };
 use crate::crypto::merkle::MerkleTree;
 use crate::zk::{
@@ -94,7 +95,11 @@ gen_try_into!(
     u128,
     usize,
     Delegate,
+    Unbonding,
+    ValidatorReward,
+    DelegatorReward,
     Staker,
+    Vec<EpochStaker>,
     ContractAccount,
     Header,
     Block,
@@ -112,7 +117,11 @@ gen_try_into!(
     ZkDataPairs,
     ZkDeltaPairs,
     Token,
+    MultisigPolicy,
+    VestingAccount,
     Amount,
+    TransactionReceipt,
+    (u64, u32),
     ()
 );
 gen_from!(
@@ -121,7 +130,11 @@ gen_from!(
     u128,
     usize,
     Delegate,
+    Unbonding,
+    ValidatorReward,
+    DelegatorReward,
     Staker,
+    Vec<EpochStaker>,
     ContractAccount,
     Header,
     &Block,
@@ -139,7 +152,11 @@ gen_from!(
     &ZkDataPairs,
     &ZkDeltaPairs,
     &Token,
+    &MultisigPolicy,
+    &VestingAccount,
     Amount,
+    &TransactionReceipt,
+    (u64, u32),
     ()
 );
 
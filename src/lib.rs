@@ -5,6 +5,7 @@ pub mod blockchain;
 
 pub mod common;
 pub mod config;
+pub mod contract;
 pub mod core;
 pub mod crypto;
 pub mod db;
@@ -59,6 +59,9 @@ pub struct MpnWorkPool {
 }
 
 impl MpnWorkPool {
+    pub fn num_works(&self) -> usize {
+        self.works.len()
+    }
     pub fn remaining_works(&self) -> HashMap<usize, MpnWork> {
         let mut remaining = self.works.clone();
         for solved in self.solutions.keys() {
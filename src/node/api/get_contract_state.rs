@@ -0,0 +1,23 @@
+use super::messages::{GetContractStateRequest, GetContractStateResponse, InputError};
+use super::{NodeContext, NodeError};
+use crate::blockchain::Blockchain;
+use crate::db::KvStore;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn get_contract_state<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: GetContractStateRequest,
+) -> Result<GetContractStateResponse, NodeError> {
+    let context = context.read().await;
+    let contract_id = req.contract_id.parse().map_err(|_| InputError::Invalid)?;
+    let locator = req.locator.parse().map_err(|_| InputError::Invalid)?;
+    let (value, proof) = context.blockchain.prove_state(contract_id, locator)?;
+    Ok(GetContractStateResponse {
+        value,
+        proof,
+        state_model: context.blockchain.get_contract(contract_id)?.state_model,
+        account: context.blockchain.get_contract_account(contract_id)?,
+        height: context.blockchain.get_height()?,
+    })
+}
@@ -0,0 +1,16 @@
+use super::messages::{GetEpochStakersRequest, GetEpochStakersResponse};
+use super::{NodeContext, NodeError};
+use crate::blockchain::Blockchain;
+use crate::db::KvStore;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn get_epoch_stakers<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: GetEpochStakersRequest,
+) -> Result<GetEpochStakersResponse, NodeError> {
+    let context = context.read().await;
+    Ok(GetEpochStakersResponse {
+        stakers: context.blockchain.get_epoch_stakers(req.epoch)?,
+    })
+}
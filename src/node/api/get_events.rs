@@ -0,0 +1,122 @@
+use super::messages::{EventTopic, GetEventsRequest, GetEventsResponse, InputError};
+use super::EventBus;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Maximum number of seconds a long-polling request is kept open
+const MAX_TIMEOUT: u32 = 60;
+
+/// A validated `GetEventsRequest`, so that bad requests are rejected before
+/// the long-polling starts
+pub struct EventsQuery {
+    since: u64,
+    timeout: Duration,
+    topics: Option<Vec<EventTopic>>,
+}
+
+impl TryFrom<GetEventsRequest> for EventsQuery {
+    type Error = InputError;
+    fn try_from(req: GetEventsRequest) -> Result<Self, Self::Error> {
+        let topics = req
+            .topics
+            .map(|topics| {
+                topics
+                    .split(',')
+                    .map(|t| t.trim().parse())
+                    .collect::<Result<Vec<EventTopic>, _>>()
+            })
+            .transpose()?;
+        Ok(Self {
+            since: req.since,
+            timeout: Duration::from_secs(
+                std::cmp::min(req.timeout.unwrap_or(0), MAX_TIMEOUT) as u64
+            ),
+            topics,
+        })
+    }
+}
+
+pub async fn get_events(events: Arc<EventBus>, query: EventsQuery) -> GetEventsResponse {
+    let (events, next) = events
+        .wait(query.since, query.topics.as_deref(), query.timeout)
+        .await;
+    GetEventsResponse { events, next }
+}
+
+#[cfg(test)]
+use super::tests::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::Blockchain;
+    use crate::client::messages::NodeEvent;
+    use crate::core::Money;
+    use crate::node::TxBuilder;
+
+    #[tokio::test]
+    async fn test_get_events() {
+        let ctx = test_context();
+        let events = ctx.read().await.events.clone();
+        let (_, since) = events.since(0, None);
+
+        let abc = TxBuilder::new(&Vec::from("ABC"));
+        let tx = abc.create_transaction(
+            "".into(),
+            abc.get_address(),
+            Money::ziesha(10),
+            Money::ziesha(0),
+            1,
+        );
+        ctx.write()
+            .await
+            .mempool_add_tx(false, tx.clone().into())
+            .unwrap();
+        // Adding the same tx again is not a new admission
+        ctx.write().await.mempool_add_tx(false, tx.into()).unwrap();
+
+        let resp = get_events(
+            events.clone(),
+            GetEventsRequest {
+                since,
+                timeout: None,
+                topics: Some("tx".into()),
+            }
+            .try_into()
+            .unwrap(),
+        )
+        .await;
+        assert_eq!(resp.events.len(), 1);
+        assert!(matches!(resp.events[0].event, NodeEvent::NewTransaction(_)));
+
+        // Rolling back the chain is announced
+        {
+            let mut ctx = ctx.write().await;
+            ctx.on_update().unwrap();
+            ctx.blockchain.rollback().unwrap();
+            ctx.on_update().unwrap();
+        }
+        let resp = get_events(
+            events,
+            GetEventsRequest {
+                since: resp.next,
+                timeout: None,
+                topics: Some("block".into()),
+            }
+            .try_into()
+            .unwrap(),
+        )
+        .await;
+        assert!(matches!(
+            resp.events.last().unwrap().event,
+            NodeEvent::Rollback { height: 100 }
+        ));
+
+        assert!(EventsQuery::try_from(GetEventsRequest {
+            since: 0,
+            timeout: None,
+            topics: Some("unknown".into()),
+        })
+        .is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use super::messages::{GetExplorerStakersRequest, GetExplorerStakersResponse};
 use super::{NodeContext, NodeError};
 use crate::blockchain::Blockchain;
+use crate::client::explorer::ExplorerStaker;
 use crate::node::KvStore;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -10,10 +11,25 @@ pub async fn get_explorer_stakers<K: KvStore, B: Blockchain<K>>(
     _req: GetExplorerStakersRequest,
 ) -> Result<GetExplorerStakersResponse, NodeError> {
     let context = context.read().await;
-    let current = context.blockchain.get_stakers()?;
-    Ok(GetExplorerStakersResponse {
-        current: current.iter().map(|b| b.into()).collect(),
-    })
+    let apy = context.blockchain.get_staking_apy()?;
+    let (epoch, _) = context
+        .blockchain
+        .epoch_slot(context.blockchain.get_tip()?.proof_of_stake.timestamp);
+    let mut current = Vec::new();
+    for (addr, stake) in context.blockchain.get_stakers()? {
+        let commision = context
+            .blockchain
+            .get_staker(addr.clone())?
+            .map(|s| s.commision_at(epoch))
+            .unwrap_or_default();
+        current.push(ExplorerStaker {
+            pub_key: addr.to_string(),
+            stake: stake.into(),
+            commision,
+            apy: apy * (u8::MAX - commision) as f64 / u8::MAX as f64,
+        });
+    }
+    Ok(GetExplorerStakersResponse { current, apy })
 }
 
 #[cfg(test)]
@@ -25,7 +41,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_explorer_stakers() {
-        let expected = "GetExplorerStakersResponse { current: [ExplorerStaker { pub_key: \"ed062ef0fde01e8544dad7e8c6541c04122e1d70e6b5e89f128a0cfbff617f7cb3\", stake: 25 }, ExplorerStaker { pub_key: \"ed2a141799ef60019f6254aaffc57ffd9b693b8ea4156a4c08965e42cfec26dc6b\", stake: 25 }, ExplorerStaker { pub_key: \"ed6e95016e0a3d299a6e761921da491da1f27189e8a340dfae212daa629853357b\", stake: 25 }] }";
+        let expected = "GetExplorerStakersResponse { current: [ExplorerStaker { pub_key: \"ed062ef0fde01e8544dad7e8c6541c04122e1d70e6b5e89f128a0cfbff617f7cb3\", stake: 25, commision: 12, apy: 1.601168845818272e18 }, ExplorerStaker { pub_key: \"ed2a141799ef60019f6254aaffc57ffd9b693b8ea4156a4c08965e42cfec26dc6b\", stake: 25, commision: 12, apy: 1.601168845818272e18 }, ExplorerStaker { pub_key: \"ed6e95016e0a3d299a6e761921da491da1f27189e8a340dfae212daa629853357b\", stake: 25, commision: 12, apy: 1.601168845818272e18 }], apy: 1.6802389122784335e18 }";
         let ctx = test_context();
         let resp = get_explorer_stakers(ctx.clone(), GetExplorerStakersRequest {})
             .await
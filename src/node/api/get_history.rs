@@ -0,0 +1,75 @@
+use super::messages::{GetHistoryRequest, GetHistoryResponse, HistoryEntry};
+use super::{NodeContext, NodeError};
+use crate::blockchain::{Blockchain, BlockchainError};
+use crate::db::KvStore;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn get_history<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: GetHistoryRequest,
+) -> Result<GetHistoryResponse, NodeError> {
+    let context = context.read().await;
+    let mut history = Vec::new();
+    for (height, index, tx_hash) in
+        context
+            .blockchain
+            .get_tx_history(req.address.parse()?, req.page, req.page_size)?
+    {
+        history.push(HistoryEntry {
+            height,
+            index,
+            tx_hash: hex::encode(tx_hash),
+            receipt: context
+                .blockchain
+                .get_tx_receipt(tx_hash)?
+                .ok_or(BlockchainError::Inconsistency)?,
+        });
+    }
+    Ok(GetHistoryResponse {
+        indexed_since: context.blockchain.get_tx_history_since()?,
+        history,
+    })
+}
+
+#[cfg(test)]
+use super::tests::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::TxBuilder;
+
+    #[tokio::test]
+    async fn test_get_history() {
+        let ctx = test_context();
+        let abc_addr = TxBuilder::new(&Vec::from("ABC")).get_address();
+        let resp = get_history(
+            ctx.clone(),
+            GetHistoryRequest {
+                address: abc_addr.to_string(),
+                page: 0,
+                page_size: 10,
+            },
+        )
+        .await
+        .unwrap();
+        // Only the genesis funding transaction has touched ABC
+        assert_eq!(resp.indexed_since, 0);
+        assert_eq!(resp.history.len(), 1);
+        assert_eq!(resp.history[0].height, 0);
+        assert_eq!(resp.history[0].receipt.height, 0);
+
+        let resp = get_history(
+            ctx.clone(),
+            GetHistoryRequest {
+                address: abc_addr.to_string(),
+                page: 1,
+                page_size: 10,
+            },
+        )
+        .await
+        .unwrap();
+        assert!(resp.history.is_empty());
+    }
+}
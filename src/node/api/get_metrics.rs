@@ -0,0 +1,137 @@
+use super::{NodeContext, NodeError};
+use crate::blockchain::Blockchain;
+use crate::db::KvStore;
+use crate::node::metrics::PrometheusWriter;
+use crate::zk::{POSEIDON_CACHE_HITS, POSEIDON_CACHE_MISSES};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn get_metrics<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+) -> Result<String, NodeError> {
+    let context = context.read().await;
+    let now = context.local_timestamp();
+    let mut w = PrometheusWriter::default();
+
+    w.gauge(
+        "bazuka_height",
+        "Height of the local chain",
+        context.blockchain.get_height()?,
+    );
+    w.gauge(
+        "bazuka_outdated_states",
+        "Number of contracts with outdated states",
+        context.blockchain.get_outdated_contracts()?.len(),
+    );
+
+    let heartbeats = context.metrics.heartbeats();
+    w.labeled_summary(
+        "bazuka_heartbeat_duration_seconds",
+        "Time spent running heartbeats",
+        "heartbeat",
+        &heartbeats
+            .iter()
+            .map(|(name, summary)| (*name, summary))
+            .collect::<Vec<_>>(),
+    );
+    w.summary(
+        "bazuka_block_apply_duration_seconds",
+        "Time spent applying blocks",
+        &context.metrics.block_apply(),
+    );
+
+    let mempool = &context.mempool;
+    w.labeled_gauge(
+        "bazuka_mempool_size",
+        "Number of transactions in the mempool",
+        "kind",
+        &[
+            ("transaction_and_delta", mempool.tx_deltas().count()),
+            ("mpn_deposit", mempool.mpn_deposits().count()),
+            ("mpn_withdraw", mempool.mpn_withdraws().count()),
+            ("mpn_transaction", mempool.mpn_txs().count()),
+        ],
+    );
+    w.gauge(
+        "bazuka_mempool_rejected",
+        "Number of remembered rejected transactions",
+        mempool.rejected_len(),
+    );
+
+    w.labeled_gauge(
+        "bazuka_peers",
+        "Number of known peers",
+        "state",
+        &[
+            ("node", context.peer_manager.node_count()),
+            ("candidate", context.peer_manager.candidate_count()),
+            ("punished", context.peer_manager.punished_count(now)),
+        ],
+    );
+    w.labeled_counter(
+        "bazuka_dropped_requests_total",
+        "Number of incoming requests dropped",
+        "reason",
+        &[
+            ("firewall", context.metrics.firewall_drops()),
+            ("punished", context.metrics.punished_drops()),
+        ],
+    );
+
+    let (works, remaining) = context
+        .mpn_work_pool
+        .as_ref()
+        .map(|pool| (pool.num_works(), pool.remaining_works().len()))
+        .unwrap_or_default();
+    w.gauge("bazuka_mpn_works", "Number of MPN works in the pool", works);
+    w.gauge(
+        "bazuka_mpn_remaining_works",
+        "Number of MPN works waiting for a proof",
+        remaining,
+    );
+
+    let hits = POSEIDON_CACHE_HITS.load(Ordering::Relaxed);
+    let misses = POSEIDON_CACHE_MISSES.load(Ordering::Relaxed);
+    w.counter(
+        "bazuka_poseidon_cache_hits_total",
+        "Poseidon cache hits",
+        hits,
+    );
+    w.counter(
+        "bazuka_poseidon_cache_misses_total",
+        "Poseidon cache misses",
+        misses,
+    );
+    w.gauge(
+        "bazuka_poseidon_cache_hit_rate",
+        "Ratio of Poseidon hashes served from the cache",
+        if hits + misses > 0 {
+            hits as f64 / (hits + misses) as f64
+        } else {
+            0.0
+        },
+    );
+
+    Ok(w.finish())
+}
+
+#[cfg(test)]
+use super::tests::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_metrics() {
+        let ctx = test_context();
+        ctx.read().await.metrics.firewall_dropped();
+        let metrics = get_metrics(ctx).await.unwrap();
+        assert!(metrics.contains("\nbazuka_height 101\n"));
+        assert!(metrics.contains("bazuka_mempool_size{kind=\"mpn_deposit\"} 0\n"));
+        assert!(metrics.contains("bazuka_block_apply_duration_seconds_count 0\n"));
+        assert!(metrics.contains("# TYPE bazuka_dropped_requests_total counter\n"));
+        assert!(metrics.contains("bazuka_dropped_requests_total{reason=\"firewall\"} 1\n"));
+    }
+}
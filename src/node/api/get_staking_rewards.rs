@@ -0,0 +1,69 @@
+use super::messages::{GetStakingRewardsRequest, GetStakingRewardsResponse};
+use super::{NodeContext, NodeError};
+use crate::blockchain::Blockchain;
+use crate::core::Address;
+use crate::db::KvStore;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn get_staking_rewards<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: GetStakingRewardsRequest,
+) -> Result<GetStakingRewardsResponse, NodeError> {
+    let context = context.read().await;
+    let address: Address = req.address.parse()?;
+    Ok(GetStakingRewardsResponse {
+        validator_rewards: context.blockchain.get_validator_rewards(address.clone())?,
+        delegator_rewards: context.blockchain.get_delegator_rewards(address)?,
+    })
+}
+
+#[cfg(test)]
+use super::tests::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::TxBuilder;
+
+    #[tokio::test]
+    async fn test_get_staking_rewards() {
+        let validator = TxBuilder::new(&Vec::from("VALIDATOR"));
+        let delegator = TxBuilder::new(&Vec::from("DELEGATOR"));
+        let ctx = test_context();
+
+        let resp = get_staking_rewards(
+            ctx.clone(),
+            GetStakingRewardsRequest {
+                address: validator.get_address().to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(!resp.validator_rewards.is_empty());
+        assert!(resp.delegator_rewards.is_empty());
+
+        let resp = get_staking_rewards(
+            ctx.clone(),
+            GetStakingRewardsRequest {
+                address: delegator.get_address().to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(resp.validator_rewards.is_empty());
+        assert_eq!(
+            resp.delegator_rewards.len(),
+            ctx.read()
+                .await
+                .blockchain
+                .get_validator_rewards(validator.get_address())
+                .unwrap()
+                .len()
+        );
+        assert!(resp
+            .delegator_rewards
+            .iter()
+            .all(|r| r.validator == validator.get_address()));
+    }
+}
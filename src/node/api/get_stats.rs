@@ -16,6 +16,7 @@ pub async fn get_stats<K: KvStore, B: Blockchain<K>>(
         social_profiles: context.social_profiles.clone(),
         address: context.validator_wallet.get_address().to_string(),
         height: context.blockchain.get_height()?,
+        pruned_height: context.blockchain.get_pruned_height()?,
         nodes: context.peer_manager.node_count(),
         next_reward: context.blockchain.next_reward()?,
         timestamp: ts,
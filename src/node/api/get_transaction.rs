@@ -0,0 +1,134 @@
+use super::messages::{
+    GetTransactionRequest, GetTransactionResponse, InputError, TransactionStatus,
+};
+use super::{NodeContext, NodeError};
+use crate::blockchain::Blockchain;
+use crate::core::{hash::Hash, Hasher};
+use crate::db::KvStore;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn get_transaction<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: GetTransactionRequest,
+) -> Result<GetTransactionResponse, NodeError> {
+    let context = context.read().await;
+    let tx_hash = <Hasher as Hash>::Output::try_from(
+        hex::decode(&req.tx_hash).map_err(|_| InputError::Invalid)?,
+    )
+    .map_err(|_| InputError::Invalid)?;
+
+    if let Some(loc) = context.blockchain.get_transaction(tx_hash)? {
+        return Ok(GetTransactionResponse {
+            status: TransactionStatus::Confirmed {
+                height: loc.height,
+                index: loc.index,
+                block_hash: hex::encode(loc.block_hash),
+                timestamp: loc.timestamp,
+                confirmations: loc.confirmations,
+            },
+            tx: loc.tx.as_ref().map(|tx| tx.into()),
+        });
+    }
+    if let Some((tx_delta, stats)) = context.mempool.get_tx(&tx_hash) {
+        return Ok(GetTransactionResponse {
+            status: TransactionStatus::Pending {
+                stats: stats.clone(),
+            },
+            tx: Some((&tx_delta.tx).into()),
+        });
+    }
+    if let Some((tx_delta, stats)) = context.mempool.get_rejected_tx(&tx_hash) {
+        return Ok(GetTransactionResponse {
+            status: TransactionStatus::Rejected {
+                stats: stats.clone(),
+            },
+            tx: Some((&tx_delta.tx).into()),
+        });
+    }
+    Ok(GetTransactionResponse {
+        status: TransactionStatus::NotFound,
+        tx: None,
+    })
+}
+
+#[cfg(test)]
+use super::tests::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Money;
+    use crate::node::TxBuilder;
+
+    #[tokio::test]
+    async fn test_get_transaction() {
+        let ctx = test_context();
+        let genesis_tx = ctx.read().await.blockchain.get_block(0).unwrap().body[2].clone();
+        let resp = get_transaction(
+            ctx.clone(),
+            GetTransactionRequest {
+                tx_hash: hex::encode(genesis_tx.hash()),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            resp.status,
+            TransactionStatus::Confirmed {
+                height: 0,
+                index: 2,
+                confirmations: 101,
+                ..
+            }
+        ));
+
+        let abc = TxBuilder::new(&Vec::from("ABC"));
+        let pending_tx = abc.create_transaction(
+            "".into(),
+            abc.get_address(),
+            Money::ziesha(10),
+            Money::ziesha(0),
+            1,
+        );
+        let resp = get_transaction(
+            ctx.clone(),
+            GetTransactionRequest {
+                tx_hash: hex::encode(pending_tx.tx.hash()),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(matches!(resp.status, TransactionStatus::NotFound));
+
+        ctx.write()
+            .await
+            .mempool_add_tx(false, pending_tx.clone().into())
+            .unwrap();
+        let resp = get_transaction(
+            ctx.clone(),
+            GetTransactionRequest {
+                tx_hash: hex::encode(pending_tx.tx.hash()),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(matches!(resp.status, TransactionStatus::Pending { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_get_transaction_invalid_hash() {
+        let ctx = test_context();
+        let resp = get_transaction(
+            ctx.clone(),
+            GetTransactionRequest {
+                tx_hash: "abcd".into(),
+            },
+        )
+        .await;
+        assert!(matches!(
+            resp,
+            Err(NodeError::InputError(InputError::Invalid))
+        ));
+    }
+}
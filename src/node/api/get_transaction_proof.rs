@@ -0,0 +1,23 @@
+use super::messages::{GetTransactionProofRequest, GetTransactionProofResponse, TransactionProof};
+use super::{NodeContext, NodeError};
+use crate::blockchain::Blockchain;
+use crate::db::KvStore;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn get_transaction_proof<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: GetTransactionProofRequest,
+) -> Result<GetTransactionProofResponse, NodeError> {
+    let context = context.read().await;
+    Ok(GetTransactionProofResponse {
+        proof: context
+            .blockchain
+            .prove_transaction(req.tx_hash)?
+            .map(|p| TransactionProof {
+                height: p.height,
+                tx: p.tx,
+                proof: p.proof,
+            }),
+    })
+}
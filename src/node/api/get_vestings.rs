@@ -0,0 +1,21 @@
+use super::messages::{GetVestingsRequest, GetVestingsResponse};
+use super::{NodeContext, NodeError};
+use crate::blockchain::Blockchain;
+use crate::db::KvStore;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+pub async fn get_vestings<K: KvStore, B: Blockchain<K>>(
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: GetVestingsRequest,
+) -> Result<GetVestingsResponse, NodeError> {
+    let context = context.read().await;
+    Ok(GetVestingsResponse {
+        vestings: context
+            .blockchain
+            .get_vestings(req.address.parse()?)?
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    })
+}
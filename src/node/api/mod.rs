@@ -1,4 +1,4 @@
-use super::{promote_block, promote_validator_claim, NodeContext, NodeError};
+use super::{promote_block, promote_validator_claim, EventBus, NodeContext, NodeError};
 
 use crate::client::messages;
 
@@ -38,6 +38,12 @@ mod get_balance;
 pub use get_balance::*;
 mod get_token;
 pub use get_token::*;
+mod get_contract_state;
+pub use get_contract_state::*;
+mod get_transaction_proof;
+pub use get_transaction_proof::*;
+mod get_epoch_stakers;
+pub use get_epoch_stakers::*;
 mod post_validator_claim;
 pub use post_validator_claim::*;
 mod get_explorer_stakers;
@@ -48,12 +54,26 @@ mod post_mpn_solution;
 pub use post_mpn_solution::*;
 mod get_delegations;
 pub use get_delegations::*;
+mod get_vestings;
+pub use get_vestings::*;
+mod get_staking_rewards;
+pub use get_staking_rewards::*;
 mod post_mpn_worker;
 pub use post_mpn_worker::*;
 mod get_explorer_mempool;
 pub use get_explorer_mempool::*;
 mod get_check_tx;
 pub use get_check_tx::*;
+mod get_history;
+pub use get_history::*;
+mod get_transaction;
+pub use get_transaction::*;
+mod get_events;
+pub use get_events::*;
+mod rpc;
+pub use rpc::*;
+mod get_metrics;
+pub use get_metrics::*;
 #[cfg(test)]
 mod generate_block;
 #[cfg(test)]
@@ -111,7 +131,14 @@ mod tests {
             }),
             mpn_workers: Default::default(),
             mpn_work_pool: None,
-            mempool: Mempool::new(Amount(1_000_000_000)),
+            mempool: Mempool::new(
+                Amount(1_000_000_000),
+                opts.mempool_max_size,
+                opts.fee_token_prices.clone(),
+            ),
+            events: Arc::new(EventBus::new(opts.max_events)),
+            announced_headers: Default::default(),
+            metrics: Default::default(),
             blockchain,
             validator_wallet: validator_wallet.clone(),
             user_wallet: user_wallet.clone(),
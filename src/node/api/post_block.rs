@@ -21,8 +21,7 @@ pub async fn post_block<K: KvStore, B: Blockchain<K>>(
         {
             return Err(NodeError::BlockTimestampInFuture);
         }
-        ctx.blockchain
-            .extend(req.block.header.number, &[req.block.clone()])?;
+        ctx.extend_blockchain(req.block.header.number, &[req.block.clone()], false)?;
         ctx.on_update()?;
         ctx.blockchain.update_states(&req.patch)?;
         drop(ctx);
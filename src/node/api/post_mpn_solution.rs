@@ -18,6 +18,9 @@ pub async fn post_mpn_solution<K: KvStore, B: Blockchain<K>>(
             }
         }
         println!("Got {} accepted SNARK proofs!", accepted);
+        if accepted > 0 {
+            ctx.publish_mpn_work_pool();
+        }
         Ok(PostMpnSolutionResponse { accepted })
     } else {
         Ok(PostMpnSolutionResponse { accepted: 0 })
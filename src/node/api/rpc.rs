@@ -0,0 +1,316 @@
+use super::messages::*;
+use super::*;
+use crate::blockchain::Blockchain;
+use crate::db::KvStore;
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+fn error_code(err: &NodeError) -> i64 {
+    match err {
+        NodeError::BlockchainError(_) | NodeError::StatesOutdated => RPC_BLOCKCHAIN_ERROR,
+        NodeError::JsonError(_)
+        | NodeError::QueryStringError(_)
+        | NodeError::BincodeError(_)
+        | NodeError::Utf8Error(_)
+        | NodeError::AddrParseError(_)
+        | NodeError::AccountParseAddressError(_)
+        | NodeError::MpnAccountParseAddressError(_)
+        | NodeError::GeneralParseAddressError(_)
+        | NodeError::TokenIdParseError(_)
+        | NodeError::InputError(_) => RPC_INVALID_PARAMS,
+        NodeError::SignatureRequired
+        | NodeError::InvalidSignatureHeader
+        | NodeError::WrongNetwork
+        | NodeError::HandshakeClientMismatch
+        | NodeError::SenderIpUnknown => RPC_FORBIDDEN,
+        _ => RPC_INTERNAL_ERROR,
+    }
+}
+
+impl From<NodeError> for RpcError {
+    fn from(err: NodeError) -> Self {
+        Self {
+            code: error_code(&err),
+            message: err.to_string(),
+        }
+    }
+}
+
+fn rpc_error(code: i64, message: &str) -> RpcError {
+    RpcError {
+        code,
+        message: message.into(),
+    }
+}
+
+async fn call<K: KvStore, B: Blockchain<K>>(
+    client: Option<SocketAddr>,
+    is_local: bool,
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    method: &str,
+    params: Value,
+) -> Result<Value, RpcError> {
+    // Params of a method are the fields of its request type
+    let params = if params.is_null() {
+        Value::Object(Default::default())
+    } else {
+        params
+    };
+    macro_rules! handle {
+        ($handler:expr) => {
+            serde_json::to_value(
+                $handler(
+                    serde_json::from_value(params)
+                        .map_err(|e| rpc_error(RPC_INVALID_PARAMS, &e.to_string()))?,
+                )
+                .await?,
+            )
+            .map_err(NodeError::from)?
+        };
+    }
+    let ctx = || Arc::clone(&context);
+    Ok(match method {
+        "get_stats" => handle!(|req| get_stats(ctx(), req)),
+        "get_debug_data" => handle!(|req| get_debug_data(ctx(), req)),
+        "get_account" => handle!(|req| get_account(ctx(), req)),
+        "get_delegations" => handle!(|req| get_delegations(ctx(), req)),
+        "get_vestings" => handle!(|req| get_vestings(ctx(), req)),
+        "get_staking_rewards" => handle!(|req| get_staking_rewards(ctx(), req)),
+        "get_balance" => handle!(|req| get_balance(ctx(), req)),
+        "get_mpn_account" => handle!(|req| get_mpn_account(ctx(), req)),
+        "get_peers" => handle!(|req| get_peers(client, ctx(), req)),
+        "post_peer" => handle!(|req| post_peer(client, ctx(), req)),
+        "get_explorer_stakers" => handle!(|req| get_explorer_stakers(ctx(), req)),
+        "get_history" => handle!(|req| get_history(ctx(), req)),
+        "get_transaction" => handle!(|req| get_transaction(ctx(), req)),
+        "get_token" => handle!(|req| get_token(ctx(), req)),
+        "get_contract_state" => handle!(|req| get_contract_state(ctx(), req)),
+        "get_transaction_proof" => handle!(|req| get_transaction_proof(ctx(), req)),
+        "get_epoch_stakers" => handle!(|req| get_epoch_stakers(ctx(), req)),
+        "transact" => handle!(|req| transact(client, ctx(), req)),
+        "get_check_tx" => handle!(|req| get_check_tx(ctx(), req)),
+        "get_explorer_blocks" => handle!(|req| get_explorer_blocks(ctx(), req)),
+        "get_explorer_mpn_accounts" => {
+            handle!(|req| get_explorer_mpn_accounts(ctx(), req))
+        }
+        "get_explorer_mempool" => handle!(|req| get_explorer_mempool(ctx(), req)),
+        "get_headers" => handle!(|req| get_headers(ctx(), req)),
+        "get_blocks" => handle!(|req| get_blocks(ctx(), req)),
+        "post_block" => handle!(|req| post_block(ctx(), req)),
+        "get_states" => handle!(|req| get_states(ctx(), req)),
+        "get_outdated_heights" => handle!(|req| get_outdated_heights(ctx(), req)),
+        "get_mempool" => handle!(|req| get_mempool(ctx(), req)),
+        "post_validator_claim" => handle!(|req| post_validator_claim(ctx(), req)),
+        "get_mpn_work" => handle!(|req| get_mpn_work(ctx(), req)),
+        "post_mpn_solution" => handle!(|req| post_mpn_solution(ctx(), req)),
+        "post_mpn_worker" => handle!(|req| post_mpn_worker(ctx(), req)),
+        "get_events" => {
+            let events = context.read().await.events.clone();
+            // Long-polling is only available through the /events route
+            handle!(|req: GetEventsRequest| async move {
+                let query = EventsQuery::try_from(GetEventsRequest {
+                    timeout: None,
+                    ..req
+                })?;
+                Ok::<_, NodeError>(get_events(events, query).await)
+            })
+        }
+        "shutdown" => {
+            if !is_local {
+                return Err(rpc_error(RPC_FORBIDDEN, "method is only available locally"));
+            }
+            handle!(|req| shutdown(ctx(), req))
+        }
+        _ => {
+            return Err(rpc_error(RPC_METHOD_NOT_FOUND, "method not found"));
+        }
+    })
+}
+
+async fn handle_request<K: KvStore, B: Blockchain<K>>(
+    client: Option<SocketAddr>,
+    is_local: bool,
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    req: Value,
+) -> Option<RpcResponse> {
+    let (result, id) = match serde_json::from_value::<RpcRequest>(req) {
+        Ok(req) => {
+            let result = if req.jsonrpc != "2.0" {
+                Err(rpc_error(
+                    RPC_INVALID_REQUEST,
+                    "unsupported jsonrpc version",
+                ))
+            } else {
+                call(client, is_local, context, &req.method, req.params).await
+            };
+            // Notifications are not answered
+            (result, req.id?)
+        }
+        Err(e) => (
+            Err(rpc_error(RPC_INVALID_REQUEST, &e.to_string())),
+            Value::Null,
+        ),
+    };
+    Some(match result {
+        Ok(result) => RpcResponse {
+            jsonrpc: "2.0".into(),
+            result: Some(result),
+            error: None,
+            id,
+        },
+        Err(error) => RpcResponse {
+            jsonrpc: "2.0".into(),
+            result: None,
+            error: Some(error),
+            id,
+        },
+    })
+}
+
+/// Handles a JSON-RPC 2.0 request (Or a batch of them) and returns the response body
+pub async fn rpc<K: KvStore, B: Blockchain<K>>(
+    client: Option<SocketAddr>,
+    is_local: bool,
+    context: Arc<RwLock<NodeContext<K, B>>>,
+    body: &[u8],
+) -> Result<Vec<u8>, NodeError> {
+    let parse_error = |e: serde_json::Error| RpcResponse {
+        jsonrpc: "2.0".into(),
+        result: None,
+        error: Some(rpc_error(RPC_PARSE_ERROR, &e.to_string())),
+        id: Value::Null,
+    };
+    let resp = match serde_json::from_slice::<Value>(body) {
+        Ok(Value::Array(reqs)) => {
+            if reqs.is_empty() {
+                serde_json::to_value(RpcResponse {
+                    jsonrpc: "2.0".into(),
+                    result: None,
+                    error: Some(rpc_error(RPC_INVALID_REQUEST, "empty batch")),
+                    id: Value::Null,
+                })?
+            } else {
+                let mut resps = Vec::new();
+                for req in reqs {
+                    if let Some(resp) =
+                        handle_request(client, is_local, Arc::clone(&context), req).await
+                    {
+                        resps.push(resp);
+                    }
+                }
+                if resps.is_empty() {
+                    return Ok(vec![]);
+                }
+                serde_json::to_value(resps)?
+            }
+        }
+        Ok(req) => {
+            if let Some(resp) = handle_request(client, is_local, context, req).await {
+                serde_json::to_value(resp)?
+            } else {
+                return Ok(vec![]);
+            }
+        }
+        Err(e) => serde_json::to_value(parse_error(e))?,
+    };
+    Ok(serde_json::to_vec(&resp)?)
+}
+
+#[cfg(test)]
+use super::tests::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn rpc_json(body: &str, is_local: bool) -> Value {
+        let resp = rpc(None, is_local, test_context(), body.as_bytes())
+            .await
+            .unwrap();
+        serde_json::from_slice(&resp).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rpc_call() {
+        let resp = rpc_json(
+            r#"{"jsonrpc": "2.0", "method": "get_stats", "params": {}, "id": 1}"#,
+            true,
+        )
+        .await;
+        assert_eq!(resp["id"], 1);
+        assert_eq!(resp["result"]["height"], 101);
+
+        // Params can be omitted when the request type has no fields
+        let resp = rpc_json(
+            r#"{"jsonrpc": "2.0", "method": "get_stats", "id": 2}"#,
+            true,
+        )
+        .await;
+        assert_eq!(resp["result"]["height"], 101);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_errors() {
+        let resp = rpc_json("{", true).await;
+        assert_eq!(resp["error"]["code"], RPC_PARSE_ERROR);
+
+        let resp = rpc_json(r#"{"jsonrpc": "2.0", "method": "foo", "id": 1}"#, true).await;
+        assert_eq!(resp["error"]["code"], RPC_METHOD_NOT_FOUND);
+
+        let resp = rpc_json(
+            r#"{"jsonrpc": "2.0", "method": "get_account", "params": {"foo": 1}, "id": 1}"#,
+            true,
+        )
+        .await;
+        assert_eq!(resp["error"]["code"], RPC_INVALID_PARAMS);
+
+        let resp = rpc_json(
+            r#"{"jsonrpc": "2.0", "method": "get_account", "params": {"address": "abc"}, "id": 1}"#,
+            true,
+        )
+        .await;
+        assert_eq!(resp["error"]["code"], RPC_INVALID_PARAMS);
+
+        let resp = rpc_json(
+            r#"{"jsonrpc": "2.0", "method": "shutdown", "params": {}, "id": 1}"#,
+            false,
+        )
+        .await;
+        assert_eq!(resp["error"]["code"], RPC_FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_batch() {
+        let resp = rpc_json(
+            r#"[
+                {"jsonrpc": "2.0", "method": "get_stats", "id": 1},
+                {"jsonrpc": "2.0", "method": "get_stats"},
+                {"jsonrpc": "2.0", "method": "foo", "id": 2},
+                {"foo": "bar"}
+            ]"#,
+            true,
+        )
+        .await;
+        let resps = resp.as_array().unwrap();
+        assert_eq!(resps.len(), 3);
+        assert_eq!(resps[0]["id"], 1);
+        assert_eq!(resps[1]["error"]["code"], RPC_METHOD_NOT_FOUND);
+        assert_eq!(resps[2]["error"]["code"], RPC_INVALID_REQUEST);
+
+        let resp = rpc_json("[]", true).await;
+        assert_eq!(resp["error"]["code"], RPC_INVALID_REQUEST);
+
+        // Only notifications
+        let resp = rpc(
+            None,
+            true,
+            test_context(),
+            br#"[{"jsonrpc": "2.0", "method": "get_stats"}]"#,
+        )
+        .await
+        .unwrap();
+        assert!(resp.is_empty());
+    }
+}
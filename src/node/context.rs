@@ -1,14 +1,15 @@
 use super::{
-    Firewall, NodeError, NodeOptions, OutgoingSender, Peer, PeerAddress, PeerManager, Timestamp,
+    EventBus, Firewall, Metrics, NodeError, NodeOptions, OutgoingSender, Peer, PeerAddress,
+    PeerManager, Timestamp,
 };
 use crate::blockchain::{BlockAndPatch, Blockchain, BlockchainError, Mempool};
-use crate::client::messages::{SocialProfiles, ValidatorClaim};
-use crate::core::{GeneralTransaction, Header, MpnAddress, TransactionAndDelta};
+use crate::client::messages::{NodeEvent, SocialProfiles, ValidatorClaim};
+use crate::core::{Block, GeneralTransaction, Header, MpnAddress, TransactionAndDelta};
 use crate::mpn::{MpnWorkPool, MpnWorker};
 use crate::node::KvStore;
 use crate::utils;
 use crate::wallet::TxBuilder;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 
 pub struct NodeContext<K: KvStore, B: Blockchain<K>> {
@@ -31,6 +32,12 @@ pub struct NodeContext<K: KvStore, B: Blockchain<K>> {
 
     pub mempool: Mempool,
 
+    pub events: Arc<EventBus>,
+    /// Latest headers announced through events, used for detecting rollbacks
+    pub announced_headers: VecDeque<Header>,
+
+    pub metrics: Arc<Metrics>,
+
     pub outdated_since: Option<Timestamp>,
     pub banned_headers: HashMap<Header, Timestamp>,
     pub _phantom: std::marker::PhantomData<K>,
@@ -58,11 +65,13 @@ impl<K: KvStore, B: Blockchain<K>> NodeContext<K, B> {
     pub fn get_info(&self) -> Result<Option<Peer>, NodeError> {
         let height = self.blockchain.get_height()?;
         let outdated_states = self.blockchain.get_outdated_contracts()?.len();
+        let pruned_height = self.blockchain.get_pruned_height()?;
         Ok(self.address.map(|address| Peer {
             address,
             height,
             pub_key: self.validator_wallet.get_address(),
             outdated_states,
+            pruned_height,
         }))
     }
 
@@ -89,8 +98,74 @@ impl<K: KvStore, B: Blockchain<K>> NodeContext<K, B> {
         tx: GeneralTransaction,
     ) -> Result<(), BlockchainError> {
         let local_ts = self.local_timestamp();
+        let is_new = !self.mempool.contains(&tx);
         self.mempool
-            .add_tx(&self.blockchain, tx, is_local, local_ts)?;
+            .add_tx(&self.blockchain, tx.clone(), is_local, local_ts)?;
+        if is_new && self.mempool.contains(&tx) {
+            self.events.publish(NodeEvent::NewTransaction((&tx).into()));
+        }
+        Ok(())
+    }
+
+    pub fn publish_mpn_work_pool(&self) {
+        let (works, remaining) = self
+            .mpn_work_pool
+            .as_ref()
+            .map(|pool| (pool.num_works(), pool.remaining_works().len()))
+            .unwrap_or_default();
+        self.events
+            .publish(NodeEvent::MpnWorkPool { works, remaining });
+    }
+
+    fn publish_chain_events(&mut self) -> Result<(), BlockchainError> {
+        let height = self.blockchain.get_height()?;
+
+        // Drop the announced headers that are not on the chain anymore
+        let mut rolled_back = None;
+        while let Some(last) = self.announced_headers.back() {
+            if last.number < height && self.blockchain.get_header(last.number)? == *last {
+                break;
+            }
+            rolled_back = Some(last.number);
+            self.announced_headers.pop_back();
+        }
+        if let Some(height) = rolled_back {
+            self.events.publish(NodeEvent::Rollback { height });
+        }
+
+        let since = match self.announced_headers.back() {
+            Some(last) => last.number + 1,
+            None => rolled_back.unwrap_or_else(|| height.saturating_sub(1)),
+        };
+        for index in since..height {
+            let block = self.blockchain.get_block(index)?;
+            self.events.publish(NodeEvent::NewBlock((&block).into()));
+            self.announced_headers.push_back(block.header);
+        }
+
+        let max_len = self.blockchain.config().max_reorg_depth as usize + 1;
+        while self.announced_headers.len() > max_len {
+            self.announced_headers.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Extends the blockchain, keeping track of the block apply latency.
+    /// Pipelined extension verifies the blocks in parallel before applying them.
+    pub fn extend_blockchain(
+        &mut self,
+        from: u64,
+        blocks: &[Block],
+        pipelined: bool,
+    ) -> Result<(), BlockchainError> {
+        let start = std::time::Instant::now();
+        if pipelined {
+            self.blockchain.import(from, blocks)?;
+        } else {
+            self.blockchain.extend(from, blocks)?;
+        }
+        self.metrics
+            .observe_block_apply(blocks.len() as u64, start.elapsed());
         Ok(())
     }
 
@@ -104,6 +179,7 @@ impl<K: KvStore, B: Blockchain<K>> NodeContext<K, B> {
             self.opts.tx_max_time_alive,
             self.opts.tx_max_time_alive,
         )?;
+        self.publish_chain_events()?;
         Ok(())
     }
 
@@ -127,6 +203,8 @@ impl<K: KvStore, B: Blockchain<K>> NodeContext<K, B> {
                 && claim.verify_signature()
             {
                 self.validator_claim = Some(claim.clone());
+                self.events
+                    .publish(NodeEvent::ValidatorClaim(claim.clone()));
                 log::info!("Address {} is the validator!", claim.address);
                 return Ok(true);
             }
@@ -141,11 +219,20 @@ impl<K: KvStore, B: Blockchain<K>> NodeContext<K, B> {
         let ts = self.network_timestamp();
         let raw_txs: Vec<TransactionAndDelta> =
             self.mempool.tx_deltas().map(|(tx, _)| tx.clone()).collect();
-        match self.blockchain.draft_block(ts, &raw_txs, &wallet, true) {
+        match self.blockchain.draft_block_with_fee_prices(
+            ts,
+            &raw_txs,
+            &wallet,
+            true,
+            &self.opts.fee_token_prices,
+        ) {
             Ok(draft) => {
                 if let Some(draft) = draft {
-                    self.blockchain
-                        .extend(draft.block.header.number, &[draft.block.clone()])?;
+                    self.extend_blockchain(
+                        draft.block.header.number,
+                        &[draft.block.clone()],
+                        false,
+                    )?;
                     self.on_update()?;
                     self.blockchain.update_states(&draft.patch.clone())?;
                     Ok(Some(draft))
@@ -0,0 +1,106 @@
+use crate::client::messages::{EventTopic, IndexedNodeEvent, NodeEvent};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Keeps the latest node events so that clients can long-poll them
+pub struct EventBus {
+    capacity: usize,
+    log: Mutex<(u64, VecDeque<IndexedNodeEvent>)>,
+    notify: Notify,
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            log: Mutex::new((0, VecDeque::new())),
+            notify: Notify::new(),
+        }
+    }
+
+    pub fn publish(&self, event: NodeEvent) {
+        let mut log = self.log.lock().unwrap();
+        let id = log.0;
+        log.0 += 1;
+        log.1.push_back(IndexedNodeEvent { id, event });
+        while log.1.len() > self.capacity {
+            log.1.pop_front();
+        }
+        drop(log);
+        self.notify.notify_waiters();
+    }
+
+    /// Events with ids at or after `since`, and the id to continue from
+    pub fn since(&self, since: u64, topics: Option<&[EventTopic]>) -> (Vec<IndexedNodeEvent>, u64) {
+        let log = self.log.lock().unwrap();
+        let events = log
+            .1
+            .iter()
+            .filter(|e| e.id >= since)
+            .filter(|e| topics.map(|t| t.contains(&e.event.topic())).unwrap_or(true))
+            .cloned()
+            .collect();
+        (events, std::cmp::max(since, log.0))
+    }
+
+    /// Like `since`, but waits up to `timeout` for new events when there are none
+    pub async fn wait(
+        &self,
+        since: u64,
+        topics: Option<&[EventTopic]>,
+        timeout: Duration,
+    ) -> (Vec<IndexedNodeEvent>, u64) {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            // Register before checking, so that no publish is missed in between
+            let notified = self.notify.notified();
+            let (events, next) = self.since(since, topics);
+            if !events.is_empty() || tokio::time::Instant::now() >= deadline {
+                return (events, next);
+            }
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return self.since(since, topics);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_event_bus() {
+        let bus = std::sync::Arc::new(EventBus::new(2));
+        assert_eq!(bus.since(0, None).0.len(), 0);
+
+        for height in 0..3 {
+            bus.publish(NodeEvent::Rollback { height });
+        }
+        // Only the latest events are kept
+        let (events, next) = bus.since(0, None);
+        assert_eq!(events.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(next, 3);
+        assert!(bus.since(0, Some(&[EventTopic::Transaction])).0.is_empty());
+
+        let waiter = {
+            let bus = bus.clone();
+            tokio::spawn(async move { bus.wait(3, None, Duration::from_secs(10)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        bus.publish(NodeEvent::MpnWorkPool {
+            works: 0,
+            remaining: 0,
+        });
+        let (events, next) = waiter.await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(next, 4);
+
+        // Times out with no events
+        let (events, next) = bus.wait(4, None, Duration::from_millis(50)).await;
+        assert!(events.is_empty());
+        assert_eq!(next, 4);
+    }
+}
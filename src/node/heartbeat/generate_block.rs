@@ -20,6 +20,7 @@ pub async fn generate_block<K: KvStore, B: Blockchain<K>>(
                     ctx.mempool_add_tx(true, tx_delta.into())?;
                     if let Some(draft) = ctx.try_produce(wallet)? {
                         ctx.mpn_work_pool = None;
+                        ctx.publish_mpn_work_pool();
                         ctx.validator_claim = None;
                         drop(ctx);
                         promote_block(context, draft).await;
@@ -76,6 +77,7 @@ pub async fn generate_block<K: KvStore, B: Blockchain<K>>(
                 ctx.validator_wallet.clone(),
                 ctx.user_wallet.clone(),
             )?);
+            ctx.publish_mpn_work_pool();
         }
         if let Some(claim) = ctx.validator_claim.clone() {
             println!("You are the validator! Promoting...");
@@ -94,7 +96,9 @@ pub async fn generate_block<K: KvStore, B: Blockchain<K>>(
                 }
             }
         }
-        ctx.mpn_work_pool = None;
+        if ctx.mpn_work_pool.take().is_some() {
+            ctx.publish_mpn_work_pool();
+        }
         if let Some(claim) = ctx.validator_claim.clone() {
             if !ctx.blockchain.is_validator(
                 timestamp,
@@ -27,16 +27,20 @@ pub async fn make_loop<
     F: Fn(&Arc<RwLock<NodeContext<K, B>>>) -> Fut,
 >(
     context: &Arc<RwLock<NodeContext<K, B>>>,
+    name: &'static str,
     func: F,
     interval: Duration,
 ) {
+    let metrics = context.read().await.metrics.clone();
     loop {
         if context.read().await.shutdown {
             break;
         }
+        let start = std::time::Instant::now();
         if let Err(e) = func(context).await {
             log::error!("Heartbeat error: {}", e);
         }
+        metrics.observe_heartbeat(name, start.elapsed());
         tokio::time::sleep(interval).await;
     }
 }
@@ -46,40 +50,57 @@ pub async fn heartbeater<K: KvStore, B: Blockchain<K>>(
 ) -> Result<(), NodeError> {
     let ints = ctx.read().await.opts.heartbeat_intervals.clone();
     tokio::join!(
-        make_loop(&ctx, |ctx| log_info::log_info(ctx.clone()), ints.log_info),
-        make_loop(&ctx, |ctx| refresh::refresh(ctx.clone()), ints.refresh),
         make_loop(
             &ctx,
+            "log_info",
+            |ctx| log_info::log_info(ctx.clone()),
+            ints.log_info
+        ),
+        make_loop(
+            &ctx,
+            "refresh",
+            |ctx| refresh::refresh(ctx.clone()),
+            ints.refresh
+        ),
+        make_loop(
+            &ctx,
+            "sync_peers",
             |ctx| sync_peers::sync_peers(ctx.clone()),
             ints.sync_peers
         ),
         make_loop(
             &ctx,
+            "discover_peers",
             |ctx| discover_peers::discover_peers(ctx.clone()),
             ints.discover_peers
         ),
         make_loop(
             &ctx,
+            "sync_clock",
             |ctx| sync_clock::sync_clock(ctx.clone()),
             ints.sync_clock
         ),
         make_loop(
             &ctx,
+            "sync_blocks",
             |ctx| sync_blocks::sync_blocks(ctx.clone()),
             ints.sync_blocks
         ),
         make_loop(
             &ctx,
+            "sync_mempool",
             |ctx| sync_mempool::sync_mempool(ctx.clone()),
             ints.sync_mempool
         ),
         make_loop(
             &ctx,
+            "sync_state",
             |ctx| sync_state::sync_state(ctx.clone()),
             ints.sync_state
         ),
         make_loop(
             &ctx,
+            "generate_block",
             |ctx| generate_block::generate_block(ctx.clone()),
             ints.generate_block
         ),
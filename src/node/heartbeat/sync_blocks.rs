@@ -1,4 +1,5 @@
 use super::*;
+use crate::blockchain::BlockchainError;
 use crate::common::*;
 
 pub async fn sync_blocks<K: KvStore, B: Blockchain<K>>(
@@ -8,6 +9,7 @@ pub async fn sync_blocks<K: KvStore, B: Blockchain<K>>(
     let net = ctx.outgoing.clone();
     let opts = ctx.opts.clone();
     let max_block_size = ctx.blockchain.config().max_block_size;
+    let max_reorg_depth = ctx.blockchain.config().max_reorg_depth;
     let mut sorted_peers = ctx.peer_manager.get_peers();
     drop(ctx);
 
@@ -18,19 +20,14 @@ pub async fn sync_blocks<K: KvStore, B: Blockchain<K>>(
             log::info!("Skipped syncing with {} (Outdated)", peer.address);
             continue;
         }
+        let mut pruned = false;
+        let mut too_deep = false;
         let mut net_fail = false;
         let mut chain_fail = false;
+        // Peers that are not taller may still be on a heavier fork, so the
+        // fork of every peer is found and weighted against the local one.
         loop {
             let ctx = context.read().await;
-            if peer.height <= ctx.blockchain.get_height()? {
-                return Ok(());
-            }
-
-            println!(
-                "Syncing blocks with: {} (Peer height: {})",
-                peer.address, peer.height
-            );
-
             let local_height = ctx.blockchain.get_height()?;
             let start_height = std::cmp::min(local_height, peer.height);
             drop(ctx);
@@ -59,7 +56,7 @@ pub async fn sync_blocks<K: KvStore, B: Blockchain<K>>(
 
             let mut headers = resp.headers;
 
-            if headers.is_empty() {
+            if headers.is_empty() && peer.height > local_height {
                 log::warn!("Peer returned no headers!");
                 chain_fail = true;
                 break;
@@ -135,7 +132,7 @@ pub async fn sync_blocks<K: KvStore, B: Blockchain<K>>(
                     chain_fail = true;
                     break;
                 }
-                if peer_header.hash() != headers[0].parent_hash {
+                if !headers.is_empty() && peer_header.hash() != headers[0].parent_hash {
                     log::warn!("Bad header hash!");
                     chain_fail = true;
                     break;
@@ -149,6 +146,12 @@ pub async fn sync_blocks<K: KvStore, B: Blockchain<K>>(
 
                 if let Some(local_header) = local_header {
                     if local_header.hash() != peer_header.hash() {
+                        // Honest peers may be on a minority fork, they are
+                        // only skipped.
+                        if local_height - index > max_reorg_depth {
+                            too_deep = true;
+                            break;
+                        }
                         headers.insert(0, peer_header);
                     } else {
                         break;
@@ -159,7 +162,23 @@ pub async fn sync_blocks<K: KvStore, B: Blockchain<K>>(
                 }
             }
 
-            if chain_fail || net_fail {
+            if chain_fail || net_fail || too_deep {
+                break;
+            }
+
+            // Peer is on the local chain and has nothing new
+            if headers.is_empty() {
+                break;
+            }
+
+            println!(
+                "Syncing blocks with: {} (Peer height: {})",
+                peer.address, peer.height
+            );
+
+            // Peer doesn't have the block bodies we need anymore
+            if headers[0].number > 0 && headers[0].number < peer.pruned_height {
+                pruned = true;
                 break;
             }
 
@@ -170,59 +189,81 @@ pub async fn sync_blocks<K: KvStore, B: Blockchain<K>>(
                 break;
             }
 
-            let will_extend = match ctx.blockchain.will_extend(headers[0].number, &headers) {
-                Ok(result) => {
-                    if !result {
-                        log::warn!("Chain is not powerful enough!");
-                    }
-                    result
+            // Lighter forks are not a misbehavior, the peer will follow the
+            // heavier one once it sees it.
+            match ctx.blockchain.will_extend(headers[0].number, &headers) {
+                Ok(true) => {}
+                Ok(false) => {
+                    log::info!("Chain of {} is not heavier than ours", peer.address);
+                    break;
+                }
+                Err(BlockchainError::ReorgTooDeep) => {
+                    too_deep = true;
+                    break;
                 }
                 Err(e) => {
                     log::warn!("Chain is invalid! Error: {}", e);
-                    false
+                    chain_fail = true;
+                    break;
                 }
-            };
-
-            if !will_extend {
-                chain_fail = true;
-                break;
             }
 
             drop(ctx);
 
-            if let Ok(resp) = net
-                .bincode_get::<GetBlocksRequest, GetBlocksResponse>(
-                    format!("http://{}/bincode/blocks", peer.address).to_string(),
-                    GetBlocksRequest {
-                        since: headers[0].number,
-                        count: opts.max_blocks_fetch,
-                    },
-                    Limit::default()
-                        .size(opts.max_blocks_fetch as u64 * max_block_size as u64 * 2)
-                        .time(opts.max_blocks_fetch as u32 * 30 * SECOND),
-                )
-                .await
-            {
-                let mut ctx = context.write().await;
-
-                match ctx.blockchain.extend(headers[0].number, &resp.blocks) {
-                    Ok(_) => {
-                        println!("Height advanced to {}!", ctx.blockchain.get_height()?);
-                        ctx.on_update()?;
-                    }
-                    Err(e) => {
+            // All blocks of the fork are needed for it to be heavier
+            let mut blocks = Vec::new();
+            while blocks.len() < headers.len() {
+                if let Ok(resp) = net
+                    .bincode_get::<GetBlocksRequest, GetBlocksResponse>(
+                        format!("http://{}/bincode/blocks", peer.address).to_string(),
+                        GetBlocksRequest {
+                            since: headers[0].number + blocks.len() as u64,
+                            count: opts.max_blocks_fetch,
+                        },
+                        Limit::default()
+                            .size(opts.max_blocks_fetch as u64 * max_block_size as u64 * 2)
+                            .time(opts.max_blocks_fetch as u32 * 30 * SECOND),
+                    )
+                    .await
+                {
+                    if resp.blocks.is_empty() {
+                        log::warn!("Peer is not providing claimed blocks!");
                         chain_fail = true;
-                        log::warn!("Cannot extend the blockchain. Error: {}", e);
                         break;
                     }
+                    blocks.extend(resp.blocks);
+                } else {
+                    net_fail = true;
+                    log::warn!("Network error! Cannot fetch blocks...");
+                    break;
                 }
-            } else {
-                net_fail = true;
-                log::warn!("Network error! Cannot fetch blocks...");
+            }
+            if chain_fail || net_fail {
                 break;
             }
+            blocks.truncate(headers.len());
+
+            let mut ctx = context.write().await;
+
+            // Blocks are verified in parallel when catching up from far behind
+            let catching_up = peer.height.saturating_sub(local_height) > opts.max_blocks_fetch;
+            match ctx.extend_blockchain(headers[0].number, &blocks, catching_up) {
+                Ok(_) => {
+                    println!("Height advanced to {}!", ctx.blockchain.get_height()?);
+                    ctx.on_update()?;
+                }
+                Err(e) => {
+                    chain_fail = true;
+                    log::warn!("Cannot extend the blockchain. Error: {}", e);
+                    break;
+                }
+            }
         }
-        if chain_fail {
+        if pruned {
+            log::info!("Skipped syncing with {} (Pruned)", peer.address);
+        } else if too_deep {
+            log::info!("Skipped syncing with {} (Fork too deep)", peer.address);
+        } else if chain_fail {
             context.write().await.punish_bad_behavior(
                 peer.address,
                 opts.incorrect_chain_punish,
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default)]
+pub struct Summary {
+    pub count: u64,
+    pub sum: Duration,
+}
+
+impl Summary {
+    pub fn observe(&mut self, duration: Duration) {
+        self.count += 1;
+        self.sum += duration;
+    }
+}
+
+/// Node internals that are not stored anywhere else. Recording does not need
+/// the lock of the node context.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    heartbeats: Mutex<HashMap<&'static str, Summary>>,
+    block_apply: Mutex<Summary>,
+    firewall_drops: AtomicU64,
+    punished_drops: AtomicU64,
+}
+
+impl Metrics {
+    pub fn observe_heartbeat(&self, name: &'static str, duration: Duration) {
+        self.heartbeats
+            .lock()
+            .unwrap()
+            .entry(name)
+            .or_default()
+            .observe(duration);
+    }
+    pub fn observe_block_apply(&self, count: u64, duration: Duration) {
+        let mut block_apply = self.block_apply.lock().unwrap();
+        block_apply.count += count;
+        block_apply.sum += duration;
+    }
+    pub fn firewall_dropped(&self) {
+        self.firewall_drops.fetch_add(1, Ordering::Relaxed);
+    }
+    pub fn punished_dropped(&self) {
+        self.punished_drops.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Heartbeat summaries, sorted by name
+    pub fn heartbeats(&self) -> Vec<(&'static str, Summary)> {
+        let mut heartbeats = self
+            .heartbeats
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, summary)| (*name, summary.clone()))
+            .collect::<Vec<_>>();
+        heartbeats.sort_unstable_by_key(|(name, _)| *name);
+        heartbeats
+    }
+    pub fn block_apply(&self) -> Summary {
+        self.block_apply.lock().unwrap().clone()
+    }
+    pub fn firewall_drops(&self) -> u64 {
+        self.firewall_drops.load(Ordering::Relaxed)
+    }
+    pub fn punished_drops(&self) -> u64 {
+        self.punished_drops.load(Ordering::Relaxed)
+    }
+}
+
+/// Generates metrics in Prometheus text exposition format
+#[derive(Default)]
+pub struct PrometheusWriter {
+    out: String,
+}
+
+impl PrometheusWriter {
+    fn header(&mut self, name: &str, help: &str, kind: &str) {
+        writeln!(self.out, "# HELP {} {}", name, help).unwrap();
+        writeln!(self.out, "# TYPE {} {}", name, kind).unwrap();
+    }
+
+    pub fn gauge<V: std::fmt::Display>(&mut self, name: &str, help: &str, value: V) {
+        self.header(name, help, "gauge");
+        writeln!(self.out, "{} {}", name, value).unwrap();
+    }
+
+    pub fn counter<V: std::fmt::Display>(&mut self, name: &str, help: &str, value: V) {
+        self.header(name, help, "counter");
+        writeln!(self.out, "{} {}", name, value).unwrap();
+    }
+
+    pub fn labeled_gauge<V: std::fmt::Display>(
+        &mut self,
+        name: &str,
+        help: &str,
+        label: &str,
+        values: &[(&str, V)],
+    ) {
+        self.labeled(name, help, "gauge", label, values);
+    }
+
+    pub fn labeled_counter<V: std::fmt::Display>(
+        &mut self,
+        name: &str,
+        help: &str,
+        label: &str,
+        values: &[(&str, V)],
+    ) {
+        self.labeled(name, help, "counter", label, values);
+    }
+
+    fn labeled<V: std::fmt::Display>(
+        &mut self,
+        name: &str,
+        help: &str,
+        kind: &str,
+        label: &str,
+        values: &[(&str, V)],
+    ) {
+        self.header(name, help, kind);
+        for (label_value, value) in values {
+            writeln!(
+                self.out,
+                "{}{{{}=\"{}\"}} {}",
+                name, label, label_value, value
+            )
+            .unwrap();
+        }
+    }
+
+    pub fn summary(&mut self, name: &str, help: &str, summary: &Summary) {
+        self.header(name, help, "summary");
+        writeln!(self.out, "{}_sum {}", name, summary.sum.as_secs_f64()).unwrap();
+        writeln!(self.out, "{}_count {}", name, summary.count).unwrap();
+    }
+
+    pub fn labeled_summary(
+        &mut self,
+        name: &str,
+        help: &str,
+        label: &str,
+        summaries: &[(&str, &Summary)],
+    ) {
+        self.header(name, help, "summary");
+        for (label_value, summary) in summaries {
+            writeln!(
+                self.out,
+                "{}_sum{{{}=\"{}\"}} {}",
+                name,
+                label,
+                label_value,
+                summary.sum.as_secs_f64()
+            )
+            .unwrap();
+            writeln!(
+                self.out,
+                "{}_count{{{}=\"{}\"}} {}",
+                name, label, label_value, summary.count
+            )
+            .unwrap();
+        }
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prometheus_writer() {
+        let mut summary = Summary::default();
+        summary.observe(Duration::from_millis(500));
+        summary.observe(Duration::from_millis(1500));
+
+        let mut writer = PrometheusWriter::default();
+        writer.gauge("height", "Chain height", 10);
+        writer.labeled_gauge("mempool", "Mempool size", "kind", &[("tx", 2), ("mpn", 3)]);
+        writer.summary("apply", "Apply time", &summary);
+        assert_eq!(
+            writer.finish(),
+            "# HELP height Chain height\n\
+             # TYPE height gauge\n\
+             height 10\n\
+             # HELP mempool Mempool size\n\
+             # TYPE mempool gauge\n\
+             mempool{kind=\"tx\"} 2\n\
+             mempool{kind=\"mpn\"} 3\n\
+             # HELP apply Apply time\n\
+             # TYPE apply summary\n\
+             apply_sum 2\n\
+             apply_count 2\n"
+        );
+    }
+}
@@ -3,11 +3,13 @@ mod test;
 
 mod api;
 mod context;
+mod events;
 mod firewall;
 mod heartbeat;
 mod http;
+mod metrics;
 mod peer_manager;
-use crate::blockchain::{BlockAndPatch, Blockchain, Mempool};
+use crate::blockchain::{BlockAndPatch, Blockchain, FeeTokenPrices, Mempool};
 use crate::client::{
     messages::*, Limit, NodeError, NodeRequest, OutgoingSender, Peer, PeerAddress, Timestamp,
     NETWORK_HEADER, SIGNATURE_HEADER,
@@ -21,9 +23,11 @@ use crate::mpn::MpnWorker;
 use crate::utils::local_timestamp;
 use crate::wallet::TxBuilder;
 use context::NodeContext;
+use events::EventBus;
 pub use firewall::Firewall;
 use hyper::body::HttpBody;
 use hyper::{Body, Method, Request, Response, StatusCode};
+use metrics::Metrics;
 use peer_manager::PeerManager;
 use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
@@ -61,8 +65,11 @@ pub struct NodeOptions {
     pub state_unavailable_ban_time: u32,
     pub candidate_remove_threshold: u32,
     pub mempool_max_fetch: usize,
+    pub mempool_max_size: usize,
+    pub max_events: usize,
     pub max_block_time_difference: u32,
     pub automatic_block_generation: bool,
+    pub fee_token_prices: FeeTokenPrices,
 }
 
 fn fetch_signature(
@@ -146,12 +153,14 @@ async fn node_service<K: KvStore, B: Blockchain<K>>(
             let mut ctx = context.write().await;
             let now = ctx.local_timestamp();
             if ctx.peer_manager.is_ip_punished(now, client.ip()) {
+                ctx.metrics.punished_dropped();
                 log::warn!("{} -> PeerManager dropped request!", client);
                 *response.status_mut() = StatusCode::FORBIDDEN;
                 return Ok(response);
             }
             if let Some(firewall) = &mut ctx.firewall {
                 if !firewall.incoming_permitted(client) {
+                    ctx.metrics.firewall_dropped();
                     log::warn!("{} -> Firewall dropped request!", client);
                     *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
                     return Ok(response);
@@ -235,6 +244,9 @@ async fn node_service<K: KvStore, B: Blockchain<K>>(
                     &api::get_stats(Arc::clone(&context), serde_qs::from_str(&qs)?).await?,
                 )?);
             }
+            (Method::GET, "/metrics") => {
+                *response.body_mut() = Body::from(api::get_metrics(Arc::clone(&context)).await?);
+            }
             (Method::GET, "/debug") => {
                 *response.body_mut() = Body::from(serde_json::to_vec(
                     &api::get_debug_data(Arc::clone(&context), serde_qs::from_str(&qs)?).await?,
@@ -250,6 +262,17 @@ async fn node_service<K: KvStore, B: Blockchain<K>>(
                     &api::get_delegations(Arc::clone(&context), serde_qs::from_str(&qs)?).await?,
                 )?);
             }
+            (Method::GET, "/vestings") => {
+                *response.body_mut() = Body::from(serde_json::to_vec(
+                    &api::get_vestings(Arc::clone(&context), serde_qs::from_str(&qs)?).await?,
+                )?);
+            }
+            (Method::GET, "/staking/rewards") => {
+                *response.body_mut() = Body::from(serde_json::to_vec(
+                    &api::get_staking_rewards(Arc::clone(&context), serde_qs::from_str(&qs)?)
+                        .await?,
+                )?);
+            }
             (Method::GET, "/balance") => {
                 *response.body_mut() = Body::from(serde_json::to_vec(
                     &api::get_balance(Arc::clone(&context), serde_qs::from_str(&qs)?).await?,
@@ -271,11 +294,61 @@ async fn node_service<K: KvStore, B: Blockchain<K>>(
                         .await?,
                 )?);
             }
+            (Method::GET, "/history") => {
+                *response.body_mut() = Body::from(serde_json::to_vec(
+                    &api::get_history(Arc::clone(&context), serde_qs::from_str(&qs)?).await?,
+                )?);
+            }
+            (Method::GET, "/transaction") => {
+                *response.body_mut() = Body::from(serde_json::to_vec(
+                    &api::get_transaction(Arc::clone(&context), serde_qs::from_str(&qs)?).await?,
+                )?);
+            }
+            (Method::POST, "/rpc") => {
+                *response.body_mut() = Body::from(
+                    api::rpc(client, is_local, Arc::clone(&context), &body_bytes).await?,
+                );
+            }
+            (Method::GET, "/events") => {
+                // Validated before long-polling, so that bad requests get a 4xx
+                let query = serde_qs::from_str::<GetEventsRequest>(&qs)
+                    .map_err(|_| InputError::Invalid)
+                    .and_then(api::EventsQuery::try_from);
+                match query {
+                    Ok(query) => {
+                        // Long-polling should not block other requests
+                        let events = context.read().await.events.clone();
+                        let (mut sender, body) = Body::channel();
+                        tokio::task::spawn(async move {
+                            let resp = api::get_events(events, query).await;
+                            match serde_json::to_vec(&resp) {
+                                Ok(bytes) => {
+                                    if sender.send_data(bytes.into()).await.is_err() {
+                                        log::warn!("Events subscriber disconnected!");
+                                    }
+                                }
+                                Err(e) => log::warn!("Cannot serialize events: {}", e),
+                            }
+                        });
+                        *response.body_mut() = body;
+                    }
+                    Err(e) => {
+                        *response.status_mut() = StatusCode::BAD_REQUEST;
+                        *response.body_mut() = Body::from(format!("Error: {}", e));
+                    }
+                }
+            }
             (Method::GET, "/token") => {
                 *response.body_mut() = Body::from(serde_json::to_vec(
                     &api::get_token(Arc::clone(&context), serde_qs::from_str(&qs)?).await?,
                 )?);
             }
+            (Method::GET, "/contract/state") => {
+                *response.body_mut() = Body::from(serde_json::to_vec(
+                    &api::get_contract_state(Arc::clone(&context), serde_qs::from_str(&qs)?)
+                        .await?,
+                )?);
+            }
             (Method::POST, "/bincode/peers") => {
                 *response.body_mut() = Body::from(bincode::serialize(
                     &api::post_peer(
@@ -349,6 +422,24 @@ async fn node_service<K: KvStore, B: Blockchain<K>>(
                         .await?,
                 )?);
             }
+            (Method::GET, "/bincode/transaction/proof") => {
+                *response.body_mut() = Body::from(bincode::serialize(
+                    &api::get_transaction_proof(
+                        Arc::clone(&context),
+                        bincode::deserialize(&body_bytes)?,
+                    )
+                    .await?,
+                )?);
+            }
+            (Method::GET, "/bincode/stakers/epoch") => {
+                *response.body_mut() = Body::from(bincode::serialize(
+                    &api::get_epoch_stakers(
+                        Arc::clone(&context),
+                        bincode::deserialize(&body_bytes)?,
+                    )
+                    .await?,
+                )?);
+            }
             (Method::GET, "/bincode/blocks") => {
                 *response.body_mut() = Body::from(bincode::serialize(
                     &api::get_blocks(Arc::clone(&context), bincode::deserialize(&body_bytes)?)
@@ -501,7 +592,14 @@ pub async fn node_create<K: KvStore, B: Blockchain<K>>(
             .map(|w| (w.mpn_address.clone(), w))
             .collect(),
         mpn_work_pool: None,
-        mempool: Mempool::new(Amount(1_000_000_000)),
+        mempool: Mempool::new(
+            Amount(1_000_000_000),
+            opts.mempool_max_size,
+            opts.fee_token_prices.clone(),
+        ),
+        events: Arc::new(EventBus::new(opts.max_events)),
+        announced_headers: Default::default(),
+        metrics: Default::default(),
         blockchain,
         validator_wallet,
         user_wallet,
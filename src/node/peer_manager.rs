@@ -102,6 +102,17 @@ impl PeerManager {
         }
     }
 
+    pub fn candidate_count(&self) -> usize {
+        self.candidates.len()
+    }
+
+    pub fn punished_count(&self, now: u32) -> usize {
+        self.punishments
+            .values()
+            .filter(|det| now < det.punished_till)
+            .count()
+    }
+
     pub fn node_count(&self) -> usize {
         self.nodes.len()
     }
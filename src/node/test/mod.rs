@@ -247,6 +247,85 @@ async fn test_blocks_get_synced() -> Result<(), NodeError> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_deep_reorgs_are_rejected() -> Result<(), NodeError> {
+    init();
+
+    let rules = Arc::new(RwLock::new(vec![]));
+
+    let mut conf = blockchain::get_test_blockchain_config();
+    conf.max_reorg_depth = 5;
+
+    let (node_futs, route_futs, chans) = simulation::test_network(
+        Arc::clone(&rules),
+        vec![
+            NodeOpts {
+                config: conf.clone(),
+                wallet: TxBuilder::new(&Vec::from("VALIDATOR")),
+                addr: 120,
+                bootstrap: vec![],
+                timestamp_offset: 5,
+                auto_gen_block: false,
+                mpn_workers: vec![],
+            },
+            NodeOpts {
+                config: conf.clone(),
+                wallet: TxBuilder::new(&Vec::from("VALIDATOR2")),
+                addr: 121,
+                bootstrap: vec![120],
+                timestamp_offset: 10,
+                auto_gen_block: false,
+                mpn_workers: vec![],
+            },
+        ],
+    );
+    let test_logic = async {
+        // Wait till clocks sync
+        sleep(Duration::from_millis(1000)).await;
+
+        *rules.write().await = vec![Rule::drop_all()];
+
+        // Node 0 is 10 blocks ahead of the fork-point
+        for i in 2..12 {
+            chans[0].mine().await?;
+            assert_eq!(chans[0].stats().await?.height, i);
+        }
+
+        // Node 1 is 30 blocks ahead of the fork-point
+        for i in 2..32 {
+            chans[1].mine().await?;
+            assert_eq!(chans[1].stats().await?.height, i);
+        }
+
+        // Now we open the connections...
+        rules.write().await.clear();
+
+        // Node 0 is not going to rollback 10 blocks
+        assert_eq!(
+            catch_change(
+                || async { Ok(chans[0].stats().await?.height) },
+                MAX_WAIT_FOR_CHANGE
+            )
+            .await?,
+            11
+        );
+
+        // Not even when node 1 keeps growing its chain
+        chans[1].mine().await?;
+        assert_eq!(chans[1].stats().await?.height, 32);
+        sleep(Duration::from_millis(2000)).await;
+        assert_eq!(chans[0].stats().await?.height, 11);
+
+        for chan in chans.iter() {
+            chan.shutdown().await?;
+        }
+
+        Ok::<(), NodeError>(())
+    };
+    tokio::try_join!(node_futs, route_futs, test_logic)?;
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_auto_block_production() -> Result<(), NodeError> {
     init();
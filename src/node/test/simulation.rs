@@ -3,6 +3,7 @@ use super::*;
 use crate::blockchain::{BlockchainConfig, KvStoreChain};
 use crate::client::{messages::SocialProfiles, BazukaClient};
 use crate::config;
+use crate::core::TransactionData;
 use crate::db::RamKvStore;
 use crate::mpn::MpnWorker;
 use crate::wallet::TxBuilder;
@@ -192,6 +193,8 @@ pub fn test_network(
         .into_iter()
         .map(|node_opts| create_test_node(node_opts))
         .unzip();
+    // Clients are returned in the same order as the nodes
+    let chans = nodes.iter().map(|n| n.incoming.clone()).collect();
     let incs: HashMap<_, _> = nodes.iter().map(|n| (n.addr, n.incoming.clone())).collect();
     let route_futs = nodes
         .into_iter()
@@ -201,6 +204,108 @@ pub fn test_network(
     (
         futures::future::try_join_all(node_futs),
         futures::future::try_join_all(route_futs),
-        incs.into_values().collect(),
+        chans,
     )
 }
+
+/// Test config in which only VALIDATOR has stake. Its blocks are always
+/// VRF-proven, while the blocks of the other validators carry no weight.
+pub fn single_staker_config() -> BlockchainConfig {
+    let mut conf = blockchain::get_test_blockchain_config();
+    let unstaked = [
+        TxBuilder::new(&Vec::from("VALIDATOR2")).get_address(),
+        TxBuilder::new(&Vec::from("VALIDATOR3")).get_address(),
+    ];
+    conf.genesis.block.body.retain(
+        |tx| !matches!(&tx.data, TransactionData::Delegate { to, .. } if unstaked.contains(to)),
+    );
+    conf
+}
+
+#[tokio::test]
+async fn test_nodes_settle_on_heavier_fork() -> Result<(), NodeError> {
+    init();
+
+    let rules = Arc::new(RwLock::new(vec![]));
+    let conf = single_staker_config();
+
+    let (node_futs, route_futs, chans) = test_network(
+        Arc::clone(&rules),
+        vec![
+            NodeOpts {
+                config: conf.clone(),
+                wallet: TxBuilder::new(&Vec::from("VALIDATOR")),
+                addr: 120,
+                bootstrap: vec![],
+                timestamp_offset: 5,
+                auto_gen_block: false,
+                mpn_workers: vec![],
+            },
+            NodeOpts {
+                config: conf.clone(),
+                wallet: TxBuilder::new(&Vec::from("VALIDATOR2")),
+                addr: 121,
+                bootstrap: vec![120],
+                timestamp_offset: 10,
+                auto_gen_block: false,
+                mpn_workers: vec![],
+            },
+            NodeOpts {
+                config: conf.clone(),
+                wallet: TxBuilder::new(&Vec::from("VALIDATOR3")),
+                addr: 122,
+                bootstrap: vec![120, 121],
+                timestamp_offset: 15,
+                auto_gen_block: false,
+                mpn_workers: vec![],
+            },
+        ],
+    );
+    let test_logic = async {
+        // Wait till clocks sync
+        sleep(Duration::from_millis(1000)).await;
+
+        *rules.write().await = vec![Rule::drop_all()];
+
+        // The staked validator builds a single block while the others build
+        // longer forks without any weight.
+        chans[0].mine().await?;
+        assert_eq!(chans[0].stats().await?.height, 2);
+        for i in 2..5 {
+            chans[1].mine().await?;
+            assert_eq!(chans[1].stats().await?.height, i);
+        }
+        for i in 2..4 {
+            chans[2].mine().await?;
+            assert_eq!(chans[2].stats().await?.height, i);
+        }
+
+        // Now we open the connections...
+        rules.write().await.clear();
+
+        // Everyone rolls back to the shorter, heavier fork
+        for chan in chans[1..].iter() {
+            assert!(
+                catch_change(
+                    || async { Ok(chan.stats().await?.height == 2) },
+                    MAX_WAIT_FOR_CHANGE
+                )
+                .await?
+            );
+        }
+        let heavy_tip = chans[0].get_headers(1, 1).await?.headers;
+        assert_eq!(heavy_tip.len(), 1);
+        for chan in chans.iter() {
+            assert_eq!(chan.stats().await?.height, 2);
+            assert_eq!(chan.get_headers(1, 1).await?.headers, heavy_tip);
+        }
+
+        for chan in chans.iter() {
+            chan.shutdown().await?;
+        }
+
+        Ok::<(), NodeError>(())
+    };
+    tokio::try_join!(node_futs, route_futs, test_logic)?;
+    Ok(())
+}
@@ -1,17 +1,25 @@
+mod multisig;
+mod storage;
 mod tx_builder;
+mod watch_only;
+pub use multisig::MultisigEnvelope;
+pub use storage::KdfParams;
 pub use tx_builder::TxBuilder;
+pub use watch_only::*;
 
 use crate::core::{GeneralTransaction, NonceGroup, TokenId};
 
 use bip39::Mnemonic;
 use rand_core_mnemonic::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
 use std::path::Path;
+use storage::WalletKey;
 use thiserror::Error;
+use zeroize::Zeroizing;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WalletType {
@@ -40,12 +48,74 @@ pub enum WalletError {
     BincodeError(#[from] bincode::Error),
     #[error("io error happened: {0}")]
     BlockchainError(#[from] io::Error),
+    #[error("wallet is encrypted, a passphrase is needed")]
+    Locked,
+    #[error("wrong passphrase or corrupted wallet")]
+    WrongPassphrase,
+    #[error("unsupported wallet file version: {0}")]
+    UnsupportedVersion(u8),
+    #[error("invalid key-derivation parameters")]
+    InvalidKdfParams,
+    #[error("wallet encryption failed")]
+    EncryptionError,
+    #[error("wallet is already encrypted")]
+    AlreadyEncrypted,
+    #[error("wallet is not encrypted")]
+    NotEncrypted,
+    #[error("passphrases do not match")]
+    PassphraseMismatch,
+    #[error("transaction does not belong to this wallet")]
+    SignerMismatch,
+    #[error("account #{0} not found")]
+    AccountNotFound(usize),
+    #[error("envelopes belong to different transactions")]
+    EnvelopeMismatch,
+    #[error("multisig threshold not met")]
+    MultisigThresholdNotMet,
+    #[error("unlocked sessions are not supported on this platform")]
+    SessionsUnsupported,
+    #[error("unlocked session could not be started")]
+    SessionNotStarted,
 }
 
+/// Wallet collections saved before accounts were introduced
+#[derive(Deserialize)]
+struct LegacyWalletCollection {
+    mnemonic: Mnemonic,
+    wallets: HashMap<WalletType, Wallet>,
+}
+
+impl From<LegacyWalletCollection> for WalletCollection {
+    fn from(legacy: LegacyWalletCollection) -> Self {
+        let mut accounts: BTreeMap<usize, String> = legacy
+            .wallets
+            .keys()
+            .filter_map(|t| match t {
+                WalletType::User(index) => Some((*index, format!("Account {}", index))),
+                WalletType::Validator => None,
+            })
+            .collect();
+        accounts.insert(0, DEFAULT_ACCOUNT_NAME.into());
+        Self {
+            mnemonic: Zeroizing::new(legacy.mnemonic),
+            wallets: legacy.wallets,
+            accounts,
+            current_account: 0,
+            key: None,
+        }
+    }
+}
+
+const DEFAULT_ACCOUNT_NAME: &str = "Default";
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WalletCollection {
-    mnemonic: Mnemonic,
+    mnemonic: Zeroizing<Mnemonic>,
     wallets: HashMap<WalletType, Wallet>,
+    accounts: BTreeMap<usize, String>,
+    current_account: usize,
+    #[serde(skip)]
+    key: Option<WalletKey>,
 }
 
 impl WalletCollection {
@@ -54,35 +124,157 @@ impl WalletCollection {
     }
     pub fn create<R: RngCore + CryptoRng>(rng: &mut R, mnemonic: Option<Mnemonic>) -> Self {
         Self {
-            mnemonic: mnemonic.unwrap_or_else(|| {
+            mnemonic: Zeroizing::new(mnemonic.unwrap_or_else(|| {
                 Mnemonic::generate_in_with(rng, bip39::Language::English, 12).unwrap()
-            }),
+            })),
             wallets: Default::default(),
+            accounts: [(0, DEFAULT_ACCOUNT_NAME.into())].into(),
+            current_account: 0,
+            key: None,
         }
     }
     pub fn user(&mut self, index: usize) -> &mut Wallet {
         self.wallets
             .entry(WalletType::User(index))
-            .or_insert(Wallet::new(WalletType::User(index), self.mnemonic.clone()))
+            .or_insert(Wallet::new(
+                WalletType::User(index),
+                (*self.mnemonic).clone(),
+            ))
+    }
+    /// Indices and names of the user accounts, sorted by index
+    pub fn accounts(&self) -> Vec<(usize, String)> {
+        self.accounts
+            .iter()
+            .map(|(index, name)| (*index, name.clone()))
+            .collect()
+    }
+    pub fn current_account(&self) -> usize {
+        self.current_account
+    }
+    /// Wallet of the account that is currently in use
+    pub fn current(&mut self) -> &mut Wallet {
+        self.user(self.current_account)
+    }
+    /// Derives a new account, returns its index
+    pub fn new_account(&mut self, name: String) -> usize {
+        let index = self.accounts.keys().last().map(|i| i + 1).unwrap_or(0);
+        self.accounts.insert(index, name);
+        self.user(index);
+        index
+    }
+    pub fn use_account(&mut self, index: usize) -> Result<(), WalletError> {
+        if !self.accounts.contains_key(&index) {
+            return Err(WalletError::AccountNotFound(index));
+        }
+        self.current_account = index;
+        Ok(())
+    }
+    pub fn rename_account(&mut self, index: usize, name: String) -> Result<(), WalletError> {
+        *self
+            .accounts
+            .get_mut(&index)
+            .ok_or(WalletError::AccountNotFound(index))? = name;
+        Ok(())
     }
     pub fn validator(&mut self) -> &mut Wallet {
         self.wallets
             .entry(WalletType::Validator)
-            .or_insert(Wallet::new(WalletType::Validator, self.mnemonic.clone()))
+            .or_insert(Wallet::new(WalletType::Validator, (*self.mnemonic).clone()))
     }
+    /// Opens a wallet file. Fails with `WalletError::Locked` if the wallet is
+    /// encrypted.
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Option<Self>, WalletError> {
+        Self::open_with_passphrase(path, || Err(WalletError::Locked))
+    }
+    /// Opens a wallet file, the passphrase is only requested when the wallet
+    /// is encrypted. Legacy plaintext wallets are opened as well, and are
+    /// migrated to the new file format on the next save.
+    pub fn open_with_passphrase<P: AsRef<Path>, F: FnOnce() -> Result<String, WalletError>>(
+        path: P,
+        passphrase: F,
+    ) -> Result<Option<Self>, WalletError> {
+        Self::read(path, None, passphrase)
+    }
+    /// Same as `open_with_passphrase`, but the passphrase is not requested
+    /// while a session is served by `serve_session`
+    pub fn open_with_session<
+        P: AsRef<Path>,
+        S: AsRef<Path>,
+        F: FnOnce() -> Result<String, WalletError>,
+    >(
+        path: P,
+        session_path: S,
+        passphrase: F,
+    ) -> Result<Option<Self>, WalletError> {
+        Self::read(path, storage::read_session(session_path)?, passphrase)
+    }
+    fn read<P: AsRef<Path>, F: FnOnce() -> Result<String, WalletError>>(
+        path: P,
+        session: Option<WalletKey>,
+        passphrase: F,
+    ) -> Result<Option<Self>, WalletError> {
         if let Ok(mut f) = File::open(&path) {
             let mut bytes = Vec::new();
             f.read_to_end(&mut bytes)?;
-            Ok(Some(bincode::deserialize(&bytes)?))
+            let (plaintext, key) = storage::decode(&bytes, session, passphrase)?;
+            let mut wallet: Self = match bincode::deserialize(&plaintext) {
+                Ok(wallet) => wallet,
+                Err(_) => bincode::deserialize::<LegacyWalletCollection>(&plaintext)?.into(),
+            };
+            wallet.key = key;
+            Ok(Some(wallet))
         } else {
             Ok(None)
         }
     }
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), WalletError> {
-        File::create(path)?.write_all(&bincode::serialize(self)?)?;
+        let plaintext = Zeroizing::new(bincode::serialize(self)?);
+        File::create(path)?.write_all(&storage::encode(&plaintext, self.key.as_ref())?)?;
         Ok(())
     }
+    /// Whether the wallet is encrypted when saved
+    pub fn is_encrypted(&self) -> bool {
+        self.key.is_some()
+    }
+    fn set_passphrase(&mut self, passphrase: &str, kdf: KdfParams) -> Result<(), WalletError> {
+        self.key = Some(WalletKey::generate(passphrase, kdf)?);
+        Ok(())
+    }
+    /// Encrypts the wallet under the given passphrase, on the next save
+    pub fn lock(&mut self, passphrase: &str) -> Result<(), WalletError> {
+        if self.is_encrypted() {
+            return Err(WalletError::AlreadyEncrypted);
+        }
+        self.set_passphrase(passphrase, KdfParams::default())
+    }
+    /// Keeps the encrypted wallet unlocked for `duration` seconds, by serving
+    /// its key from the memory of this process. Blocks until the session ends.
+    pub fn serve_session<P: AsRef<Path>>(
+        &self,
+        session_path: P,
+        duration: u64,
+    ) -> Result<(), WalletError> {
+        let key = self.key.as_ref().ok_or(WalletError::NotEncrypted)?;
+        storage::serve_session(session_path, key, duration)
+    }
+    pub fn end_session<P: AsRef<Path>>(session_path: P) -> Result<(), WalletError> {
+        storage::end_session(session_path)
+    }
+    /// Stores the wallet in plaintext, on the next save
+    pub fn remove_passphrase(&mut self) -> Result<(), WalletError> {
+        if !self.is_encrypted() {
+            return Err(WalletError::NotEncrypted);
+        }
+        self.key = None;
+        Ok(())
+    }
+    /// Re-encrypts the wallet under a new passphrase, with a fresh salt
+    pub fn change_passphrase(&mut self, passphrase: &str) -> Result<(), WalletError> {
+        if !self.is_encrypted() {
+            return Err(WalletError::NotEncrypted);
+        }
+        self.set_passphrase(passphrase, KdfParams::default())
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -102,11 +294,12 @@ impl Wallet {
             mnemonic,
         }
     }
-    fn seed(&self) -> [u8; 64] {
-        self.mnemonic.to_seed(&self.wallet_type.bip39_passphrase())
+    /// The decrypted seed is wiped from memory once dropped
+    fn seed(&self) -> Zeroizing<[u8; 64]> {
+        Zeroizing::new(self.mnemonic.to_seed(self.wallet_type.bip39_passphrase()))
     }
     pub fn tx_builder(&self) -> TxBuilder {
-        TxBuilder::new(&self.seed())
+        TxBuilder::new(self.seed().as_ref())
     }
     pub fn add_token(&mut self, token_id: TokenId) {
         if !self.tokens.contains(&token_id) {
@@ -136,3 +329,294 @@ impl Wallet {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Money;
+
+    const TEST_KDF: KdfParams = KdfParams {
+        log_n: 4,
+        r: 8,
+        p: 1,
+    };
+
+    #[test]
+    fn test_encrypted_wallet() {
+        let path = std::env::temp_dir().join(format!("bazuka-wallet-{}", rand::random::<u64>()));
+        let mut wallet = WalletCollection::create(&mut rand_mnemonic::thread_rng(), None);
+        let addr = wallet.user(0).tx_builder().get_address();
+
+        // Legacy plaintext wallets are migrated
+        File::create(&path)
+            .unwrap()
+            .write_all(&bincode::serialize(&wallet).unwrap())
+            .unwrap();
+        let mut opened = WalletCollection::open(&path).unwrap().unwrap();
+        assert!(!opened.is_encrypted());
+        assert_eq!(opened.user(0).tx_builder().get_address(), addr);
+        assert!(matches!(
+            wallet.remove_passphrase(),
+            Err(WalletError::NotEncrypted)
+        ));
+
+        wallet.set_passphrase("secret", TEST_KDF).unwrap();
+        assert!(matches!(
+            wallet.lock("secret"),
+            Err(WalletError::AlreadyEncrypted)
+        ));
+        wallet.save(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        let mnemonic = wallet.mnemonic().to_string();
+        assert!(!bytes
+            .windows(mnemonic.len())
+            .any(|w| w == mnemonic.as_bytes()));
+
+        assert!(matches!(
+            WalletCollection::open(&path),
+            Err(WalletError::Locked)
+        ));
+        assert!(matches!(
+            WalletCollection::open_with_passphrase(&path, || Ok("wrong".into())),
+            Err(WalletError::WrongPassphrase)
+        ));
+        let mut opened = WalletCollection::open_with_passphrase(&path, || Ok("secret".into()))
+            .unwrap()
+            .unwrap();
+        assert!(opened.is_encrypted());
+        assert_eq!(opened.user(0).tx_builder().get_address(), addr);
+
+        // Re-encrypted with the same key on save
+        opened.save(&path).unwrap();
+        let mut opened = WalletCollection::open_with_passphrase(&path, || Ok("secret".into()))
+            .unwrap()
+            .unwrap();
+
+        opened.set_passphrase("new secret", TEST_KDF).unwrap();
+        opened.save(&path).unwrap();
+        assert!(matches!(
+            WalletCollection::open_with_passphrase(&path, || Ok("secret".into())),
+            Err(WalletError::WrongPassphrase)
+        ));
+        let mut opened = WalletCollection::open_with_passphrase(&path, || Ok("new secret".into()))
+            .unwrap()
+            .unwrap();
+
+        opened.remove_passphrase().unwrap();
+        opened.save(&path).unwrap();
+        let mut opened = WalletCollection::open(&path).unwrap().unwrap();
+        assert!(!opened.is_encrypted());
+        assert_eq!(opened.user(0).tx_builder().get_address(), addr);
+
+        // Tampered headers are rejected
+        opened.set_passphrase("secret", TEST_KDF).unwrap();
+        opened.save(&path).unwrap();
+
+        // A served session opens the wallet without the passphrase
+        let session = path.with_extension("session");
+        assert!(matches!(
+            WalletCollection::create(&mut rand_mnemonic::thread_rng(), None)
+                .serve_session(&session, 60),
+            Err(WalletError::NotEncrypted)
+        ));
+        #[cfg(unix)]
+        {
+            let serve = |duration| {
+                let (path, session) = (path.clone(), session.clone());
+                std::thread::spawn(move || {
+                    WalletCollection::open_with_passphrase(&path, || Ok("secret".into()))
+                        .unwrap()
+                        .unwrap()
+                        .serve_session(&session, duration)
+                })
+            };
+            let server = serve(60);
+            let reopened = (0..500)
+                .find_map(|_| {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                    WalletCollection::open_with_session(&path, &session, || {
+                        Err(WalletError::Locked)
+                    })
+                    .ok()
+                })
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                reopened.mnemonic().to_string(),
+                opened.mnemonic().to_string()
+            );
+            WalletCollection::end_session(&session).unwrap();
+            server.join().unwrap().unwrap();
+            assert!(!session.exists());
+            assert!(matches!(
+                WalletCollection::open_with_session(&path, &session, || Err(WalletError::Locked)),
+                Err(WalletError::Locked)
+            ));
+
+            // Expired sessions are not served anymore
+            serve(0).join().unwrap().unwrap();
+            assert!(!session.exists());
+        }
+
+        // Unbounded scrypt parameters are rejected before deriving
+        assert!(matches!(
+            opened.set_passphrase(
+                "secret",
+                KdfParams {
+                    log_n: 30,
+                    r: 8,
+                    p: 1
+                }
+            ),
+            Err(WalletError::InvalidKdfParams)
+        ));
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[6] = 2;
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(matches!(
+            WalletCollection::open_with_passphrase(&path, || Ok("secret".into())),
+            Err(WalletError::UnsupportedVersion(2))
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_offline_signing() {
+        let signer = TxBuilder::new(&Vec::from("ABC"));
+        let other = TxBuilder::new(&Vec::from("CBA"));
+        let watch_only = WatchOnlyWallet::new(signer.get_address(), signer.get_mpn_address());
+        let mpn_contract_id = crate::config::blockchain::get_test_blockchain_config()
+            .mpn_config
+            .mpn_contract_id;
+
+        let txs: Vec<UnsignedTransaction> = vec![
+            watch_only
+                .create_transaction(
+                    "".into(),
+                    other.get_address(),
+                    Money::ziesha(10),
+                    Money::ziesha(1),
+                    1,
+                )
+                .into(),
+            watch_only
+                .deposit_mpn(
+                    "".into(),
+                    mpn_contract_id,
+                    other.get_mpn_address(),
+                    1,
+                    Money::ziesha(10),
+                    Money::ziesha(1),
+                )
+                .into(),
+            watch_only
+                .create_mpn_transaction(
+                    other.get_mpn_address(),
+                    Money::ziesha(10),
+                    Money::ziesha(1),
+                    1,
+                )
+                .into(),
+        ];
+
+        let path = std::env::temp_dir().join(format!("bazuka-unsigned-{}", rand::random::<u64>()));
+        write_transactions(&path, &txs).unwrap();
+        let txs = read_transactions::<UnsignedTransaction, _>(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        for tx in txs.iter() {
+            assert!(matches!(
+                tx.clone().sign(&other),
+                Err(WalletError::SignerMismatch)
+            ));
+            let unsigned: GeneralTransaction = match tx.clone() {
+                UnsignedTransaction::TransactionAndDelta(tx) => (*tx).into(),
+                UnsignedTransaction::MpnDeposit(tx) => (*tx).into(),
+                UnsignedTransaction::MpnTransaction(tx) => (*tx).into(),
+            };
+            assert!(!unsigned.verify_signature());
+            assert!(tx.clone().sign(&signer).unwrap().verify_signature());
+        }
+
+        // Same as signing online
+        let signed = txs[0].clone().sign(&signer).unwrap();
+        let online: GeneralTransaction = signer
+            .create_transaction(
+                "".into(),
+                other.get_address(),
+                Money::ziesha(10),
+                Money::ziesha(1),
+                1,
+            )
+            .into();
+        assert_eq!(
+            bincode::serialize(&signed).unwrap(),
+            bincode::serialize(&online).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_accounts() {
+        let path = std::env::temp_dir().join(format!("bazuka-wallet-{}", rand::random::<u64>()));
+        let mut wallet = WalletCollection::create(&mut rand_mnemonic::thread_rng(), None);
+        assert_eq!(wallet.accounts(), vec![(0, DEFAULT_ACCOUNT_NAME.into())]);
+        assert_eq!(wallet.new_account("Savings".into()), 1);
+        assert_eq!(wallet.new_account("Trading".into()), 2);
+        let addrs = (0..3)
+            .map(|i| wallet.user(i).tx_builder().get_address())
+            .collect::<Vec<_>>();
+        assert!(addrs[0] != addrs[1] && addrs[1] != addrs[2]);
+
+        assert!(matches!(
+            wallet.use_account(3),
+            Err(WalletError::AccountNotFound(3))
+        ));
+        wallet.use_account(1).unwrap();
+        assert_eq!(wallet.current().tx_builder().get_address(), addrs[1]);
+
+        // Token lists are kept per account
+        let token_id = TokenId::Custom(1.into());
+        wallet.current().add_token(token_id);
+        assert!(wallet.user(1).get_tokens().contains(&token_id));
+        assert!(!wallet.user(0).get_tokens().contains(&token_id));
+
+        wallet.rename_account(2, "Cold".into()).unwrap();
+        assert!(matches!(
+            wallet.rename_account(3, "None".into()),
+            Err(WalletError::AccountNotFound(3))
+        ));
+
+        wallet.save(&path).unwrap();
+        let mut opened = WalletCollection::open(&path).unwrap().unwrap();
+        assert_eq!(
+            opened.accounts(),
+            vec![
+                (0, DEFAULT_ACCOUNT_NAME.into()),
+                (1, "Savings".into()),
+                (2, "Cold".into())
+            ]
+        );
+        assert_eq!(opened.current_account(), 1);
+        assert!(opened.current().get_tokens().contains(&token_id));
+
+        // Wallets saved before accounts were introduced
+        File::create(&path)
+            .unwrap()
+            .write_all(&bincode::serialize(&(wallet.mnemonic(), &wallet.wallets)).unwrap())
+            .unwrap();
+        let mut opened = WalletCollection::open(&path).unwrap().unwrap();
+        assert_eq!(
+            opened.accounts(),
+            vec![
+                (0, DEFAULT_ACCOUNT_NAME.into()),
+                (1, "Account 1".into()),
+                (2, "Account 2".into())
+            ]
+        );
+        assert_eq!(opened.current_account(), 0);
+        assert!(opened.user(1).get_tokens().contains(&token_id));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
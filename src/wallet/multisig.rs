@@ -0,0 +1,104 @@
+use super::{TxBuilder, WalletError};
+use crate::core::{
+    Address, Money, MultisigPolicy, Signature, Signer, Transaction, TransactionAndDelta,
+    TransactionData,
+};
+use crate::crypto::SignatureScheme;
+
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+/// A transaction of a multisig account, partially signed by its co-signers.
+/// It's passed around between the co-signers until enough signatures are
+/// collected.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultisigEnvelope {
+    pub policy: MultisigPolicy,
+    pub tx: TransactionAndDelta,
+    pub sigs: Vec<(Address, <Signer as SignatureScheme>::Sig)>,
+}
+
+impl MultisigEnvelope {
+    pub fn new(
+        policy: MultisigPolicy,
+        memo: String,
+        data: TransactionData,
+        fee: Money,
+        nonce: u32,
+    ) -> Self {
+        Self {
+            tx: TransactionAndDelta {
+                tx: Transaction {
+                    memo,
+                    src: Some(policy.address()),
+                    data,
+                    nonce,
+                    fee,
+                    sig: Signature::Unsigned,
+                },
+                state_delta: None,
+            },
+            policy,
+            sigs: Vec::new(),
+        }
+    }
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, WalletError> {
+        let mut bytes = Vec::new();
+        File::open(path)?.read_to_end(&mut bytes)?;
+        Ok(bincode::deserialize(&bytes)?)
+    }
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), WalletError> {
+        File::create(path)?.write_all(&bincode::serialize(self)?)?;
+        Ok(())
+    }
+    fn unsigned_bytes(&self) -> Vec<u8> {
+        let mut unsigned = self.tx.tx.clone();
+        unsigned.sig = Signature::Unsigned;
+        bincode::serialize(&unsigned).unwrap()
+    }
+    fn add_sig(
+        &mut self,
+        signer: Address,
+        sig: <Signer as SignatureScheme>::Sig,
+    ) -> Result<(), WalletError> {
+        if !self.policy.signers.contains(&signer)
+            || !Signer::verify(&signer, &self.unsigned_bytes(), &sig)
+        {
+            return Err(WalletError::SignerMismatch);
+        }
+        self.sigs.retain(|(pk, _)| pk != &signer);
+        self.sigs.push((signer, sig));
+        Ok(())
+    }
+    /// Adds the signature of a co-signer
+    pub fn sign(&mut self, tx_builder: &TxBuilder) -> Result<(), WalletError> {
+        let sig = tx_builder.sign(&self.unsigned_bytes());
+        self.add_sig(tx_builder.get_address(), sig)
+    }
+    /// Collects the signatures of another copy of the same envelope
+    pub fn merge(&mut self, other: MultisigEnvelope) -> Result<(), WalletError> {
+        if other.policy != self.policy || other.tx.tx != self.tx.tx {
+            return Err(WalletError::EnvelopeMismatch);
+        }
+        for (signer, sig) in other.sigs {
+            self.add_sig(signer, sig)?;
+        }
+        Ok(())
+    }
+    pub fn is_complete(&self) -> bool {
+        self.policy
+            .is_satisfied_by(self.sigs.iter().map(|(pk, _)| pk))
+    }
+    /// Turns the envelope into a transaction, once enough signatures are
+    /// collected
+    pub fn finalize(self) -> Result<TransactionAndDelta, WalletError> {
+        if !self.is_complete() {
+            return Err(WalletError::MultisigThresholdNotMet);
+        }
+        let mut tx = self.tx;
+        tx.tx.sig = Signature::MultiSigned(self.sigs);
+        Ok(tx)
+    }
+}
@@ -0,0 +1,287 @@
+use super::WalletError;
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::io::prelude::*;
+use std::path::Path;
+use zeroize::Zeroizing;
+
+const MAGIC: &[u8; 6] = b"BZKWLT";
+const VERSION: u8 = 1;
+
+// Parameters are read from the wallet file, which might be corrupted or
+// malicious. Derivations need 128 * r * 2^log_n bytes of memory, at most 1GiB.
+const MAX_LOG_N: u8 = 20;
+const MAX_R: u32 = 8;
+const MAX_P: u32 = 16;
+
+/// Parameters of the scrypt key-derivation function
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl KdfParams {
+    fn is_bounded(&self) -> bool {
+        self.log_n <= MAX_LOG_N && self.r <= MAX_R && self.p <= MAX_P
+    }
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        // Needs 32MiB of memory for each derivation
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// Symmetric key derived from the passphrase of the wallet. It's kept in
+/// memory so that the wallet can be re-encrypted on save, without asking for
+/// the passphrase again.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WalletKey {
+    kdf: KdfParams,
+    salt: [u8; 16],
+    key: Zeroizing<[u8; 32]>,
+}
+
+impl std::fmt::Debug for WalletKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WalletKey")
+            .field("kdf", &self.kdf)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WalletKey {
+    fn derive(passphrase: &str, kdf: KdfParams, salt: [u8; 16]) -> Result<Self, WalletError> {
+        if !kdf.is_bounded() {
+            return Err(WalletError::InvalidKdfParams);
+        }
+        let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p, 32)
+            .map_err(|_| WalletError::InvalidKdfParams)?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        scrypt::scrypt(passphrase.as_bytes(), &salt, &params, key.as_mut())
+            .map_err(|_| WalletError::InvalidKdfParams)?;
+        Ok(Self { kdf, salt, key })
+    }
+    pub fn generate(passphrase: &str, kdf: KdfParams) -> Result<Self, WalletError> {
+        let mut salt = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self::derive(passphrase, kdf, salt)
+    }
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&Key::from(*self.key))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum WalletFile {
+    Plain(Vec<u8>),
+    Encrypted {
+        kdf: KdfParams,
+        salt: [u8; 16],
+        nonce: [u8; 24],
+        ciphertext: Vec<u8>,
+    },
+}
+
+/// Header fields are authenticated along with the ciphertext
+fn associated_data(kdf: &KdfParams, salt: &[u8; 16]) -> Result<Vec<u8>, WalletError> {
+    let mut aad = MAGIC.to_vec();
+    aad.push(VERSION);
+    aad.extend(bincode::serialize(kdf)?);
+    aad.extend(salt);
+    Ok(aad)
+}
+
+pub fn encode(plaintext: &[u8], key: Option<&WalletKey>) -> Result<Vec<u8>, WalletError> {
+    let file = if let Some(key) = key {
+        let mut nonce = [0u8; 24];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = key
+            .cipher()
+            .encrypt(
+                &XNonce::from(nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &associated_data(&key.kdf, &key.salt)?,
+                },
+            )
+            .map_err(|_| WalletError::EncryptionError)?;
+        WalletFile::Encrypted {
+            kdf: key.kdf,
+            salt: key.salt,
+            nonce,
+            ciphertext,
+        }
+    } else {
+        WalletFile::Plain(plaintext.to_vec())
+    };
+    let mut bytes = MAGIC.to_vec();
+    bytes.push(VERSION);
+    bytes.extend(bincode::serialize(&file)?);
+    Ok(bytes)
+}
+
+/// Returns the plaintext of a wallet file, along with the key it was
+/// encrypted with. The key of an unlocked session is used when it belongs to
+/// the file, otherwise the passphrase is only requested when the file is
+/// encrypted. Files without a header are legacy plaintext wallets.
+pub fn decode<F: FnOnce() -> Result<String, WalletError>>(
+    bytes: &[u8],
+    session: Option<WalletKey>,
+    passphrase: F,
+) -> Result<(Zeroizing<Vec<u8>>, Option<WalletKey>), WalletError> {
+    let body = match bytes.strip_prefix(MAGIC) {
+        Some(rest) => match rest.split_first() {
+            Some((&VERSION, body)) => body,
+            Some((&version, _)) => return Err(WalletError::UnsupportedVersion(version)),
+            None => return Err(WalletError::UnsupportedVersion(0)),
+        },
+        None => return Ok((Zeroizing::new(bytes.to_vec()), None)),
+    };
+    match bincode::deserialize(body)? {
+        WalletFile::Plain(plaintext) => Ok((Zeroizing::new(plaintext), None)),
+        WalletFile::Encrypted {
+            kdf,
+            salt,
+            nonce,
+            ciphertext,
+        } => {
+            let key = match session {
+                Some(key) if key.kdf == kdf && key.salt == salt => key,
+                _ => {
+                    let passphrase = Zeroizing::new(passphrase()?);
+                    WalletKey::derive(&passphrase, kdf, salt)?
+                }
+            };
+            let plaintext = key
+                .cipher()
+                .decrypt(
+                    &XNonce::from(nonce),
+                    Payload {
+                        msg: &ciphertext,
+                        aad: &associated_data(&kdf, &salt)?,
+                    },
+                )
+                .map_err(|_| WalletError::WrongPassphrase)?;
+            Ok((Zeroizing::new(plaintext), Some(key)))
+        }
+    }
+}
+
+/// Requests understood by a session agent
+const SESSION_GET_KEY: u8 = 0;
+const SESSION_END: u8 = 1;
+
+/// Serves the key of an unlocked wallet to the commands of its owner, over a
+/// Unix socket only accessible by the owner, until `duration` seconds have
+/// passed or `end_session` is called. The key is never written to disk, it
+/// only lives in the memory of the serving process.
+#[cfg(unix)]
+pub fn serve_session<P: AsRef<Path>>(
+    path: P,
+    key: &WalletKey,
+    duration: u64,
+) -> Result<(), WalletError> {
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::UnixListener;
+    use std::time::{Duration, Instant};
+
+    let path = path.as_ref();
+    // Only a single session is served at a time
+    end_session(path)?;
+    let listener = UnixListener::bind(path)?;
+    let serve = || -> Result<(), WalletError> {
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        listener.set_nonblocking(true)?;
+        let expires_at = Instant::now() + Duration::from_secs(duration);
+        while Instant::now() < expires_at {
+            let mut stream = match listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(50));
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+            stream.set_nonblocking(false)?;
+            stream.set_read_timeout(Some(Duration::from_secs(1)))?;
+            let mut req = [0u8; 1];
+            if stream.read_exact(&mut req).is_err() {
+                continue;
+            }
+            match req[0] {
+                SESSION_GET_KEY => {
+                    let bytes = Zeroizing::new(bincode::serialize(key)?);
+                    let _ = stream.write_all(&bytes);
+                }
+                SESSION_END => {
+                    // The socket is removed before `end_session` returns, so
+                    // that it can't remove the socket of a newer session
+                    std::fs::remove_file(path)?;
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+        std::fs::remove_file(path)?;
+        Ok(())
+    };
+    let result = serve();
+    if result.is_err() {
+        let _ = std::fs::remove_file(path);
+    }
+    result
+}
+
+#[cfg(not(unix))]
+pub fn serve_session<P: AsRef<Path>>(
+    _path: P,
+    _key: &WalletKey,
+    _duration: u64,
+) -> Result<(), WalletError> {
+    Err(WalletError::SessionsUnsupported)
+}
+
+/// Key of the unlocked session, if it's still being served
+#[cfg(unix)]
+pub fn read_session<P: AsRef<Path>>(path: P) -> Result<Option<WalletKey>, WalletError> {
+    let mut stream = match std::os::unix::net::UnixStream::connect(path) {
+        Ok(stream) => stream,
+        Err(_) => return Ok(None),
+    };
+    stream.write_all(&[SESSION_GET_KEY])?;
+    let mut bytes = Zeroizing::new(Vec::new());
+    stream.read_to_end(&mut bytes)?;
+    Ok(bincode::deserialize(&bytes).ok())
+}
+
+#[cfg(not(unix))]
+pub fn read_session<P: AsRef<Path>>(_path: P) -> Result<Option<WalletKey>, WalletError> {
+    Ok(None)
+}
+
+/// Stops serving the unlocked session, if any
+pub fn end_session<P: AsRef<Path>>(path: P) -> Result<(), WalletError> {
+    #[cfg(unix)]
+    if let Ok(mut stream) = std::os::unix::net::UnixStream::connect(&path) {
+        stream.write_all(&[SESSION_END])?;
+        // Returns once the session has stopped
+        let _ = stream.read_to_end(&mut Vec::new());
+        return Ok(());
+    }
+    // Sockets of sessions which were killed, or files of older versions
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
@@ -2,9 +2,10 @@
 use crate::client::{messages::ValidatorClaim, PeerAddress};
 
 use crate::core::{
-    Address, Amount, ContractDeposit, ContractId, ContractUpdate, ContractWithdraw, Hasher, Money,
-    MpnAddress, MpnDeposit, MpnWithdraw, RegularSendEntry, Signature, Signer, Token, TokenId,
-    Transaction, TransactionAndDelta, TransactionData, ValidatorProof, Vrf, ZkSigner,
+    Address, Amount, ContractDeposit, ContractId, ContractUpdate, ContractWithdraw, Hasher, Header,
+    LockTime, Money, MpnAddress, MpnDeposit, MpnWithdraw, MultisigPolicy, RegularSendEntry,
+    Signature, Signer, Token, TokenId, TokenMetadata, TokenUpdate, Transaction,
+    TransactionAndDelta, TransactionData, ValidatorProof, Vesting, VestingId, Vrf, ZkSigner,
 };
 use crate::crypto::SignatureScheme;
 use crate::crypto::VerifiableRandomFunction;
@@ -14,9 +15,12 @@ use crate::zk::ZkHasher;
 use rand::SeedableRng;
 use rand_chacha::ChaChaRng;
 
+use super::WatchOnlyWallet;
+use zeroize::Zeroizing;
+
 #[derive(Clone)]
 pub struct TxBuilder {
-    seed: Vec<u8>,
+    seed: Zeroizing<Vec<u8>>,
     vrf_private_key: <Vrf as VerifiableRandomFunction>::Priv,
     vrf_public_key: <Vrf as VerifiableRandomFunction>::Pub,
     private_key: <Signer as SignatureScheme>::Priv,
@@ -33,7 +37,7 @@ impl TxBuilder {
         let mut chacha_rng = ChaChaRng::from_seed(chacha_seed);
         let (vrf_public_key, vrf_private_key) = Vrf::generate_keys(&mut chacha_rng);
         Self {
-            seed: seed.to_vec(),
+            seed: Zeroizing::new(seed.to_vec()),
             address: pk,
             zk_address: zk_pk,
             private_key: sk,
@@ -70,6 +74,16 @@ impl TxBuilder {
         let bytes = bincode::serialize(&tx).unwrap();
         tx.sig = Signature::Signed(Signer::sign(&self.private_key, &bytes));
     }
+    pub fn sign_header(&self, header: &mut Header) {
+        header.proof_of_stake.sig = Some(Signer::sign(&self.private_key, &header.signing_bytes()));
+    }
+    pub fn sign_mpn_transaction(&self, tx: &mut zk::MpnTransaction) {
+        tx.sign(&self.zk_private_key);
+    }
+    /// Public keys of this builder, for building transactions without signing
+    pub fn watch_only(&self) -> WatchOnlyWallet {
+        WatchOnlyWallet::new(self.get_address(), self.get_mpn_address())
+    }
     pub fn delegate(
         &self,
         memo: String,
@@ -98,6 +112,54 @@ impl TxBuilder {
             state_delta: None,
         }
     }
+    pub fn reclaim_delegate(
+        &self,
+        memo: String,
+        from: Address,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::ReclaimDelegate { from },
+            nonce,
+            fee,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn slash_validator(
+        &self,
+        memo: String,
+        first: Header,
+        second: Header,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::SlashValidator {
+                first: Box::new(first),
+                second: Box::new(second),
+            },
+            nonce,
+            fee,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
     pub fn generate_random(
         &self,
         epoch: u32,
@@ -136,6 +198,27 @@ impl TxBuilder {
             state_delta: None,
         }
     }
+    pub fn unregister_validator(
+        &self,
+        memo: String,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::UnregisterStaker,
+            nonce,
+            fee,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
     #[cfg(feature = "client")]
     pub fn claim_validator(
         &self,
@@ -154,6 +237,84 @@ impl TxBuilder {
         claim.sig = Signature::Signed(Signer::sign(&self.private_key, &bytes));
         claim
     }
+    pub fn register_multisig(
+        &self,
+        memo: String,
+        policy: MultisigPolicy,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::RegisterMultisig { policy },
+            nonce,
+            fee,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn create_vesting(
+        &self,
+        memo: String,
+        beneficiary: Address,
+        amount: Money,
+        unlock: LockTime,
+        linear_until: Option<LockTime>,
+        fee: Money,
+        nonce: u32,
+    ) -> (TransactionAndDelta, VestingId) {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::CreateVesting {
+                vesting: Vesting {
+                    beneficiary,
+                    amount,
+                    unlock,
+                    linear_until,
+                },
+            },
+            nonce,
+            fee,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+
+        let vesting_id = VestingId::new(&tx);
+        (
+            TransactionAndDelta {
+                tx,
+                state_delta: None,
+            },
+            vesting_id,
+        )
+    }
+    pub fn claim_vesting(
+        &self,
+        memo: String,
+        vesting_id: VestingId,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::ClaimVesting { vesting_id },
+            nonce,
+            fee,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
     pub fn create_transaction(
         &self,
         memo: String,
@@ -164,6 +325,7 @@ impl TxBuilder {
     ) -> TransactionAndDelta {
         self.create_multi_transaction(memo, vec![RegularSendEntry { dst, amount }], fee, nonce)
     }
+    #[allow(clippy::too_many_arguments)]
     pub fn create_token(
         &self,
         memo: String,
@@ -172,6 +334,8 @@ impl TxBuilder {
         supply: Amount,
         decimals: u8,
         minter: Option<Address>,
+        max_supply: Option<Amount>,
+        metadata: Option<TokenMetadata>,
         fee: Money,
         nonce: u32,
     ) -> (TransactionAndDelta, TokenId) {
@@ -185,6 +349,8 @@ impl TxBuilder {
                     minter,
                     supply,
                     decimals,
+                    max_supply,
+                    metadata,
                 },
             },
             nonce,
@@ -202,45 +368,103 @@ impl TxBuilder {
             token_id,
         )
     }
-    pub fn create_multi_transaction(
+    pub fn update_token(
         &self,
         memo: String,
-        entries: Vec<RegularSendEntry>,
+        token_id: TokenId,
+        update: TokenUpdate,
         fee: Money,
         nonce: u32,
     ) -> TransactionAndDelta {
         let mut tx = Transaction {
             memo,
             src: Some(self.get_address()),
-            data: TransactionData::RegularSend { entries },
+            data: TransactionData::UpdateToken { token_id, update },
             nonce,
             fee,
             sig: Signature::Unsigned,
         };
         self.sign_tx(&mut tx);
+
         TransactionAndDelta {
             tx,
             state_delta: None,
         }
     }
-    pub fn create_mpn_transaction(
+    pub fn approve(
         &self,
-        to: MpnAddress,
+        memo: String,
+        spender: Address,
         amount: Money,
         fee: Money,
         nonce: u32,
-    ) -> zk::MpnTransaction {
-        let mut tx = zk::MpnTransaction {
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::Approve {
+                spender,
+                token_id: amount.token_id,
+                amount: amount.amount,
+            },
             nonce,
+            fee,
+            sig: Signature::Unsigned,
+        };
+        self.sign_tx(&mut tx);
 
-            src_pub_key: self.get_zk_address(),
-            dst_pub_key: to.pub_key,
-
-            amount,
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn transfer_from(
+        &self,
+        memo: String,
+        owner: Address,
+        entries: Vec<RegularSendEntry>,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = Transaction {
+            memo,
+            src: Some(self.get_address()),
+            data: TransactionData::TransferFrom { owner, entries },
+            nonce,
             fee,
-            sig: Default::default(),
+            sig: Signature::Unsigned,
         };
-        tx.sign(&self.zk_private_key);
+        self.sign_tx(&mut tx);
+
+        TransactionAndDelta {
+            tx,
+            state_delta: None,
+        }
+    }
+    pub fn create_multi_transaction(
+        &self,
+        memo: String,
+        entries: Vec<RegularSendEntry>,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        let mut tx = self
+            .watch_only()
+            .create_multi_transaction(memo, entries, fee, nonce);
+        self.sign_tx(&mut tx.tx);
+        tx
+    }
+    pub fn create_mpn_transaction(
+        &self,
+        to: MpnAddress,
+        amount: Money,
+        fee: Money,
+        nonce: u32,
+    ) -> zk::MpnTransaction {
+        let mut tx = self
+            .watch_only()
+            .create_mpn_transaction(to, amount, fee, nonce);
+        self.sign_mpn_transaction(&mut tx);
         tx
     }
     pub fn create_contract(
@@ -314,34 +538,11 @@ impl TxBuilder {
         amount: Money,
         fee: Money,
     ) -> MpnDeposit {
-        let mut calldata_builder =
-            zk::ZkStateBuilder::<crate::core::ZkHasher>::new(zk::MPN_DEPOSIT_STATE_MODEL.clone());
-        let pk = to.pub_key.0.decompress();
-        calldata_builder
-            .batch_set(&zk::ZkDeltaPairs(
-                [
-                    (zk::ZkDataLocator(vec![0]), Some(pk.0)),
-                    (zk::ZkDataLocator(vec![1]), Some(pk.1)),
-                ]
-                .into(),
-            ))
-            .unwrap();
-        let mut tx = ContractDeposit {
-            memo,
-            src: self.get_address(),
-            contract_id,
-            deposit_circuit_id: 0,
-            calldata: calldata_builder.compress().unwrap().state_hash,
-            nonce,
-            amount,
-            fee,
-            sig: None,
-        };
-        self.sign_deposit(&mut tx);
-        MpnDeposit {
-            zk_address: to.pub_key,
-            payment: tx,
-        }
+        let mut tx = self
+            .watch_only()
+            .deposit_mpn(memo, contract_id, to, nonce, amount, fee);
+        self.sign_deposit(&mut tx.payment);
+        tx
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -0,0 +1,205 @@
+use super::{TxBuilder, WalletError};
+use crate::core::{
+    Address, ContractDeposit, ContractId, GeneralTransaction, Money, MpnAddress, MpnDeposit,
+    RegularSendEntry, Signature, Transaction, TransactionAndDelta, TransactionData,
+};
+use crate::zk;
+
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+/// A wallet that only knows the public keys of an account. Transactions built
+/// by it are unsigned, and should be signed by the holder of the private keys,
+/// possibly on an offline machine.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WatchOnlyWallet {
+    pub address: Address,
+    pub mpn_address: MpnAddress,
+}
+
+impl WatchOnlyWallet {
+    pub fn new(address: Address, mpn_address: MpnAddress) -> Self {
+        Self {
+            address,
+            mpn_address,
+        }
+    }
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Option<Self>, WalletError> {
+        if let Ok(mut f) = File::open(&path) {
+            let mut bytes = Vec::new();
+            f.read_to_end(&mut bytes)?;
+            Ok(Some(bincode::deserialize(&bytes)?))
+        } else {
+            Ok(None)
+        }
+    }
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), WalletError> {
+        File::create(path)?.write_all(&bincode::serialize(self)?)?;
+        Ok(())
+    }
+    pub fn create_transaction(
+        &self,
+        memo: String,
+        dst: Address,
+        amount: Money,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        self.create_multi_transaction(memo, vec![RegularSendEntry { dst, amount }], fee, nonce)
+    }
+    pub fn create_multi_transaction(
+        &self,
+        memo: String,
+        entries: Vec<RegularSendEntry>,
+        fee: Money,
+        nonce: u32,
+    ) -> TransactionAndDelta {
+        TransactionAndDelta {
+            tx: Transaction {
+                memo,
+                src: Some(self.address.clone()),
+                data: TransactionData::RegularSend { entries },
+                nonce,
+                fee,
+                sig: Signature::Unsigned,
+            },
+            state_delta: None,
+        }
+    }
+    pub fn create_mpn_transaction(
+        &self,
+        to: MpnAddress,
+        amount: Money,
+        fee: Money,
+        nonce: u32,
+    ) -> zk::MpnTransaction {
+        zk::MpnTransaction {
+            nonce,
+
+            src_pub_key: self.mpn_address.pub_key.clone(),
+            dst_pub_key: to.pub_key,
+
+            amount,
+            fee,
+            sig: Default::default(),
+        }
+    }
+    pub fn deposit_mpn(
+        &self,
+        memo: String,
+        contract_id: ContractId,
+        to: MpnAddress,
+        nonce: u32,
+        amount: Money,
+        fee: Money,
+    ) -> MpnDeposit {
+        let mut calldata_builder =
+            zk::ZkStateBuilder::<crate::core::ZkHasher>::new(zk::MPN_DEPOSIT_STATE_MODEL.clone());
+        let pk = to.pub_key.0.decompress();
+        calldata_builder
+            .batch_set(&zk::ZkDeltaPairs(
+                [
+                    (zk::ZkDataLocator(vec![0]), Some(pk.0)),
+                    (zk::ZkDataLocator(vec![1]), Some(pk.1)),
+                ]
+                .into(),
+            ))
+            .unwrap();
+        MpnDeposit {
+            zk_address: to.pub_key,
+            payment: ContractDeposit {
+                memo,
+                src: self.address.clone(),
+                contract_id,
+                deposit_circuit_id: 0,
+                calldata: calldata_builder.compress().unwrap().state_hash,
+                nonce,
+                amount,
+                fee,
+                sig: None,
+            },
+        }
+    }
+}
+
+/// A transaction built by a watch-only wallet, waiting to be signed
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum UnsignedTransaction {
+    TransactionAndDelta(Box<TransactionAndDelta>),
+    MpnDeposit(Box<MpnDeposit>),
+    MpnTransaction(Box<zk::MpnTransaction>),
+}
+
+impl From<TransactionAndDelta> for UnsignedTransaction {
+    fn from(tx: TransactionAndDelta) -> Self {
+        Self::TransactionAndDelta(Box::new(tx))
+    }
+}
+
+impl From<MpnDeposit> for UnsignedTransaction {
+    fn from(tx: MpnDeposit) -> Self {
+        Self::MpnDeposit(Box::new(tx))
+    }
+}
+
+impl From<zk::MpnTransaction> for UnsignedTransaction {
+    fn from(tx: zk::MpnTransaction) -> Self {
+        Self::MpnTransaction(Box::new(tx))
+    }
+}
+
+impl UnsignedTransaction {
+    /// Signs the transaction, given that its source account belongs to the
+    /// given builder
+    pub fn sign(self, tx_builder: &TxBuilder) -> Result<GeneralTransaction, WalletError> {
+        let signed: GeneralTransaction = match self {
+            UnsignedTransaction::TransactionAndDelta(mut tx_delta) => {
+                if tx_delta.tx.src != Some(tx_builder.get_address()) {
+                    return Err(WalletError::SignerMismatch);
+                }
+                tx_delta.tx.sig = Signature::Unsigned;
+                tx_builder.sign_tx(&mut tx_delta.tx);
+                (*tx_delta).into()
+            }
+            UnsignedTransaction::MpnDeposit(mut deposit) => {
+                if deposit.payment.src != tx_builder.get_address() {
+                    return Err(WalletError::SignerMismatch);
+                }
+                deposit.payment.sig = None;
+                tx_builder.sign_deposit(&mut deposit.payment);
+                (*deposit).into()
+            }
+            UnsignedTransaction::MpnTransaction(mut tx) => {
+                if tx.src_pub_key != tx_builder.get_zk_address() {
+                    return Err(WalletError::SignerMismatch);
+                }
+                tx_builder.sign_mpn_transaction(&mut tx);
+                (*tx).into()
+            }
+        };
+        if !signed.verify_signature() {
+            return Err(WalletError::SignerMismatch);
+        }
+        Ok(signed)
+    }
+}
+
+/// Reads a list of transactions from a portable file
+pub fn read_transactions<T: for<'de> Deserialize<'de>, P: AsRef<Path>>(
+    path: P,
+) -> Result<Vec<T>, WalletError> {
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+    Ok(bincode::deserialize(&bytes)?)
+}
+
+/// Writes a list of transactions into a portable file
+pub fn write_transactions<T: Serialize, P: AsRef<Path>>(
+    path: P,
+    txs: &[T],
+) -> Result<(), WalletError> {
+    File::create(path)?.write_all(&bincode::serialize(txs)?)?;
+    Ok(())
+}
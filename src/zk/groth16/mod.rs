@@ -1,7 +1,11 @@
 pub mod gadgets;
 
 use super::ZkScalar;
-use bls12_381::{Bls12, G1Affine as BellmanG1, G2Affine as BellmanG2, Scalar as BellmanFr};
+use bls12_381::{
+    multi_miller_loop, Bls12, G1Affine as BellmanG1, G1Projective, G2Affine as BellmanG2,
+    G2Prepared, Gt, Scalar as BellmanFr,
+};
+use ff::Field;
 use serde::{Deserialize, Serialize};
 
 impl From<ZkScalar> for BellmanFr {
@@ -64,15 +68,20 @@ impl From<bellman::groth16::VerifyingKey<Bls12>> for Groth16VerifyingKey {
     }
 }
 
-pub fn groth16_verify(
-    vk: &Groth16VerifyingKey,
-    prev_height: u64,
-    prev_state: ZkScalar,
-    aux_data: ZkScalar,
-    next_state: ZkScalar,
-    proof: &Groth16Proof,
-) -> bool {
-    let (vk, proof) = unsafe {
+impl From<bellman::groth16::Proof<Bls12>> for Groth16Proof {
+    fn from(proof: bellman::groth16::Proof<Bls12>) -> Self {
+        unsafe {
+            Self {
+                a: std::mem::transmute::<BellmanG1, (Fp, Fp, bool)>(proof.a),
+                b: std::mem::transmute::<BellmanG2, ((Fp, Fp), (Fp, Fp), bool)>(proof.b),
+                c: std::mem::transmute::<BellmanG1, (Fp, Fp, bool)>(proof.c),
+            }
+        }
+    }
+}
+
+fn to_bellman_vk(vk: &Groth16VerifyingKey) -> bellman::groth16::VerifyingKey<Bls12> {
+    unsafe {
         let alpha_g1 = std::mem::transmute::<(Fp, Fp, bool), BellmanG1>(vk.alpha_g1.clone());
         let beta_g1 = std::mem::transmute::<(Fp, Fp, bool), BellmanG1>(vk.beta_g1.clone());
         let beta_g2 =
@@ -88,23 +97,38 @@ pub fn groth16_verify(
             .cloned()
             .map(|p| std::mem::transmute::<(Fp, Fp, bool), BellmanG1>(p))
             .collect();
-        let proof = bellman::groth16::Proof::<Bls12> {
+        bellman::groth16::VerifyingKey::<Bls12> {
+            alpha_g1,
+            beta_g1,
+            beta_g2,
+            gamma_g2,
+            delta_g1,
+            delta_g2,
+            ic,
+        }
+    }
+}
+
+fn to_bellman_proof(proof: &Groth16Proof) -> bellman::groth16::Proof<Bls12> {
+    unsafe {
+        bellman::groth16::Proof::<Bls12> {
             a: std::mem::transmute::<(Fp, Fp, bool), BellmanG1>(proof.a.clone()),
             b: std::mem::transmute::<((Fp, Fp), (Fp, Fp), bool), BellmanG2>(proof.b.clone()),
             c: std::mem::transmute::<(Fp, Fp, bool), BellmanG1>(proof.c.clone()),
-        };
-        let vk =
-            bellman::groth16::prepare_verifying_key(&bellman::groth16::VerifyingKey::<Bls12> {
-                alpha_g1,
-                beta_g1,
-                beta_g2,
-                gamma_g2,
-                delta_g1,
-                delta_g2,
-                ic,
-            });
-        (vk, proof)
-    };
+        }
+    }
+}
+
+pub fn groth16_verify(
+    vk: &Groth16VerifyingKey,
+    prev_height: u64,
+    prev_state: ZkScalar,
+    aux_data: ZkScalar,
+    next_state: ZkScalar,
+    proof: &Groth16Proof,
+) -> bool {
+    let vk = bellman::groth16::prepare_verifying_key(&to_bellman_vk(vk));
+    let proof = to_bellman_proof(proof);
     bellman::groth16::verify_proof(
         &vk,
         &proof,
@@ -117,3 +141,71 @@ pub fn groth16_verify(
     )
     .is_ok()
 }
+
+/// Public inputs and proof of a single contract update
+#[derive(Debug, Clone)]
+pub struct Groth16BatchItem {
+    pub prev_height: u64,
+    pub prev_state: ZkScalar,
+    pub aux_data: ZkScalar,
+    pub next_state: ZkScalar,
+    pub proof: Groth16Proof,
+}
+
+/// Verifies multiple proofs of the same verifying key at once, by checking a
+/// random linear combination of their pairing equations:
+///
+/// prod e(r_i * A_i, B_i) = e(sum r_i * alpha, beta) * e(sum r_i * IC_i, gamma) * e(sum r_i * C_i, delta)
+///
+/// Returns false if any of the proofs is invalid (With overwhelming probability)
+pub fn groth16_verify_batch(vk: &Groth16VerifyingKey, items: &[Groth16BatchItem]) -> bool {
+    if items.is_empty() {
+        return true;
+    }
+    let vk = to_bellman_vk(vk);
+    if vk.ic.len() != 5 {
+        return false;
+    }
+
+    let mut rng = rand::thread_rng();
+    let mut sum_r = BellmanFr::zero();
+    let mut sum_ic = G1Projective::identity();
+    let mut sum_c = G1Projective::identity();
+    let mut scaled_a = Vec::with_capacity(items.len());
+    let mut prepared_b = Vec::with_capacity(items.len());
+    for item in items {
+        let proof = to_bellman_proof(&item.proof);
+        let r = BellmanFr::random(&mut rng);
+        let inputs: [BellmanFr; 4] = [
+            item.prev_height.into(),
+            item.prev_state.into(),
+            item.aux_data.into(),
+            item.next_state.into(),
+        ];
+        let mut ic = G1Projective::from(vk.ic[0]);
+        for (input, base) in inputs.iter().zip(vk.ic.iter().skip(1)) {
+            ic += base * input;
+        }
+        sum_ic += ic * r;
+        sum_c += proof.c * r;
+        sum_r += r;
+        scaled_a.push(BellmanG1::from(proof.a * r));
+        prepared_b.push(G2Prepared::from(proof.b));
+    }
+
+    let alpha = BellmanG1::from(-(vk.alpha_g1 * sum_r));
+    let ic = BellmanG1::from(-sum_ic);
+    let c = BellmanG1::from(-sum_c);
+    let beta = G2Prepared::from(vk.beta_g2);
+    let gamma = G2Prepared::from(vk.gamma_g2);
+    let delta = G2Prepared::from(vk.delta_g2);
+
+    let mut terms = scaled_a.iter().zip(prepared_b.iter()).collect::<Vec<_>>();
+    terms.push((&alpha, &beta));
+    terms.push((&ic, &gamma));
+    terms.push((&c, &delta));
+    multi_miller_loop(&terms).final_exponentiation() == Gt::identity()
+}
+
+#[cfg(test)]
+mod test;
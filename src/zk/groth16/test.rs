@@ -0,0 +1,159 @@
+use super::*;
+use bellman::{groth16, Circuit, ConstraintSystem, SynthesisError};
+use rand::rngs::OsRng;
+use std::time::Instant;
+
+/// Proves knowledge of nothing, but enforces `next_state = prev_height + prev_state + aux_data`
+#[derive(Clone)]
+struct SumCircuit {
+    inputs: Option<[BellmanFr; 4]>,
+}
+
+impl Circuit<BellmanFr> for SumCircuit {
+    fn synthesize<CS: ConstraintSystem<BellmanFr>>(
+        self,
+        cs: &mut CS,
+    ) -> Result<(), SynthesisError> {
+        let mut vars = Vec::new();
+        for i in 0..4 {
+            vars.push(cs.alloc_input(
+                || format!("input {}", i),
+                || {
+                    self.inputs
+                        .map(|v| v[i])
+                        .ok_or(SynthesisError::AssignmentMissing)
+                },
+            )?);
+        }
+        cs.enforce(
+            || "sum",
+            |lc| lc + vars[0] + vars[1] + vars[2],
+            |lc| lc + CS::one(),
+            |lc| lc + vars[3],
+        );
+        Ok(())
+    }
+}
+
+fn setup() -> (Groth16VerifyingKey, bellman::groth16::Parameters<Bls12>) {
+    let params =
+        groth16::generate_random_parameters::<Bls12, _, _>(SumCircuit { inputs: None }, &mut OsRng)
+            .unwrap();
+    (params.vk.clone().into(), params)
+}
+
+fn prove(params: &bellman::groth16::Parameters<Bls12>, seed: u64) -> Groth16BatchItem {
+    let prev_state = ZkScalar::from(seed * 3);
+    let aux_data = ZkScalar::from(seed * 7);
+    let next_state = ZkScalar::from(seed) + prev_state + aux_data;
+    let inputs = [
+        ZkScalar::from(seed).into(),
+        prev_state.into(),
+        aux_data.into(),
+        next_state.into(),
+    ];
+    let proof = groth16::create_random_proof(
+        SumCircuit {
+            inputs: Some(inputs),
+        },
+        params,
+        &mut OsRng,
+    )
+    .unwrap();
+    Groth16BatchItem {
+        prev_height: seed,
+        prev_state,
+        aux_data,
+        next_state,
+        proof: proof.into(),
+    }
+}
+
+fn verify_one_by_one(vk: &Groth16VerifyingKey, items: &[Groth16BatchItem]) -> bool {
+    items.iter().all(|item| {
+        groth16_verify(
+            vk,
+            item.prev_height,
+            item.prev_state,
+            item.aux_data,
+            item.next_state,
+            &item.proof,
+        )
+    })
+}
+
+#[test]
+fn test_groth16_verify_batch() {
+    let (vk, params) = setup();
+    let mut items = (1..=5).map(|i| prove(&params, i)).collect::<Vec<_>>();
+
+    assert!(verify_one_by_one(&vk, &items));
+    assert!(groth16_verify_batch(&vk, &items));
+    assert!(groth16_verify_batch(&vk, &[]));
+
+    // A single wrong public input breaks the whole batch
+    items[3].next_state = items[3].next_state + ZkScalar::from(1);
+    assert!(!groth16_verify_batch(&vk, &items));
+
+    // Valid proofs of the wrong verifying key
+    let (other_vk, _) = setup();
+    assert!(!groth16_verify_batch(&other_vk, &items[..3]));
+}
+
+#[test]
+fn test_check_proofs() {
+    let (vk, params) = setup();
+    let checks = (1..=3)
+        .map(|i| {
+            let item = prove(&params, i);
+            crate::zk::ZkProofCheck {
+                vk: crate::zk::ZkVerifierKey::Groth16(Box::new(vk.clone())),
+                prev_height: item.prev_height,
+                prev_state: item.prev_state,
+                aux_data: item.aux_data,
+                next_state: item.next_state,
+                proof: crate::zk::ZkProof::Groth16(Box::new(item.proof)),
+            }
+        })
+        .collect::<Vec<_>>();
+    assert!(crate::zk::check_proofs(&checks));
+
+    let mut with_dummy = checks.clone();
+    with_dummy.push(crate::zk::ZkProofCheck {
+        vk: crate::zk::ZkVerifierKey::Dummy,
+        prev_height: 0,
+        prev_state: ZkScalar::from(0),
+        aux_data: ZkScalar::from(0),
+        next_state: ZkScalar::from(0),
+        proof: crate::zk::ZkProof::Dummy(false),
+    });
+    assert!(!crate::zk::check_proofs(&with_dummy));
+
+    let mut invalid = checks;
+    invalid[1].prev_height += 1;
+    assert!(!crate::zk::check_proofs(&invalid));
+    assert_eq!(invalid.iter().position(|c| !c.check()), Some(1));
+}
+
+/// Run with `cargo test --release bench_groth16_verify_batch -- --ignored --nocapture`
+#[test]
+#[ignore]
+fn bench_groth16_verify_batch() {
+    let (vk, params) = setup();
+    for count in [1, 4, 16, 64] {
+        let items = (1..=count).map(|i| prove(&params, i)).collect::<Vec<_>>();
+
+        let start = Instant::now();
+        assert!(verify_one_by_one(&vk, &items));
+        let one_by_one = start.elapsed();
+
+        let start = Instant::now();
+        assert!(groth16_verify_batch(&vk, &items));
+        let batch = start.elapsed();
+
+        println!(
+            "{} proofs: one-by-one {:?}, batch {:?}",
+            count, one_by_one, batch
+        );
+    }
+}
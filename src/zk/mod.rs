@@ -16,6 +16,7 @@ pub mod groth16;
 pub mod poseidon;
 
 use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 pub struct LruCache<K: std::hash::Hash + Clone, V> {
     capacity: usize,
@@ -146,8 +147,10 @@ pub enum ZkError {
     ScalarBiggerThanU64,
 }
 
-#[derive(Debug, Clone, Default)]
-pub struct ZkStateProof(Vec<ZkScalar>);
+/// Siblings of a piece of data and of all its parents, from the data up to the
+/// root of the state
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZkStateProof(pub Vec<ZkScalar>);
 
 pub trait ZkHasher: Clone + Default {
     const MAX_ARITY: usize;
@@ -183,6 +186,67 @@ pub fn check_proof(
     }
 }
 
+/// A proof check that can be postponed, so that it gets verified in a batch
+#[derive(Debug, Clone)]
+pub struct ZkProofCheck {
+    pub vk: ZkVerifierKey,
+    pub prev_height: u64,
+    pub prev_state: ZkScalar,
+    pub aux_data: ZkScalar,
+    pub next_state: ZkScalar,
+    pub proof: ZkProof,
+}
+
+impl ZkProofCheck {
+    pub fn check(&self) -> bool {
+        check_proof(
+            &self.vk,
+            self.prev_height,
+            self.prev_state,
+            self.aux_data,
+            self.next_state,
+            &self.proof,
+        )
+    }
+}
+
+/// Checks all of the proofs, verifying the Groth16 proofs of the same verifying
+/// key in a single batch
+pub fn check_proofs(checks: &[ZkProofCheck]) -> bool {
+    let mut batches: Vec<(
+        &groth16::Groth16VerifyingKey,
+        Vec<groth16::Groth16BatchItem>,
+    )> = Vec::new();
+    for check in checks {
+        match (&check.vk, &check.proof) {
+            #[allow(irrefutable_let_patterns)]
+            (ZkVerifierKey::Groth16(vk), ZkProof::Groth16(proof)) => {
+                let item = groth16::Groth16BatchItem {
+                    prev_height: check.prev_height,
+                    prev_state: check.prev_state,
+                    aux_data: check.aux_data,
+                    next_state: check.next_state,
+                    proof: *proof.clone(),
+                };
+                if let Some((_, items)) = batches.iter_mut().find(|(k, _)| **k == **vk) {
+                    items.push(item);
+                } else {
+                    batches.push((vk, vec![item]));
+                }
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                if !check.check() {
+                    return false;
+                }
+            }
+        }
+    }
+    batches
+        .iter()
+        .all(|(vk, items)| groth16::groth16_verify_batch(vk, items))
+}
+
 lazy_static! {
     static ref ZKSCALAR_MODULUS: BigUint = BigUint::from_str(
         "52435875175126190479447740508185965837690552500527637822603658699938581184513"
@@ -484,6 +548,9 @@ lazy_static! {
         Arc::new(Mutex::new(LruCache::new(64)));
 }
 
+pub static POSEIDON_CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+pub static POSEIDON_CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+
 #[derive(Debug, Clone, PartialEq, Eq, std::hash::Hash, Default)]
 pub struct PoseidonHasher;
 impl ZkHasher for PoseidonHasher {
@@ -492,8 +559,10 @@ impl ZkHasher for PoseidonHasher {
         let mut h = POSEIDON_CACHE.lock().unwrap();
         let vals_vec = vals.to_vec();
         if let Some(v) = h.get(&vals_vec) {
+            POSEIDON_CACHE_HITS.fetch_add(1, Ordering::Relaxed);
             *v
         } else {
+            POSEIDON_CACHE_MISSES.fetch_add(1, Ordering::Relaxed);
             let v = poseidon::poseidon(vals);
             h.insert(vals_vec, v);
             v
@@ -274,6 +274,38 @@ impl<H: ZkHasher> KvStoreStateManager<H> {
         }
     }
 
+    /// Proves the data at the given locator against the root of the state
+    pub fn prove_data<K: KvStore>(
+        db: &K,
+        id: ContractId,
+        locator: &ZkDataLocator,
+    ) -> Result<ZkStateProof, StateManagerError> {
+        let contract_type = Self::type_of(db, id)?;
+        contract_type.locate(locator)?;
+        let mut proof = Vec::new();
+        let mut locator = locator.clone();
+        while let Some(curr_loc) = locator.0.pop() {
+            match contract_type.locate(&locator)? {
+                ZkStateModel::List { .. } => {
+                    for proof_part in Self::prove(db, id, locator.clone(), curr_loc)? {
+                        proof.extend(proof_part);
+                    }
+                }
+                ZkStateModel::Struct { field_types } => {
+                    for field_index in 0..field_types.len() as u64 {
+                        if field_index != curr_loc {
+                            proof.push(Self::get_data(db, id, &locator.index(field_index))?);
+                        }
+                    }
+                }
+                ZkStateModel::Scalar => {
+                    return Err(StateManagerError::NonTreeLocatorError);
+                }
+            }
+        }
+        Ok(ZkStateProof(proof))
+    }
+
     pub fn type_of<K: KvStore>(db: &K, id: ContractId) -> Result<ZkStateModel, StateManagerError> {
         let cont: ZkContract = db
             .get(keys::contract(&id))?